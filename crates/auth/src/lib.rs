@@ -11,7 +11,9 @@ use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use decomp_dev_core::{
     AppError,
     config::{Config, GitHubConfig},
+    models::{MaintainerRole, Project},
 };
+use decomp_dev_db::Database;
 use decomp_dev_github::graphql::{
     CurrentUserResponse, RepositoryPermission, fetch_current_user, fetch_simple_current_user,
 };
@@ -55,6 +57,9 @@ pub struct CurrentUser {
     pub data: CurrentUserResponse,
     #[serde(skip, default)]
     pub super_admin: bool,
+    /// Roles granted on decomp.dev, keyed by project ID
+    #[serde(skip, default)]
+    pub maintainer_roles: Arc<Vec<(u64, MaintainerRole)>>,
 }
 
 impl CurrentUser {
@@ -72,20 +77,42 @@ impl CurrentUser {
         }
     }
 
-    pub fn permissions_for_repo(&self, id: u64) -> RepositoryPermission {
+    /// Combines the user's permission on the project's GitHub repository with their
+    /// decomp.dev maintainer role.
+    pub fn permissions_for_project(&self, project: &Project) -> RepositoryPermission {
         if self.super_admin {
             return RepositoryPermission::Admin;
         }
-        self.data
+        let github_permission = self
+            .data
             .repositories
             .iter()
-            .find(|r| r.id == id)
+            .find(|r| r.id == project.repository_id)
             .map(|r| r.permission.clone())
-            .unwrap_or(RepositoryPermission::None)
+            .unwrap_or(RepositoryPermission::None);
+        let maintainer_permission = match self.maintainer_role(project.id) {
+            Some(MaintainerRole::Admin) => RepositoryPermission::Admin,
+            Some(MaintainerRole::Maintainer) => RepositoryPermission::Maintain,
+            None => RepositoryPermission::None,
+        };
+        github_permission.max(maintainer_permission)
+    }
+
+    pub fn maintainer_role(&self, id: u64) -> Option<MaintainerRole> {
+        self.maintainer_roles
+            .iter()
+            .find(|(project_id, _)| *project_id == id)
+            .map(|(_, role)| *role)
+    }
+
+    pub fn can_manage_project(&self, project: &Project) -> bool {
+        matches!(self.permissions_for_project(project), RepositoryPermission::Admin)
+            || self.maintainer_role(project.id).is_some()
     }
 
-    pub fn can_manage_repo(&self, id: u64) -> bool {
-        matches!(self.permissions_for_repo(id), RepositoryPermission::Admin)
+    /// Whether the user can add or remove project maintainers.
+    pub fn can_manage_maintainers(&self, project: &Project) -> bool {
+        matches!(self.permissions_for_project(project), RepositoryPermission::Admin)
     }
 }
 
@@ -214,7 +241,7 @@ async fn fetch_access_token(config: &GitHubConfig, code: &str) -> Result<Current
     } else {
         tracing::info!("Logged in as @{} ({} repos)", data.login, data.repositories.len());
     }
-    Ok(CurrentUser { oauth: Some(oauth), data, super_admin })
+    Ok(CurrentUser { oauth: Some(oauth), data, super_admin, maintainer_roles: Default::default() })
 }
 
 async fn refresh_access_token(
@@ -241,12 +268,18 @@ async fn refresh_access_token(
     let oauth = StoredOAuth::from(oauth);
     let super_admin = config.super_admin_ids.contains(&prev_auth.data.id);
     tracing::info!("Refreshed token for @{}", prev_auth.data.login);
-    Ok(CurrentUser { oauth: Some(oauth), data: prev_auth.data.clone(), super_admin })
+    Ok(CurrentUser {
+        oauth: Some(oauth),
+        data: prev_auth.data.clone(),
+        super_admin,
+        maintainer_roles: Default::default(),
+    })
 }
 
 impl<S> FromRequestParts<S> for CurrentUser
 where
     Arc<Config>: FromRef<S>,
+    Arc<Database>: FromRef<S>,
     S: Send + Sync,
 {
     type Rejection = Response;
@@ -281,6 +314,7 @@ where
 impl<S> OptionalFromRequestParts<S> for CurrentUser
 where
     Arc<Config>: FromRef<S>,
+    Arc<Database>: FromRef<S>,
     S: Send + Sync,
 {
     type Rejection = (StatusCode, &'static str);
@@ -344,7 +378,7 @@ where
                     // Refresh token expired
                     return Ok(None);
                 }
-                let current_user =
+                let mut current_user =
                     match refresh_access_token(&config.github, refresh_token, &user).await {
                         Ok(current_user) => current_user,
                         Err(e) => {
@@ -355,10 +389,100 @@ where
                 if let Err(e) = session.insert(CURRENT_USER, current_user.clone()).await {
                     tracing::error!("Failed to insert user into session: {}", e);
                 }
+                current_user.maintainer_roles = maintainer_roles(state, &current_user).await;
                 return Ok(Some(current_user));
             }
             return Ok(None);
         }
+        user.maintainer_roles = maintainer_roles(state, &user).await;
         Ok(Some(user))
     }
 }
+
+async fn maintainer_roles<S>(state: &S, user: &CurrentUser) -> Arc<Vec<(u64, MaintainerRole)>>
+where Arc<Database>: FromRef<S> {
+    let db = Arc::<Database>::from_ref(state);
+    match db.get_maintainer_roles(user.data.id).await {
+        Ok(roles) => roles,
+        Err(e) => {
+            tracing::error!("Failed to fetch maintainer roles: {:?}", e);
+            Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use decomp_dev_github::graphql::CurrentUserRepository;
+
+    use super::*;
+
+    fn user(
+        permission: Option<RepositoryPermission>,
+        maintainer_roles: Vec<(u64, MaintainerRole)>,
+    ) -> CurrentUser {
+        CurrentUser {
+            oauth: None,
+            data: CurrentUserResponse {
+                id: 1,
+                login: "user".to_string(),
+                url: "https://github.com/user".to_string(),
+                repositories: permission
+                    .into_iter()
+                    .map(|permission| CurrentUserRepository {
+                        id: 100,
+                        owner: "zeldaret".to_string(),
+                        name: "tp".to_string(),
+                        permission,
+                    })
+                    .collect(),
+            },
+            super_admin: false,
+            maintainer_roles: Arc::new(maintainer_roles),
+        }
+    }
+
+    #[test]
+    fn test_project_permissions() {
+        let project = Project { id: 100, repository_id: 100, ..Default::default() };
+        // A second project sharing the repository
+        let sibling = Project { id: 1_000_001, repository_id: 100, ..Default::default() };
+
+        // Repository admins manage every project of the repository, including maintainers
+        let admin = user(Some(RepositoryPermission::Admin), vec![]);
+        for project in [&project, &sibling] {
+            assert_eq!(admin.permissions_for_project(project), RepositoryPermission::Admin);
+            assert!(admin.can_manage_project(project));
+            assert!(admin.can_manage_maintainers(project));
+        }
+
+        // Other repository permissions don't grant management
+        let writer = user(Some(RepositoryPermission::Write), vec![]);
+        assert_eq!(writer.permissions_for_project(&project), RepositoryPermission::Write);
+        assert!(!writer.can_manage_project(&project));
+        assert!(!user(None, vec![]).can_manage_project(&project));
+
+        // Maintainers manage only their project, and can't manage its maintainers
+        let maintainer =
+            user(Some(RepositoryPermission::Read), vec![(project.id, MaintainerRole::Maintainer)]);
+        assert_eq!(maintainer.permissions_for_project(&project), RepositoryPermission::Maintain);
+        assert!(maintainer.can_manage_project(&project));
+        assert!(!maintainer.can_manage_maintainers(&project));
+        assert_eq!(maintainer.permissions_for_project(&sibling), RepositoryPermission::Read);
+        assert!(!maintainer.can_manage_project(&sibling));
+
+        // Maintainer admins also manage maintainers, without any repository permission
+        let maintainer_admin = user(None, vec![(project.id, MaintainerRole::Admin)]);
+        assert_eq!(maintainer_admin.permissions_for_project(&project), RepositoryPermission::Admin);
+        assert!(maintainer_admin.can_manage_maintainers(&project));
+        assert!(!maintainer_admin.can_manage_project(&sibling));
+
+        // The higher of both permissions applies
+        let both =
+            user(Some(RepositoryPermission::Admin), vec![(project.id, MaintainerRole::Maintainer)]);
+        assert_eq!(both.permissions_for_project(&project), RepositoryPermission::Admin);
+
+        let super_admin = CurrentUser { super_admin: true, ..user(None, vec![]) };
+        assert!(super_admin.can_manage_maintainers(&sibling));
+    }
+}
@@ -8,12 +8,17 @@ pub struct Config {
     pub openai: Option<OpenAiConfig>,
     #[serde(default)]
     pub worker: WorkerConfig,
+    pub telemetry: Option<TelemetryConfig>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ServerConfig {
     pub port: u16,
     pub jobs_port: Option<u16>,
+    /// Bearer token required to fetch `/metrics` from the public server. Without it, metrics
+    /// are only served by the jobs server.
+    #[serde(default)]
+    pub metrics_token: Option<String>,
     #[serde(default)]
     pub dev_mode: bool,
 }
@@ -68,3 +73,20 @@ impl Default for WorkerConfig {
         Self { workflow_run_concurrency: 3, refresh_project_concurrency: 3, retry_attempts: 5 }
     }
 }
+
+/// OpenTelemetry trace export. Disabled when omitted.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TelemetryConfig {
+    /// OTLP gRPC endpoint, e.g. `http://localhost:4317`.
+    pub otlp_endpoint: String,
+    /// Service name reported with each span.
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+    /// Fraction of new traces to sample, from 0.0 to 1.0.
+    #[serde(default = "default_sample_ratio")]
+    pub sample_ratio: f64,
+}
+
+fn default_service_name() -> String { "decomp-dev".to_string() }
+
+fn default_sample_ratio() -> f64 { 1.0 }
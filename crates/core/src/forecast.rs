@@ -0,0 +1,189 @@
+use serde::Serialize;
+use time::{Duration, UtcDateTime};
+
+/// Number of days of history used for the recent velocity forecast.
+pub const RECENT_WINDOW_DAYS: i64 = 90;
+/// Minimum number of reports required to fit a trend.
+const MIN_SAMPLES: usize = 3;
+/// Two-sided 95% confidence multiplier for the slope's standard error.
+const CONFIDENCE_Z: f64 = 1.96;
+/// Forecasts further out than this are reported as unbounded.
+const MAX_FORECAST_DAYS: f64 = 365.0 * 50.0;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForecastWindow {
+    Recent,
+    AllTime,
+}
+
+impl ForecastWindow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Recent => "recent",
+            Self::AllTime => "all_time",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Recent => "Last 90 days",
+            Self::AllTime => "All time",
+        }
+    }
+}
+
+/// Estimated completion date from a linear fit of progress over time.
+#[derive(Debug, Clone)]
+pub struct Forecast {
+    pub window: ForecastWindow,
+    /// Number of reports the fit is based on
+    pub samples: usize,
+    /// Fitted progress in percentage points per day
+    pub rate_per_day: f64,
+    /// Current progress, as of the latest report
+    pub current_percent: f64,
+    pub latest_timestamp: UtcDateTime,
+    /// Estimated completion, or `None` if progress is not trending upwards
+    pub estimate: Option<UtcDateTime>,
+    /// Completion at the upper bound of the confidence interval
+    pub earliest: Option<UtcDateTime>,
+    /// Completion at the lower bound of the confidence interval, or `None` if unbounded
+    pub latest: Option<UtcDateTime>,
+}
+
+impl Forecast {
+    /// Fits a trend to `(timestamp, percent)` samples, only considering samples at or after
+    /// `since`.
+    pub fn fit(
+        window: ForecastWindow,
+        samples: &[(UtcDateTime, f32)],
+        since: Option<UtcDateTime>,
+    ) -> Option<Self> {
+        let samples = samples
+            .iter()
+            .filter(|(timestamp, _)| since.is_none_or(|since| *timestamp >= since))
+            .collect::<Vec<_>>();
+        if samples.len() < MIN_SAMPLES {
+            return None;
+        }
+        let &&(latest_timestamp, current_percent) =
+            samples.iter().max_by_key(|(timestamp, _)| *timestamp)?;
+        let current_percent = current_percent as f64;
+
+        // Ordinary least squares over time in days
+        let points = samples
+            .iter()
+            .map(|(timestamp, percent)| {
+                (timestamp.unix_timestamp() as f64 / SECONDS_PER_DAY, *percent as f64)
+            })
+            .collect::<Vec<_>>();
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let mut sxx = 0.0;
+        let mut sxy = 0.0;
+        for (x, y) in &points {
+            sxx += (x - mean_x) * (x - mean_x);
+            sxy += (x - mean_x) * (y - mean_y);
+        }
+        if sxx <= f64::EPSILON {
+            return None;
+        }
+        let slope = sxy / sxx;
+        let intercept = mean_y - slope * mean_x;
+        let residuals = points
+            .iter()
+            .map(|(x, y)| {
+                let r = y - (intercept + slope * x);
+                r * r
+            })
+            .sum::<f64>();
+        let std_error = (residuals / (n - 2.0)).sqrt() / sxx.sqrt();
+
+        let remaining = (100.0 - current_percent).max(0.0);
+        let project = |rate: f64| -> Option<UtcDateTime> {
+            if remaining <= 0.0 {
+                return Some(latest_timestamp);
+            }
+            if rate <= 0.0 {
+                return None;
+            }
+            let days = remaining / rate;
+            if !days.is_finite() || days > MAX_FORECAST_DAYS {
+                return None;
+            }
+            Some(latest_timestamp + Duration::seconds_f64(days * SECONDS_PER_DAY))
+        };
+        Some(Self {
+            window,
+            samples: points.len(),
+            rate_per_day: slope,
+            current_percent,
+            latest_timestamp,
+            estimate: project(slope),
+            earliest: project(slope + CONFIDENCE_Z * std_error),
+            latest: project(slope - CONFIDENCE_Z * std_error),
+        })
+    }
+
+    /// Fits both the recent and all-time trends, relative to the latest sample.
+    pub fn fit_all(samples: &[(UtcDateTime, f32)]) -> Vec<Self> {
+        let Some(latest) = samples.iter().map(|(timestamp, _)| *timestamp).max() else {
+            return vec![];
+        };
+        let since = latest - Duration::days(RECENT_WINDOW_DAYS);
+        [
+            Self::fit(ForecastWindow::Recent, samples, Some(since)),
+            Self::fit(ForecastWindow::AllTime, samples, None),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(n: i64) -> UtcDateTime { UtcDateTime::UNIX_EPOCH + Duration::days(n) }
+
+    #[test]
+    fn test_linear_progress() {
+        let samples = (0..=10).map(|n| (day(n), n as f32)).collect::<Vec<_>>();
+        let forecast = Forecast::fit(ForecastWindow::AllTime, &samples, None).unwrap();
+        assert!((forecast.rate_per_day - 1.0).abs() < 1e-6);
+        // A perfect fit has no uncertainty
+        for estimate in [forecast.estimate, forecast.earliest, forecast.latest] {
+            let estimate = estimate.unwrap();
+            assert!((estimate - day(100)).abs() < Duration::minutes(1));
+        }
+    }
+
+    #[test]
+    fn test_stalled_progress() {
+        let samples = (0..=10).map(|n| (day(n), 50.0)).collect::<Vec<_>>();
+        let forecast = Forecast::fit(ForecastWindow::AllTime, &samples, None).unwrap();
+        assert_eq!(forecast.estimate, None);
+    }
+
+    #[test]
+    fn test_recent_window() {
+        // Fast early progress followed by a slow recent trend
+        let mut samples = (0..100).map(|n| (day(n), n as f32 * 0.5)).collect::<Vec<_>>();
+        samples.extend((100..=200).map(|n| (day(n), 50.0 + (n - 100) as f32 * 0.1)));
+        let forecasts = Forecast::fit_all(&samples);
+        assert_eq!(forecasts.len(), 2);
+        assert_eq!(forecasts[0].window, ForecastWindow::Recent);
+        assert!((forecasts[0].rate_per_day - 0.1).abs() < 1e-4);
+        assert!(forecasts[1].rate_per_day > forecasts[0].rate_per_day);
+    }
+
+    #[test]
+    fn test_too_few_samples() {
+        let samples = [(day(0), 1.0), (day(1), 2.0)];
+        assert!(Forecast::fit(ForecastWindow::AllTime, &samples, None).is_none());
+    }
+}
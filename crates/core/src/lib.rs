@@ -1,4 +1,5 @@
 pub mod config;
+pub mod forecast;
 pub mod models;
 pub mod util;
 
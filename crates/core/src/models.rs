@@ -1,6 +1,7 @@
 use std::{borrow::Cow, fmt, str::FromStr, sync::Arc};
 
 use objdiff_core::bindings::report::{Measures, Report, ReportCategory, ReportUnit};
+use regex::{Regex, RegexSet};
 use serde::{Deserialize, Serialize};
 use time::UtcDateTime;
 
@@ -50,8 +51,17 @@ impl fmt::Display for PullReportStyle {
 #[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub struct Project {
     pub id: u64,
+    /// GitHub repository the project's reports are built in
+    pub repository_id: u64,
     pub owner: String,
     pub repo: String,
+    /// Distinguishes projects sharing a repository, `None` for the repository's primary project
+    pub slug: Option<String>,
+    /// Only artifacts with versions starting with this prefix are imported, with the prefix
+    /// removed from the stored version
+    pub artifact_prefix: Option<String>,
+    /// Only versions matching this glob (or `re:` regex) are imported
+    pub version_pattern: Option<String>,
     pub name: Option<String>,
     pub short_name: Option<String>,
     pub default_category: Option<String>,
@@ -68,8 +78,12 @@ impl Default for Project {
     fn default() -> Self {
         Self {
             id: 0,
+            repository_id: 0,
             owner: String::new(),
             repo: String::new(),
+            slug: None,
+            artifact_prefix: None,
+            version_pattern: None,
             name: None,
             short_name: None,
             default_category: None,
@@ -99,7 +113,104 @@ impl Project {
 
     pub fn repo_url(&self) -> String { format!("https://github.com/{}/{}", self.owner, self.repo) }
 
+    /// The repository segment of the project's URLs, `{repo}:{slug}` for projects that share a
+    /// repository with its primary project.
+    pub fn repo_path(&self) -> Cow<'_, str> {
+        match &self.slug {
+            Some(slug) => Cow::Owned(format!("{}:{}", self.repo, slug)),
+            None => Cow::Borrowed(&self.repo),
+        }
+    }
+
+    /// Maps the version of a workflow run artifact to the version stored for this project,
+    /// or `None` if the artifact belongs to another project in the repository.
+    pub fn select_artifact_version(&self, version: &str) -> Option<String> {
+        let version = match &self.artifact_prefix {
+            Some(prefix) => version.strip_prefix(prefix.as_str()).filter(|v| !v.is_empty())?,
+            None => version,
+        };
+        if let Some(pattern) = &self.version_pattern
+            && !version_regex(pattern).is_ok_and(|r| r.is_match(version))
+        {
+            return None;
+        }
+        Some(version.to_string())
+    }
+
+    /// Whether the project selects artifacts by prefix or version pattern.
+    pub fn has_artifact_rules(&self) -> bool {
+        self.artifact_prefix.is_some() || self.version_pattern.is_some()
+    }
+
+    /// Like [`Project::select_artifact_version`], but a project without artifact rules leaves
+    /// artifacts that other projects in the repository select by their rules to them.
+    pub fn claim_artifact_version<'a>(
+        &self,
+        version: &str,
+        siblings: impl IntoIterator<Item = &'a Project>,
+    ) -> Option<String> {
+        if !self.has_artifact_rules()
+            && siblings.into_iter().any(|sibling| {
+                sibling.id != self.id
+                    && sibling.has_artifact_rules()
+                    && sibling.select_artifact_version(version).is_some()
+            })
+        {
+            return None;
+        }
+        self.select_artifact_version(version)
+    }
+
+    /// The artifact version a stored version is built from, the inverse of
+    /// [`Project::select_artifact_version`].
+    pub fn artifact_version(&self, version: &str) -> String {
+        format!("{}{}", self.artifact_prefix.as_deref().unwrap_or_default(), version)
+    }
+
     pub fn default_category(&self) -> &str { self.default_category.as_deref().unwrap_or("all") }
+
+    /// Lists the fields that differ between two versions of a project.
+    /// Pass `None` as `before` to list all set fields of a new project.
+    pub fn diff(before: Option<&Project>, after: &Project) -> Vec<FieldChange> {
+        let before_fields = before.map(Project::audit_fields).unwrap_or_default();
+        after
+            .audit_fields()
+            .into_iter()
+            .filter_map(|(field, after_value)| {
+                let before_value =
+                    before_fields.iter().find(|(f, _)| *f == field).and_then(|(_, v)| v.clone());
+                (before_value != after_value).then(|| FieldChange {
+                    field: field.to_string(),
+                    before: before_value,
+                    after: after_value,
+                })
+            })
+            .collect()
+    }
+
+    fn audit_fields(&self) -> Vec<(&'static str, Option<String>)> {
+        vec![
+            ("owner", Some(self.owner.clone())),
+            ("repo", Some(self.repo.clone())),
+            ("slug", self.slug.clone()),
+            ("artifact_prefix", self.artifact_prefix.clone()),
+            ("version_pattern", self.version_pattern.clone()),
+            ("name", self.name.clone()),
+            ("short_name", self.short_name.clone()),
+            ("default_category", self.default_category.clone()),
+            ("default_version", self.default_version.clone()),
+            ("platform", self.platform.clone()),
+            ("workflow_id", self.workflow_id.clone()),
+            ("enable_pr_comments", Some(self.enable_pr_comments.to_string())),
+            ("pr_report_style", Some(self.pr_report_style.as_str().to_string())),
+            (
+                "header_image_id",
+                self.header_image_id
+                    .map(|id| id.iter().map(|b| format!("{b:02x}")).collect::<String>()),
+            ),
+            ("enabled", Some(self.enabled.to_string())),
+        ]
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize)]
@@ -109,6 +220,8 @@ pub struct ProjectInfo {
     pub report_versions: Vec<String>,
     pub prev_commit: Option<String>,
     pub next_commit: Option<String>,
+    /// Tags assigned by maintainers, sorted by name
+    pub tags: Vec<String>,
 }
 
 impl ProjectInfo {
@@ -130,6 +243,40 @@ pub struct Commit {
     pub timestamp: UtcDateTime,
 }
 
+/// Who authored a commit, and who opened the pull request that merged it.
+#[derive(Debug, Clone, Default)]
+pub struct CommitAuthor {
+    /// Git author name
+    pub name: Option<String>,
+    /// GitHub login of the commit author, if the author email is linked to an account
+    pub login: Option<String>,
+    pub pr_number: Option<u64>,
+    /// GitHub login of the pull request author
+    pub pr_login: Option<String>,
+}
+
+/// Progress attributed to a single contributor over a time window.
+#[derive(Debug, Clone)]
+pub struct ContributorStats {
+    /// Pull request author, commit author login or git author name, in order of preference
+    pub contributor: String,
+    pub commits: u32,
+    pub matched_code: i64,
+    pub matched_data: i64,
+    pub matched_functions: i64,
+    pub last_commit: UtcDateTime,
+}
+
+/// Progress made by a commit compared to the previous report.
+#[derive(Debug, Clone)]
+pub struct CommitDelta {
+    pub commit: Commit,
+    pub pr_number: Option<u64>,
+    pub matched_code: i64,
+    pub matched_data: i64,
+    pub matched_functions: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct ReportFile<R> {
     pub commit: Commit,
@@ -198,114 +345,830 @@ pub struct FrogressMapping {
     pub project_measure: String,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub enum Platform {
-    PS,      // 1994
-    Win32,   // 1995
-    N64,     // 1996
-    PS2,     // 2000
-    GBA,     // 2001
-    GC,      // 2001
-    Xbox,    // 2001
-    DS,      // 2004
-    PSP,     // 2004
-    Xbox360, // 2005
-    Wii,     // 2006
-    _3DS,    // 2011
-    Switch,  // 2017
-}
-
-pub const ALL_PLATFORMS: &[Platform] = &[
-    Platform::PS,
-    Platform::Win32,
-    Platform::N64,
-    Platform::PS2,
-    Platform::GBA,
-    Platform::GC,
-    Platform::Xbox,
-    Platform::DS,
-    Platform::PSP,
-    Platform::Xbox360,
-    Platform::Wii,
-    Platform::_3DS,
-    Platform::Switch,
-];
-
-impl Platform {
-    pub fn to_str(self) -> &'static str {
+/// A platform that projects can target. Platforms are managed by super admins.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Platform {
+    /// Short identifier stored in [`Project::platform`], e.g. `gc`
+    pub id: String,
+    pub name: String,
+    /// Release year
+    pub year: Option<u16>,
+    /// Uploaded icon; platforms without one fall back to the icon font
+    #[serde(skip)]
+    pub icon_image_id: Option<ImageId>,
+    /// Position in platform lists, lowest first
+    pub sort_order: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectVisibility {
+    Visible,
+    Hidden,
+    Disabled,
+}
+
+pub fn project_visibility(project: &Project, measures: Option<&Measures>) -> ProjectVisibility {
+    // Hide projects with less than 0.5% matched code or if the project is disabled
+    if !project.enabled {
+        ProjectVisibility::Disabled
+    } else if measures.is_none_or(|m| m.matched_code_percent < 0.5) {
+        ProjectVisibility::Hidden
+    } else {
+        ProjectVisibility::Visible
+    }
+}
+
+/// A pre-generated resized or blurred copy of an uploaded image.
+#[derive(Debug, Clone)]
+pub struct ImageVariant {
+    pub name: String,
+    pub mime_type: String,
+    pub width: u32,
+    pub height: u32,
+    pub blur: Option<f32>,
+    pub data: Vec<u8>,
+}
+
+/// Role of a project maintainer granted on decomp.dev, independent of GitHub repository
+/// permissions.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MaintainerRole {
+    /// Can change project settings and trigger refreshes.
+    #[default]
+    Maintainer,
+    /// Can additionally add and remove maintainers.
+    Admin,
+}
+
+impl MaintainerRole {
+    pub const fn variants() -> &'static [Self] { &[Self::Maintainer, Self::Admin] }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Maintainer => "maintainer",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl FromStr for MaintainerRole {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "maintainer" => Ok(Self::Maintainer),
+            "admin" => Ok(Self::Admin),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for MaintainerRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Maintainer => "Maintainer",
+            Self::Admin => "Admin",
+        })
+    }
+}
+
+/// A group of related projects, e.g. a game series. Groups are managed by super admins, and
+/// maintainers add their projects to them.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectGroup {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub project_ids: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectMaintainer {
+    pub project_id: u64,
+    pub user_id: u64,
+    pub user_login: String,
+    pub role: MaintainerRole,
+    pub created_at: UtcDateTime,
+}
+
+/// A category defined on decomp.dev by rules over unit names, for projects whose reports don't
+/// include it.
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomCategory {
+    pub project_id: u64,
+    pub id: String,
+    pub name: String,
+    /// Glob patterns over unit names, or regular expressions prefixed with `re:`
+    pub rules: Vec<String>,
+    pub created_at: UtcDateTime,
+}
+
+impl CustomCategory {
+    pub fn matcher(&self) -> Result<UnitMatcher, regex::Error> {
+        RegexSet::new(self.rules.iter().map(|rule| match rule.strip_prefix("re:") {
+            Some(pattern) => pattern.to_string(),
+            None => glob_to_regex(rule),
+        }))
+        .map(UnitMatcher)
+    }
+}
+
+/// Matches unit names against the rules of a [`CustomCategory`].
+#[derive(Debug, Clone)]
+pub struct UnitMatcher(RegexSet);
+
+impl UnitMatcher {
+    pub fn is_match(&self, unit_name: &str) -> bool { self.0.is_match(unit_name) }
+
+    /// Aggregates the measures of all matching units, or `None` if no units match.
+    pub fn measures<'a>(
+        &self,
+        units: impl IntoIterator<Item = &'a ReportUnit>,
+    ) -> Option<Measures> {
+        let mut matched = units.into_iter().filter(|u| self.is_match(&u.name)).peekable();
+        matched.peek()?;
+        Some(matched.filter_map(|u| u.measures).collect())
+    }
+}
+
+/// Compiles a version pattern, either a glob or a regex prefixed with `re:`.
+pub fn version_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    match pattern.strip_prefix("re:") {
+        Some(pattern) => Regex::new(pattern),
+        None => Regex::new(&glob_to_regex(pattern)),
+    }
+}
+
+/// Splits the repository segment of a project URL into the repository name and project slug.
+pub fn split_repo_path(repo_path: &str) -> (&str, Option<&str>) {
+    match repo_path.split_once(':') {
+        Some((repo, slug)) => (repo, Some(slug)),
+        None => (repo_path, None),
+    }
+}
+
+/// Converts a glob pattern to an anchored regular expression. `*` and `?` don't match `/`,
+/// while `**` matches across path segments.
+fn glob_to_regex(glob: &str) -> String {
+    let mut out = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                out.push_str(".*");
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    out.push('$');
+    out
+}
+
+/// Measure tracked by a [`Milestone`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MilestoneMeasure {
+    #[default]
+    MatchedCode,
+    MatchedData,
+    MatchedFunctions,
+    CompleteCode,
+    CompleteData,
+    CompleteUnits,
+}
+
+impl MilestoneMeasure {
+    pub const fn variants() -> &'static [Self] {
+        &[
+            Self::MatchedCode,
+            Self::MatchedData,
+            Self::MatchedFunctions,
+            Self::CompleteCode,
+            Self::CompleteData,
+            Self::CompleteUnits,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Self::PS => "ps",
-            Self::Win32 => "win32",
-            Self::N64 => "n64",
-            Self::PS2 => "ps2",
-            Self::GBA => "gba",
-            Self::GC => "gc",
-            Self::Xbox => "xbox",
-            Self::DS => "nds",
-            Self::PSP => "psp",
-            Self::Xbox360 => "xbox360",
-            Self::Wii => "wii",
-            Self::_3DS => "3ds",
-            Self::Switch => "switch",
+            Self::MatchedCode => "matched_code",
+            Self::MatchedData => "matched_data",
+            Self::MatchedFunctions => "matched_functions",
+            Self::CompleteCode => "complete_code",
+            Self::CompleteData => "complete_data",
+            Self::CompleteUnits => "complete_units",
         }
     }
 
-    pub fn name(self) -> &'static str {
+    /// Returns the measure as a percentage.
+    pub fn percent(&self, measures: &Measures) -> f32 {
         match self {
-            Platform::PS => "PlayStation",
-            Platform::Win32 => "Windows",
-            Platform::N64 => "Nintendo 64",
-            Platform::PS2 => "PlayStation 2",
-            Platform::GBA => "Game Boy Advance",
-            Platform::GC => "GameCube",
-            Platform::Xbox => "Xbox",
-            Platform::DS => "Nintendo DS",
-            Platform::PSP => "PlayStation Portable",
-            Platform::Xbox360 => "Xbox 360",
-            Platform::Wii => "Wii",
-            Platform::_3DS => "Nintendo 3DS",
-            Platform::Switch => "Switch",
+            Self::MatchedCode => measures.matched_code_percent,
+            Self::MatchedData => measures.matched_data_percent,
+            Self::MatchedFunctions => measures.matched_functions_percent,
+            Self::CompleteCode => measures.complete_code_percent,
+            Self::CompleteData => measures.complete_data_percent,
+            Self::CompleteUnits => {
+                if measures.total_units == 0 {
+                    100.0
+                } else {
+                    measures.complete_units as f32 / measures.total_units as f32 * 100.0
+                }
+            }
         }
     }
 }
 
-impl FromStr for Platform {
+impl FromStr for MilestoneMeasure {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "ps" => Ok(Self::PS),
-            "win32" => Ok(Self::Win32),
-            "n64" => Ok(Self::N64),
-            "ps2" => Ok(Self::PS2),
-            "gba" => Ok(Self::GBA),
-            "gc" => Ok(Self::GC),
-            "xbox" => Ok(Self::Xbox),
-            "nds" => Ok(Self::DS),
-            "psp" => Ok(Self::PSP),
-            "xbox360" => Ok(Self::Xbox360),
-            "wii" => Ok(Self::Wii),
-            "3ds" => Ok(Self::_3DS),
-            "switch" => Ok(Self::Switch),
+            "matched_code" => Ok(Self::MatchedCode),
+            "matched_data" => Ok(Self::MatchedData),
+            "matched_functions" => Ok(Self::MatchedFunctions),
+            "complete_code" => Ok(Self::CompleteCode),
+            "complete_data" => Ok(Self::CompleteData),
+            "complete_units" => Ok(Self::CompleteUnits),
             _ => Err(()),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ProjectVisibility {
-    Visible,
-    Hidden,
-    Disabled,
+impl fmt::Display for MilestoneMeasure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MatchedCode => "Matched code",
+            Self::MatchedData => "Matched data",
+            Self::MatchedFunctions => "Matched functions",
+            Self::CompleteCode => "Linked code",
+            Self::CompleteData => "Linked data",
+            Self::CompleteUnits => "Linked units",
+        })
+    }
 }
 
-pub fn project_visibility(project: &Project, measures: Option<&Measures>) -> ProjectVisibility {
-    // Hide projects with less than 0.5% matched code or if the project is disabled
-    if !project.enabled {
-        ProjectVisibility::Disabled
-    } else if measures.is_none_or(|m| m.matched_code_percent < 0.5) {
-        ProjectVisibility::Hidden
-    } else {
-        ProjectVisibility::Visible
+/// A progress target set by the project maintainers, e.g. 100% linked code in `main/Game`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Milestone {
+    pub id: u64,
+    pub project_id: u64,
+    pub name: String,
+    pub version: String,
+    pub measure: MilestoneMeasure,
+    /// Target percentage of the measure
+    pub target: f32,
+    /// Restricts the milestone to a progress category
+    pub category: Option<String>,
+    /// Restricts the milestone to units with this name prefix
+    pub unit_prefix: Option<String>,
+    pub due_date: Option<UtcDateTime>,
+    pub achieved_at: Option<UtcDateTime>,
+    pub achieved_commit: Option<String>,
+    pub created_at: UtcDateTime,
+}
+
+impl Milestone {
+    /// Aggregates the measures within the milestone's scope.
+    pub fn measures(&self, report: &FullReport) -> Measures {
+        let Some(prefix) = self.unit_prefix.as_deref() else {
+            return *report.measures(self.category.as_deref());
+        };
+        let mut measures = Measures::default();
+        for unit in report.units.iter().filter(|u| u.name.starts_with(prefix)) {
+            if let Some(category) = &self.category
+                && !unit.metadata.as_ref().is_some_and(|m| m.progress_categories.contains(category))
+            {
+                continue;
+            }
+            if let Some(unit_measures) = &unit.measures {
+                measures += *unit_measures;
+            }
+        }
+        measures.calc_matched_percent();
+        measures
+    }
+
+    /// Current value of the target measure, as a percentage.
+    pub fn progress(&self, report: &FullReport) -> f32 {
+        self.measure.percent(&self.measures(report))
+    }
+
+    pub fn is_reached(&self, report: &FullReport) -> bool { self.progress(report) >= self.target }
+
+    /// Describes the scope of the milestone, e.g. `main/Game` or `dol`.
+    pub fn scope(&self) -> Option<String> {
+        match (self.category.as_deref(), self.unit_prefix.as_deref()) {
+            (Some(category), Some(prefix)) => Some(format!("{category}: {prefix}*")),
+            (Some(category), None) => Some(category.to_string()),
+            (None, Some(prefix)) => Some(format!("{prefix}*")),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Audit log project ID for site-wide actions, such as editing platforms.
+pub const SITE_AUDIT_PROJECT_ID: u64 = 0;
+
+/// A management action recorded in the audit log.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    CreateProject,
+    UpdateProject,
+    RefreshProject,
+    DeleteCommit,
+    AddMaintainer,
+    RemoveMaintainer,
+    AddMilestone,
+    RemoveMilestone,
+    AddCategory,
+    RemoveCategory,
+    SavePlatform,
+    RemovePlatform,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CreateProject => "create_project",
+            Self::UpdateProject => "update_project",
+            Self::RefreshProject => "refresh_project",
+            Self::DeleteCommit => "delete_commit",
+            Self::AddMaintainer => "add_maintainer",
+            Self::RemoveMaintainer => "remove_maintainer",
+            Self::AddMilestone => "add_milestone",
+            Self::RemoveMilestone => "remove_milestone",
+            Self::AddCategory => "add_category",
+            Self::RemoveCategory => "remove_category",
+            Self::SavePlatform => "save_platform",
+            Self::RemovePlatform => "remove_platform",
+        }
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "create_project" => Ok(Self::CreateProject),
+            "update_project" => Ok(Self::UpdateProject),
+            "refresh_project" => Ok(Self::RefreshProject),
+            "delete_commit" => Ok(Self::DeleteCommit),
+            "add_maintainer" => Ok(Self::AddMaintainer),
+            "remove_maintainer" => Ok(Self::RemoveMaintainer),
+            "add_milestone" => Ok(Self::AddMilestone),
+            "remove_milestone" => Ok(Self::RemoveMilestone),
+            "add_category" => Ok(Self::AddCategory),
+            "remove_category" => Ok(Self::RemoveCategory),
+            "save_platform" => Ok(Self::SavePlatform),
+            "remove_platform" => Ok(Self::RemovePlatform),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::CreateProject => "Created project",
+            Self::UpdateProject => "Updated project",
+            Self::RefreshProject => "Refreshed project",
+            Self::DeleteCommit => "Deleted commit reports",
+            Self::AddMaintainer => "Added maintainer",
+            Self::RemoveMaintainer => "Removed maintainer",
+            Self::AddMilestone => "Added milestone",
+            Self::RemoveMilestone => "Removed milestone",
+            Self::AddCategory => "Saved category",
+            Self::RemoveCategory => "Removed category",
+            Self::SavePlatform => "Saved platform",
+            Self::RemovePlatform => "Removed platform",
+        })
+    }
+}
+
+/// A changed [`Project`] field, with values formatted for display.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditLogEntry {
+    pub id: u64,
+    pub user_id: u64,
+    pub user_login: String,
+    /// [`SITE_AUDIT_PROJECT_ID`] for actions that don't belong to a project
+    pub project_id: u64,
+    /// None if the project has since been deleted
+    pub project_owner: Option<String>,
+    /// Repository segment of the project URL, see [`Project::repo_path`]
+    pub project_repo: Option<String>,
+    pub action: AuditAction,
+    pub changes: Vec<FieldChange>,
+    pub details: Option<String>,
+    pub created_at: UtcDateTime,
+}
+
+/// Kind of symbol in the search index.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Unit,
+    Function,
+}
+
+impl SymbolKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unit => "unit",
+            Self::Function => "function",
+        }
+    }
+}
+
+impl FromStr for SymbolKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unit" => Ok(Self::Unit),
+            "function" => Ok(Self::Function),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for SymbolKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Unit => "Unit",
+            Self::Function => "Function",
+        })
+    }
+}
+
+/// A unit or function from the latest report of a project version.
+#[derive(Debug, Clone)]
+pub struct SymbolMatch {
+    pub project_id: u64,
+    pub project_owner: String,
+    /// Repository segment of the project URL, see [`Project::repo_path`]
+    pub project_repo: String,
+    pub project_name: Option<String>,
+    pub version: String,
+    pub commit_sha: String,
+    pub kind: SymbolKind,
+    /// Containing unit, or the unit itself
+    pub unit_name: String,
+    pub name: String,
+    pub demangled_name: Option<String>,
+    pub size: u64,
+    pub fuzzy_match_percent: f32,
+}
+
+/// Kind of background job recorded in the job history.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    WorkflowRun,
+    RefreshProject,
+}
+
+impl JobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::WorkflowRun => "workflow_run",
+            Self::RefreshProject => "refresh_project",
+        }
+    }
+}
+
+impl FromStr for JobKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "workflow_run" => Ok(Self::WorkflowRun),
+            "refresh_project" => Ok(Self::RefreshProject),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for JobKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::WorkflowRun => "Workflow run",
+            Self::RefreshProject => "Refresh",
+        })
+    }
+}
+
+/// Outcome of a background job that ingests reports for a project.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobRun {
+    pub id: u64,
+    pub project_id: u64,
+    pub kind: JobKind,
+    /// GitHub Actions workflow run ID
+    pub run_id: Option<u64>,
+    /// Event that triggered the workflow run (push, pull_request)
+    pub event: Option<String>,
+    pub commit_sha: Option<String>,
+    /// Number of report artifacts found, if known
+    pub artifacts: Option<u32>,
+    pub reports_inserted: u32,
+    pub versions: Vec<String>,
+    /// Why the job did nothing, if skipped
+    pub skip_reason: Option<String>,
+    /// Error chain, one cause per line, if the job failed
+    pub error: Option<String>,
+    pub started_at: UtcDateTime,
+    pub finished_at: UtcDateTime,
+}
+
+impl JobRun {
+    pub fn new(project_id: u64, kind: JobKind) -> Self {
+        let now = UtcDateTime::now();
+        Self {
+            id: 0,
+            project_id,
+            kind,
+            run_id: None,
+            event: None,
+            commit_sha: None,
+            artifacts: None,
+            reports_inserted: 0,
+            versions: Vec::new(),
+            skip_reason: None,
+            error: None,
+            started_at: now,
+            finished_at: now,
+        }
+    }
+
+    /// Marks the job as finished, recording the error chain if it failed.
+    pub fn finish(&mut self, result: &anyhow::Result<()>) {
+        self.finished_at = UtcDateTime::now();
+        if let Err(e) = result {
+            self.fail(e);
+        }
+    }
+
+    /// Records the error chain of a failure.
+    pub fn fail(&mut self, error: &anyhow::Error) {
+        self.error = Some(error.chain().map(|c| c.to_string()).collect::<Vec<_>>().join("\n"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use objdiff_core::bindings::report::ReportUnitMetadata;
+
+    use super::*;
+
+    fn category(rules: &[&str]) -> CustomCategory {
+        CustomCategory {
+            project_id: 1,
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            rules: rules.iter().map(|r| r.to_string()).collect(),
+            created_at: UtcDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        assert_eq!(glob_to_regex("src/*.c"), "^src/[^/]*\\.c$");
+        assert_eq!(glob_to_regex("src/**"), "^src/.*$");
+        assert_eq!(glob_to_regex("a?c"), "^a[^/]c$");
+        assert_eq!(glob_to_regex("f(x)+[1]"), "^f\\(x\\)\\+\\[1\\]$");
+    }
+
+    #[test]
+    fn test_category_matcher() {
+        // `*` stays within a path segment, `**` crosses segments
+        let matcher = category(&["src/*.c"]).matcher().unwrap();
+        assert!(matcher.is_match("src/main.c"));
+        assert!(!matcher.is_match("src/game/main.c"));
+        assert!(!matcher.is_match("lib/src/main.c"));
+        let matcher = category(&["src/**.c"]).matcher().unwrap();
+        assert!(matcher.is_match("src/main.c"));
+        assert!(matcher.is_match("src/game/main.c"));
+
+        // `?` matches a single character, but not a separator
+        let matcher = category(&["d_a_?.cpp"]).matcher().unwrap();
+        assert!(matcher.is_match("d_a_b.cpp"));
+        assert!(!matcher.is_match("d_a_bc.cpp"));
+        assert!(!matcher.is_match("d_a_/.cpp"));
+
+        // Regex metacharacters in globs are literal
+        let matcher = category(&["main.c"]).matcher().unwrap();
+        assert!(matcher.is_match("main.c"));
+        assert!(!matcher.is_match("mainxc"));
+
+        // `re:` rules are unanchored regexes, and any rule may match
+        let matcher = category(&["re:^src/(game|util)/", "lib/**"]).matcher().unwrap();
+        assert!(matcher.is_match("src/game/main.c"));
+        assert!(matcher.is_match("src/util/list.c"));
+        assert!(matcher.is_match("lib/a/b.c"));
+        assert!(!matcher.is_match("src/main.c"));
+        assert!(category(&["re:("]).matcher().is_err());
+    }
+
+    fn unit(
+        name: &str,
+        total_code: u64,
+        matched_code: u64,
+        categories: &[&str],
+    ) -> Arc<ReportUnit> {
+        Arc::new(ReportUnit {
+            name: name.to_string(),
+            measures: Some(Measures {
+                total_code,
+                matched_code,
+                total_units: 1,
+                complete_units: (matched_code == total_code) as u32,
+                ..Default::default()
+            }),
+            metadata: Some(ReportUnitMetadata {
+                progress_categories: categories.iter().map(|c| c.to_string()).collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    fn milestone(category: Option<&str>, unit_prefix: Option<&str>) -> Milestone {
+        Milestone {
+            id: 1,
+            project_id: 1,
+            name: "Test".to_string(),
+            version: "v1".to_string(),
+            measure: MilestoneMeasure::MatchedCode,
+            target: 100.0,
+            category: category.map(str::to_string),
+            unit_prefix: unit_prefix.map(str::to_string),
+            due_date: None,
+            achieved_at: None,
+            achieved_commit: None,
+            created_at: UtcDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_milestone_measures() {
+        let units = vec![
+            unit("main/Game/a.c", 100, 50, &["game"]),
+            unit("main/Game/b.c", 100, 100, &[]),
+            unit("main/Other/c.c", 200, 0, &["game"]),
+        ];
+        let mut measures = units.iter().filter_map(|u| u.measures).collect::<Measures>();
+        measures.calc_matched_percent();
+        let mut game = Measures { total_code: 300, matched_code: 50, ..Default::default() };
+        game.calc_matched_percent();
+        let report = FullReport {
+            version: 0,
+            measures,
+            units,
+            categories: vec![ReportCategory {
+                id: "game".to_string(),
+                name: "Game".to_string(),
+                measures: Some(game),
+            }],
+        };
+
+        // Without a unit prefix, the report or category measures are used as is
+        assert_eq!(milestone(None, None).measures(&report).matched_code, 150);
+        assert_eq!(milestone(Some("game"), None).measures(&report).matched_code, 50);
+        assert_eq!(milestone(Some("missing"), None).measures(&report).matched_code, 150);
+
+        let measures = milestone(None, Some("main/Game/")).measures(&report);
+        assert_eq!((measures.total_code, measures.matched_code), (200, 150));
+        assert_eq!(measures.matched_code_percent, 75.0);
+        let measures = milestone(Some("game"), Some("main/Game/")).measures(&report);
+        assert_eq!((measures.total_code, measures.matched_code), (100, 50));
+        assert_eq!(measures.matched_code_percent, 50.0);
+        let measures = milestone(None, Some("lib/")).measures(&report);
+        assert_eq!(measures.total_code, 0);
+
+        let milestone = milestone(None, Some("main/Game/"));
+        assert_eq!(milestone.progress(&report), 75.0);
+        assert!(!milestone.is_reached(&report));
+    }
+
+    #[test]
+    fn test_milestone_measure_percent() {
+        let measures = Measures {
+            matched_code_percent: 10.0,
+            matched_data_percent: 20.0,
+            matched_functions_percent: 30.0,
+            complete_code_percent: 40.0,
+            complete_data_percent: 50.0,
+            total_units: 8,
+            complete_units: 2,
+            ..Default::default()
+        };
+        assert_eq!(MilestoneMeasure::MatchedCode.percent(&measures), 10.0);
+        assert_eq!(MilestoneMeasure::MatchedData.percent(&measures), 20.0);
+        assert_eq!(MilestoneMeasure::MatchedFunctions.percent(&measures), 30.0);
+        assert_eq!(MilestoneMeasure::CompleteCode.percent(&measures), 40.0);
+        assert_eq!(MilestoneMeasure::CompleteData.percent(&measures), 50.0);
+        assert_eq!(MilestoneMeasure::CompleteUnits.percent(&measures), 25.0);
+        // A scope without units is complete
+        assert_eq!(MilestoneMeasure::CompleteUnits.percent(&Measures::default()), 100.0);
+    }
+
+    #[test]
+    fn test_project_diff() {
+        let before = Project {
+            id: 1,
+            owner: "zeldaret".to_string(),
+            repo: "tp".to_string(),
+            name: Some("Twilight Princess".to_string()),
+            platform: Some("gc".to_string()),
+            ..Default::default()
+        };
+        let change = |field: &str, before: Option<&str>, after: Option<&str>| FieldChange {
+            field: field.to_string(),
+            before: before.map(str::to_string),
+            after: after.map(str::to_string),
+        };
+
+        // A new project lists every set field, including defaults
+        let created = Project::diff(None, &before);
+        assert!(created.contains(&change("owner", None, Some("zeldaret"))));
+        assert!(created.contains(&change("name", None, Some("Twilight Princess"))));
+        assert!(created.contains(&change("enabled", None, Some("true"))));
+        assert!(!created.iter().any(|c| c.field == "short_name"));
+
+        assert!(Project::diff(Some(&before), &before).is_empty());
+
+        let after = Project {
+            name: None,
+            short_name: Some("TP".to_string()),
+            header_image_id: Some([0xab; 32]),
+            enabled: false,
+            ..before.clone()
+        };
+        assert_eq!(Project::diff(Some(&before), &after), vec![
+            change("name", Some("Twilight Princess"), None),
+            change("short_name", None, Some("TP")),
+            change("header_image_id", None, Some(&"ab".repeat(32))),
+            change("enabled", Some("true"), Some("false")),
+        ]);
+    }
+
+    #[test]
+    fn test_split_repo_path() {
+        assert_eq!(split_repo_path("tp"), ("tp", None));
+        assert_eq!(split_repo_path("tp:engine"), ("tp", Some("engine")));
+        assert_eq!(split_repo_path("tp:"), ("tp", Some("")));
+    }
+
+    #[test]
+    fn test_select_artifact_version() {
+        let project =
+            |id: u64, artifact_prefix: Option<&str>, version_pattern: Option<&str>| Project {
+                id,
+                artifact_prefix: artifact_prefix.map(str::to_string),
+                version_pattern: version_pattern.map(str::to_string),
+                ..Default::default()
+            };
+        let any = project(1, None, None);
+        assert_eq!(any.select_artifact_version("GZLE01").as_deref(), Some("GZLE01"));
+
+        // The prefix is stripped, and must leave a version behind
+        let engine = project(2, Some("engine-"), None);
+        assert_eq!(engine.select_artifact_version("engine-GZLE01").as_deref(), Some("GZLE01"));
+        assert_eq!(engine.select_artifact_version("GZLE01"), None);
+        assert_eq!(engine.select_artifact_version("engine-"), None);
+        assert_eq!(engine.artifact_version("GZLE01"), "engine-GZLE01");
+
+        // Patterns match the version after the prefix is stripped
+        let us = project(3, Some("game-"), Some("GZL?01"));
+        assert_eq!(us.select_artifact_version("game-GZLE01").as_deref(), Some("GZLE01"));
+        assert_eq!(us.select_artifact_version("game-GZLJ02"), None);
+        let re = project(4, None, Some("re:^(GZLE|GZLP)"));
+        assert_eq!(re.select_artifact_version("GZLP01").as_deref(), Some("GZLP01"));
+        assert_eq!(re.select_artifact_version("GZLJ01"), None);
+        assert_eq!(project(5, None, Some("re:(")).select_artifact_version("GZLE01"), None);
+
+        // A project without rules leaves artifacts selected by its siblings' rules to them
+        let siblings = [any.clone(), engine.clone()];
+        assert_eq!(any.claim_artifact_version("engine-GZLE01", &siblings), None);
+        assert_eq!(any.claim_artifact_version("GZLE01", &siblings).as_deref(), Some("GZLE01"));
+        assert_eq!(
+            engine.claim_artifact_version("engine-GZLE01", &siblings).as_deref(),
+            Some("GZLE01")
+        );
     }
 }
@@ -1,17 +1,30 @@
 use core::mem;
-use std::{borrow::Cow, cell::RefCell, collections::HashMap, sync::Arc};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
 use anyhow::{Context, Result, anyhow, bail};
 use decomp_dev_core::{
     config::DbConfig,
     models::{
-        CachedReport, CachedReportFile, Commit, FrogressMapping, FullReport, FullReportFile,
-        ImageId, Project, ProjectInfo, UnitKey,
+        AuditAction, AuditLogEntry, CachedReport, CachedReportFile, Commit, CommitAuthor,
+        CommitDelta, ContributorStats, CustomCategory, FieldChange, FrogressMapping, FullReport,
+        FullReportFile, ImageId, ImageVariant, JobRun, MaintainerRole, Milestone, Platform,
+        Project, ProjectGroup, ProjectInfo, ProjectMaintainer, SymbolKind, SymbolMatch, UnitKey,
+        UnitMatcher, split_repo_path,
     },
 };
 use futures_util::TryStreamExt;
 use moka::future::Cache;
-use objdiff_core::bindings::report::{REPORT_VERSION, Report, ReportUnit};
+use objdiff_core::bindings::report::{
+    Measures, REPORT_VERSION, Report, ReportCategory, ReportUnit,
+};
 use prost::Message;
 use sqlx::{
     Connection, Executor, Pool, Row, Sqlite, SqliteConnection, SqlitePool, migrate::MigrateDatabase,
@@ -23,6 +36,115 @@ pub struct Database {
     pub pool: Pool<Sqlite>,
     report_cache: Cache<ReportKey, CachedReportFile>,
     report_unit_cache: Cache<UnitKey, Arc<ReportUnit>>,
+    /// Maintainer roles by user ID, looked up for every logged-in request
+    maintainer_role_cache: Cache<u64, Arc<Vec<(u64, MaintainerRole)>>>,
+    report_cache_counters: Arc<CacheCounters>,
+    report_unit_cache_counters: Arc<CacheCounters>,
+}
+
+#[derive(Default)]
+struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheCounters {
+    fn record(&self, hit: bool) {
+        if hit {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Snapshot of an in-memory cache's usage since startup.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheStats {
+    pub entries: u64,
+    pub weighted_size: u64,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    fn new<K, V>(cache: &Cache<K, V>, counters: &CacheCounters) -> Self
+    where
+        K: std::hash::Hash + Eq + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        Self {
+            entries: cache.entry_count(),
+            weighted_size: cache.weighted_size(),
+            hits: counters.hits.load(Ordering::Relaxed),
+            misses: counters.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+/// Database-wide counts for the admin dashboard.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DatabaseStats {
+    pub size_bytes: u64,
+    pub projects: u64,
+    pub reports: u64,
+    pub report_units: u64,
+    pub images: u64,
+}
+
+/// Values that change whenever a project or its reports are updated, used to validate cached
+/// report responses.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct ProjectRevision {
+    pub updated_at: Option<UtcDateTime>,
+    pub last_report_id: Option<i64>,
+    pub last_ingested_at: Option<UtcDateTime>,
+    /// Number of reports with a recorded author, which grows as authors are backfilled
+    pub authored_reports: i64,
+    pub categories_version: i64,
+}
+
+/// A row of the `job_runs` table.
+struct JobRunRow {
+    id: i64,
+    project_id: i64,
+    kind: String,
+    run_id: Option<i64>,
+    event: Option<String>,
+    commit_sha: Option<String>,
+    artifacts: Option<i64>,
+    reports_inserted: i64,
+    versions: String,
+    skip_reason: Option<String>,
+    error: Option<String>,
+    started_at: OffsetDateTime,
+    finished_at: OffsetDateTime,
+}
+
+impl JobRunRow {
+    /// Returns `None` for rows with an unknown job kind.
+    fn into_job_run(self) -> Option<JobRun> {
+        Some(JobRun {
+            id: self.id as u64,
+            project_id: self.project_id as u64,
+            kind: self.kind.parse().ok()?,
+            run_id: self.run_id.map(|id| id as u64),
+            event: self.event,
+            commit_sha: self.commit_sha,
+            artifacts: self.artifacts.map(|n| n as u32),
+            reports_inserted: self.reports_inserted as u32,
+            versions: serde_json::from_str(&self.versions).unwrap_or_default(),
+            skip_reason: self.skip_reason,
+            error: self.error,
+            started_at: self.started_at.to_utc(),
+            finished_at: self.finished_at.to_utc(),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -35,6 +157,13 @@ struct ReportKey {
 // Maximum number of bind parameters in a single query (SQLite limit)
 const BIND_LIMIT: usize = 32766;
 
+// Number of job runs kept in the job history for each project
+const MAX_JOB_RUNS_PER_PROJECT: i64 = 100;
+
+// Projects sharing a repository with an existing project are assigned IDs from this range,
+// well clear of GitHub repository IDs
+const SHARED_PROJECT_ID_BASE: i64 = 1 << 48;
+
 impl Database {
     pub async fn new(config: &DbConfig) -> Result<Arc<Self>> {
         if !Sqlite::database_exists(&config.url).await.unwrap_or(false) {
@@ -50,6 +179,7 @@ impl Database {
             .context("Failed to run database migrations")?;
         let report_cache = Cache::<ReportKey, CachedReportFile>::builder()
             .max_capacity(8192)
+            .support_invalidation_closures()
             .eviction_listener(|k, _v, _cause| {
                 tracing::debug!(
                     "Evicting report from cache: {}@{}:{}",
@@ -66,15 +196,29 @@ impl Database {
                 tracing::debug!("Evicting report unit from cache: {:?}", hex::encode(k.as_ref()));
             })
             .build();
-        let db = Self { pool, report_cache, report_unit_cache };
+        let maintainer_role_cache = Cache::<u64, Arc<Vec<(u64, MaintainerRole)>>>::builder()
+            .max_capacity(4096)
+            .time_to_live(std::time::Duration::from_secs(60 * 60))
+            .build();
+        let db = Self {
+            pool,
+            report_cache,
+            report_unit_cache,
+            maintainer_role_cache,
+            report_cache_counters: Default::default(),
+            report_unit_cache_counters: Default::default(),
+        };
         db.fixup_report_units().await.context("Fixing report units")?;
         db.migrate_reports().await.context("Migrating reports")?;
+        db.rebuild_symbol_index().await.context("Rebuilding symbol index")?;
+        db.backfill_report_deltas().await.context("Backfilling report deltas")?;
         // db.cleanup_report_units().await.context("Running report cleanup")?;
         Ok(Arc::new(db))
     }
 
     pub async fn close(&self) { self.pool.close().await }
 
+    #[tracing::instrument(skip_all, fields(project_id = project.id, commit = %commit.sha, version = %version))]
     pub async fn insert_report(
         &self,
         project: &Project,
@@ -108,16 +252,17 @@ impl Database {
         )
             .execute(&mut *tx)
             .await?;
+        Self::insert_project_repository(&mut tx, project, true).await?;
         report.migrate()?;
         let units = mem::take(&mut report.units);
         let data = compress(&report.encode_to_vec());
         let timestamp = to_primitive_date_time(commit.timestamp);
         let report_id = sqlx::query!(
             r#"
-            INSERT INTO reports (project_id, version, git_commit, git_commit_message, timestamp, data, data_version)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO reports (project_id, version, git_commit, git_commit_message, timestamp, data, data_version, ingested_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
             ON CONFLICT (project_id, version COLLATE NOCASE, git_commit COLLATE NOCASE) DO UPDATE
-            SET timestamp = EXCLUDED.timestamp
+            SET timestamp = EXCLUDED.timestamp, ingested_at = EXCLUDED.ingested_at
             RETURNING id
             "#,
             project_id,
@@ -132,6 +277,29 @@ impl Database {
             .await?
             .id;
         Self::insert_report_units(&mut tx, &units, report_id).await?;
+        let latest_report_id = sqlx::query!(
+            r#"
+            SELECT id
+            FROM reports
+            WHERE project_id = ? AND version = ? COLLATE NOCASE
+            ORDER BY timestamp DESC
+            LIMIT 1
+            "#,
+            project_id,
+            version,
+        )
+        .fetch_one(&mut *tx)
+        .await?
+        .id;
+        if latest_report_id == report_id {
+            Self::index_symbols(&mut tx, report_id, project_id, version, &units).await?;
+        }
+        let categories = Self::fetch_project_categories(&mut tx, project_id).await?;
+        if !categories.is_empty() {
+            let matchers = custom_category_matchers(&categories);
+            Self::store_custom_categories(&mut tx, report_id, &matchers, &units).await?;
+        }
+        Self::update_report_deltas(&mut tx, project_id, version, &timestamp).await?;
         tx.commit().await?;
         // self.report_cache
         //     .insert(
@@ -179,536 +347,2364 @@ impl Database {
         Ok(())
     }
 
-    pub async fn get_versions_for_commit(
+    /// Updates the measure deltas of the report at `timestamp` and of the report after it, which
+    /// is now compared against the inserted report.
+    async fn update_report_deltas(
+        conn: &mut SqliteConnection,
+        project_id: i64,
+        version: &str,
+        timestamp: &str,
+    ) -> Result<()> {
+        let mut previous = match sqlx::query!(
+            r#"
+            SELECT data
+            FROM reports
+            WHERE project_id = ? AND version = ? COLLATE NOCASE AND timestamp < ?
+            ORDER BY timestamp DESC
+            LIMIT 1
+            "#,
+            project_id,
+            version,
+            timestamp,
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        {
+            Some(row) => Some(decode_measures(&row.data)?),
+            None => None,
+        };
+        let rows = sqlx::query!(
+            r#"
+            SELECT id AS "id!", data
+            FROM reports
+            WHERE project_id = ? AND version = ? COLLATE NOCASE AND timestamp >= ?
+            ORDER BY timestamp
+            LIMIT 2
+            "#,
+            project_id,
+            version,
+            timestamp,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        for row in rows {
+            let measures = decode_measures(&row.data)?;
+            Self::set_report_deltas(conn, row.id, previous.as_ref(), &measures).await?;
+            previous = Some(measures);
+        }
+        Ok(())
+    }
+
+    async fn set_report_deltas(
+        conn: &mut SqliteConnection,
+        report_id: i64,
+        previous: Option<&Measures>,
+        measures: &Measures,
+    ) -> Result<()> {
+        let delta = |f: fn(&Measures) -> u64| previous.map(|p| f(measures) as i64 - f(p) as i64);
+        let matched_code_delta = delta(|m| m.matched_code);
+        let matched_data_delta = delta(|m| m.matched_data);
+        let matched_functions_delta = delta(|m| m.matched_functions as u64);
+        sqlx::query!(
+            r#"
+            UPDATE reports
+            SET matched_code_delta = ?, matched_data_delta = ?, matched_functions_delta = ?
+            WHERE id = ?
+            "#,
+            matched_code_delta,
+            matched_data_delta,
+            matched_functions_delta,
+            report_id,
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Computes measure deltas for project versions with reports that predate them.
+    async fn backfill_report_deltas(&self) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let versions = sqlx::query!(
+            r#"
+            SELECT DISTINCT project_id, version
+            FROM reports r
+            WHERE matched_code_delta IS NULL AND EXISTS (
+                SELECT 1
+                FROM reports
+                WHERE project_id = r.project_id AND version = r.version COLLATE NOCASE
+                    AND timestamp < r.timestamp
+            )
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        for row in &versions {
+            let reports = sqlx::query!(
+                r#"
+                SELECT id AS "id!", data
+                FROM reports
+                WHERE project_id = ? AND version = ? COLLATE NOCASE
+                ORDER BY timestamp
+                "#,
+                row.project_id,
+                row.version,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+            let mut tx = conn.begin().await?;
+            let mut previous = None;
+            for report in reports {
+                let measures = decode_measures(&report.data)?;
+                Self::set_report_deltas(&mut tx, report.id, previous.as_ref(), &measures).await?;
+                previous = Some(measures);
+            }
+            tx.commit().await?;
+        }
+        if !versions.is_empty() {
+            tracing::info!("Computed report deltas for {} project versions", versions.len());
+        }
+        Ok(())
+    }
+
+    /// Records the authors of a commit on each of its reports.
+    ///
+    /// Commits whose author email isn't linked to a GitHub account only have an author name.
+    /// So that contributors aren't split between their login and their name, the login is
+    /// taken from other commits of the project by the same author name, and recording a login
+    /// also fills it in for earlier commits that only have the name.
+    pub async fn update_commit_author(
         &self,
         project_id: u64,
-        commit: &str,
-    ) -> Result<Vec<String>> {
+        commit_sha: &str,
+        author: &CommitAuthor,
+    ) -> Result<()> {
         let mut conn = self.pool.acquire().await?;
+        let mut tx = conn.begin().await?;
         let project_id_db = project_id as i64;
-        let versions = sqlx::query!(
+        let pr_number = author.pr_number.map(|n| n as i64);
+        let login = match (&author.login, &author.name) {
+            (Some(login), _) => Some(login.clone()),
+            (None, Some(name)) => sqlx::query!(
+                r#"
+                SELECT git_author_login AS "login!"
+                FROM reports
+                WHERE project_id = ? AND git_author_name = ? AND git_author_login IS NOT NULL
+                ORDER BY timestamp DESC
+                LIMIT 1
+                "#,
+                project_id_db,
+                name,
+            )
+            .fetch_optional(&mut *tx)
+            .await?
+            .map(|row| row.login),
+            (None, None) => None,
+        };
+        sqlx::query!(
             r#"
-            SELECT version
-            FROM reports JOIN projects ON reports.project_id = projects.id
-            WHERE projects.id = ? AND git_commit = ? COLLATE NOCASE
-            ORDER BY version
+            UPDATE reports
+            SET git_author_name = ?, git_author_login = ?, pr_number = ?, pr_author_login = ?
+            WHERE project_id = ? AND git_commit = ? COLLATE NOCASE
             "#,
+            author.name,
+            login,
+            pr_number,
+            author.pr_login,
             project_id_db,
-            commit
+            commit_sha,
+        )
+        .execute(&mut *tx)
+        .await?;
+        if let (Some(login), Some(name)) = (&author.login, &author.name) {
+            sqlx::query!(
+                r#"
+                UPDATE reports
+                SET git_author_login = ?
+                WHERE project_id = ? AND git_author_name = ? AND git_author_login IS NULL
+                "#,
+                login,
+                project_id_db,
+                name,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Sums the progress of each contributor to a project version, optionally since a point
+    /// in time. Contributors are ordered by matched code.
+    pub async fn get_contributor_stats(
+        &self,
+        project_id: u64,
+        version: &str,
+        since: Option<UtcDateTime>,
+    ) -> Result<Vec<ContributorStats>> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let since = to_primitive_date_time(since.unwrap_or(UtcDateTime::UNIX_EPOCH));
+        let stats = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE(pr_author_login, git_author_login, git_author_name) AS "contributor!: String",
+                COUNT(*) AS "commits!: i64",
+                SUM(matched_code_delta) AS "matched_code!: i64",
+                SUM(matched_data_delta) AS "matched_data!: i64",
+                SUM(matched_functions_delta) AS "matched_functions!: i64",
+                MAX(timestamp) AS "last_commit!: time::OffsetDateTime"
+            FROM reports
+            WHERE project_id = ? AND version = ? COLLATE NOCASE AND timestamp >= ?
+                AND matched_code_delta IS NOT NULL
+                AND COALESCE(pr_author_login, git_author_login, git_author_name) IS NOT NULL
+            GROUP BY 1
+            ORDER BY 3 DESC, 5 DESC
+            "#,
+            project_id_db,
+            version,
+            since,
         )
         .fetch_all(&mut *conn)
         .await?
         .into_iter()
-        .map(|row| row.version)
+        .map(|row| ContributorStats {
+            contributor: row.contributor,
+            commits: row.commits as u32,
+            matched_code: row.matched_code,
+            matched_data: row.matched_data,
+            matched_functions: row.matched_functions,
+            last_commit: row.last_commit.to_utc(),
+        })
         .collect();
-        Ok(versions)
+        Ok(stats)
     }
 
-    pub async fn get_report(
+    /// Returns the most recent commits attributed to a contributor, newest first.
+    pub async fn get_contributor_commits(
         &self,
         project_id: u64,
-        commit: &str,
         version: &str,
-    ) -> Result<Option<CachedReportFile>> {
-        let key = ReportKey {
-            project_id,
-            commit: commit.to_ascii_lowercase(),
-            version: version.to_ascii_lowercase(),
-        };
-        if let Some(report) = self.report_cache.get(&key).await {
-            return Ok(Some(report));
-        }
+        contributor: &str,
+        limit: u32,
+    ) -> Result<Vec<CommitDelta>> {
         let mut conn = self.pool.acquire().await?;
         let project_id_db = project_id as i64;
-        let (report_id, commit, version, mut report) = match sqlx::query!(
+        let limit_db = limit as i64;
+        let commits = sqlx::query!(
             r#"
-            SELECT
-                reports.id as "report_id!",
-                git_commit,
-                git_commit_message,
-                timestamp,
-                version,
-                data
-            FROM reports JOIN projects ON reports.project_id = projects.id
-            WHERE projects.id = ? AND version = ? COLLATE NOCASE AND git_commit = ? COLLATE NOCASE
+            SELECT git_commit, git_commit_message, timestamp, pr_number, matched_code_delta,
+                   matched_data_delta, matched_functions_delta
+            FROM reports
+            WHERE project_id = ? AND version = ? COLLATE NOCASE
+                AND COALESCE(pr_author_login, git_author_login, git_author_name) = ?
+            ORDER BY timestamp DESC
+            LIMIT ?
             "#,
             project_id_db,
             version,
-            commit
+            contributor,
+            limit_db,
         )
-        .fetch_optional(&mut *conn)
+        .fetch_all(&mut *conn)
         .await?
-        {
-            Some(row) => {
-                let data = decompress(&row.data).context("Failed to decompress report data")?;
-                let report = Report::decode(data.as_ref()).context("Failed to decode report")?;
-                (
-                    row.report_id,
-                    Commit {
-                        sha: row.git_commit,
-                        message: row.git_commit_message,
-                        timestamp: row.timestamp.to_utc(),
-                    },
-                    row.version,
-                    CachedReport {
-                        version: report.version,
-                        measures: report.measures.unwrap_or_default(),
-                        units: vec![],
-                        categories: report.categories.clone(),
-                    },
-                )
-            }
-            None => return Ok(None),
-        };
-        let mut stream = sqlx::query!(
+        .into_iter()
+        .map(|row| CommitDelta {
+            commit: Commit {
+                sha: row.git_commit,
+                message: row.git_commit_message,
+                timestamp: row.timestamp.to_utc(),
+            },
+            pr_number: row.pr_number.map(|n| n as u64),
+            matched_code: row.matched_code_delta.unwrap_or_default(),
+            matched_data: row.matched_data_delta.unwrap_or_default(),
+            matched_functions: row.matched_functions_delta.unwrap_or_default(),
+        })
+        .collect();
+        Ok(commits)
+    }
+
+    async fn fetch_report_units(
+        conn: &mut SqliteConnection,
+        report_id: i64,
+    ) -> Result<Vec<ReportUnit>> {
+        let mut units = Vec::new();
+        let mut unit_stream = sqlx::query!(
             r#"
-            SELECT ru.id AS "id!", ru.data, rru.unit_index
+            SELECT ru.id AS "id!", ru.data
             FROM report_report_units rru JOIN report_units ru ON rru.report_unit_id = ru.id
             WHERE rru.report_id = ?
             ORDER BY rru.unit_index
             "#,
-            report_id
+            report_id,
         )
         .fetch(&mut *conn);
-        while let Some(row) = stream.try_next().await? {
-            let idx = row.unit_index as usize;
-            if idx != report.units.len() {
-                bail!("Report unit index mismatch: {} but expected {}", idx, report.units.len());
-            }
-            let key: UnitKey = row.id.as_slice().try_into()?;
-            report.units.push(key);
+        while let Some(unit_row) = unit_stream.try_next().await? {
+            let data =
+                decompress(&unit_row.data).context("Failed to decompress report unit data")?;
+            let unit = ReportUnit::decode(data.as_ref()).context("Failed to decode report unit")?;
+            units.push(unit);
         }
-        let report_file = CachedReportFile {
-            commit: commit.clone(),
-            version: version.clone(),
-            report: Arc::new(report),
-        };
-        self.report_cache.insert(key, report_file.clone()).await;
-        Ok(Some(report_file))
+        Ok(units)
     }
 
-    pub async fn upgrade_report(&self, file: &CachedReportFile) -> Result<FullReportFile> {
-        let mut units = Vec::with_capacity(file.report.units.len());
-        let mut missing_unit_keys = Vec::with_capacity(file.report.units.len());
-        let mut missing_unit_idx = HashMap::<UnitKey, Vec<usize>>::new();
-        for (idx, &key) in file.report.units.iter().enumerate() {
-            if let Some(unit) = self.report_unit_cache.get(&key).await {
-                units.push(Some(unit));
-            } else {
-                units.push(None);
-                missing_unit_keys.push(key);
-                missing_unit_idx.entry(key).or_default().push(idx);
-            }
-        }
-        if !missing_unit_keys.is_empty() {
-            let mut conn = self.pool.acquire().await?;
-            for chunk in missing_unit_keys.chunks(BIND_LIMIT) {
-                let mut builder = sqlx::QueryBuilder::<Sqlite>::new(
-                    "SELECT ru.id, ru.data FROM report_units ru WHERE ru.id IN (",
-                );
-                let mut separated = builder.separated(", ");
-                for key in chunk {
-                    separated.push_bind(&key[..]);
-                }
-                separated.push_unseparated(")");
-                let mut stream = conn.fetch(builder.build());
-                while let Some(row) = stream.try_next().await? {
-                    let row_id: Box<[u8]> = row.get(0);
-                    let row_data: Box<[u8]> = row.get(1);
-                    let key = row_id.as_ref().try_into()?;
-                    let data =
-                        decompress(&row_data).context("Failed to decompress report unit data")?;
-                    // Skip hash check since we're rehydrating a cached report
-                    // Units were checked when the report was initially loaded
-                    let unit = Arc::new(
-                        ReportUnit::decode(data.as_ref())
-                            .context("Failed to decode report unit")?,
-                    );
-                    self.report_unit_cache.insert(key, unit.clone()).await;
-                    if let Some(v) = missing_unit_idx.get(&key) {
-                        for &idx in v {
-                            units[idx] = Some(unit.clone());
-                        }
-                    } else {
-                        tracing::error!("Unexpected unit index: {:?}", hex::encode(key));
-                    }
-                }
+    /// Replaces the symbol index entries of a project version with the units and functions of
+    /// its latest report.
+    async fn index_symbols(
+        conn: &mut SqliteConnection,
+        report_id: i64,
+        project_id: i64,
+        version: &str,
+        units: &[ReportUnit],
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM symbol_index
+            WHERE project_id = ? AND version = ?
+            "#,
+            project_id,
+            version,
+        )
+        .execute(&mut *conn)
+        .await?;
+        let mut entries = Vec::new();
+        for unit in units {
+            let measures = unit.measures.as_ref();
+            entries.push((
+                SymbolKind::Unit,
+                unit.name.as_str(),
+                unit.name.as_str(),
+                None,
+                measures.map_or(0, |m| m.total_code),
+                measures.map_or(0.0, |m| m.fuzzy_match_percent),
+            ));
+            for function in &unit.functions {
+                entries.push((
+                    SymbolKind::Function,
+                    unit.name.as_str(),
+                    function.name.as_str(),
+                    function.metadata.as_ref().and_then(|m| m.demangled_name.as_deref()),
+                    function.size,
+                    function.fuzzy_match_percent,
+                ));
             }
         }
-        let mut out_units = Vec::with_capacity(file.report.units.len());
-        for (unit, &unit_key) in units.into_iter().zip(&file.report.units) {
-            if let Some(unit) = unit {
-                out_units.push(unit);
-            } else {
-                tracing::error!("Failed to load report unit: {}", hex::encode(unit_key));
-            }
+        for chunk in entries.chunks(BIND_LIMIT / 9) {
+            let mut builder = sqlx::QueryBuilder::<Sqlite>::new(
+                "INSERT INTO symbol_index (report_id, project_id, version, kind, unit_name, name, demangled_name, size, fuzzy_match_percent) ",
+            );
+            builder.push_values(
+                chunk,
+                |mut b, (kind, unit_name, name, demangled_name, size, fuzzy_match_percent)| {
+                    b.push_bind(report_id)
+                        .push_bind(project_id)
+                        .push_bind(version)
+                        .push_bind(kind.as_str())
+                        .push_bind(*unit_name)
+                        .push_bind(*name)
+                        .push_bind(*demangled_name)
+                        .push_bind(*size as i64)
+                        .push_bind(*fuzzy_match_percent);
+                },
+            );
+            conn.execute(builder.build()).await?;
         }
-        Ok(FullReportFile {
-            commit: file.commit.clone(),
-            version: file.version.clone(),
-            report: FullReport {
-                version: file.report.version,
-                measures: file.report.measures,
-                units: out_units,
-                categories: file.report.categories.clone(),
-            },
-        })
+        Ok(())
     }
 
-    pub async fn report_exists(&self, project_id: u64, commit: &str) -> Result<bool> {
+    pub async fn get_project_categories(&self, project_id: u64) -> Result<Vec<CustomCategory>> {
         let mut conn = self.pool.acquire().await?;
-        let project_id_db = project_id as i64;
-        let exists = sqlx::query!(
+        Self::fetch_project_categories(&mut conn, project_id as i64).await
+    }
+
+    async fn fetch_project_categories(
+        conn: &mut SqliteConnection,
+        project_id: i64,
+    ) -> Result<Vec<CustomCategory>> {
+        let rows = sqlx::query!(
             r#"
-            SELECT EXISTS (
-                SELECT 1
-                FROM reports JOIN projects ON reports.project_id = projects.id
-                WHERE projects.id = ? AND git_commit = ? COLLATE NOCASE
-            ) AS "exists!"
+            SELECT project_id, id, name, rules, created_at
+            FROM project_categories
+            WHERE project_id = ?
+            ORDER BY id
+            "#,
+            project_id,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(CustomCategory {
+                    project_id: row.project_id as u64,
+                    id: row.id,
+                    name: row.name,
+                    rules: serde_json::from_str(&row.rules)
+                        .context("Failed to parse category rules")?,
+                    created_at: row.created_at.to_utc(),
+                })
+            })
+            .collect()
+    }
+
+    /// Adds a project category, or replaces its name and rules if it already exists. Measures
+    /// for existing reports are computed by [`Self::rebuild_custom_categories`].
+    pub async fn upsert_project_category(&self, category: &CustomCategory) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = category.project_id as i64;
+        let rules_db = serde_json::to_string(&category.rules)?;
+        sqlx::query!(
+            r#"
+            INSERT INTO project_categories (project_id, id, name, rules, created_at)
+            VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT (project_id, id) DO UPDATE
+            SET name = EXCLUDED.name, rules = EXCLUDED.rules
             "#,
             project_id_db,
-            commit
+            category.id,
+            category.name,
+            rules_db,
         )
-        .fetch_one(&mut *conn)
-        .await?
-        .exists
-            != 0;
-        Ok(exists)
+        .execute(&mut *conn)
+        .await?;
+        Self::bump_categories_version(&mut conn, project_id_db).await?;
+        Ok(())
     }
 
-    pub async fn project_exists(&self, project_id: u64) -> Result<bool> {
+    pub async fn delete_project_category(&self, project_id: u64, id: &str) -> Result<bool> {
         let mut conn = self.pool.acquire().await?;
+        let mut tx = conn.begin().await?;
         let project_id_db = project_id as i64;
-        let exists = sqlx::query!(
+        let result = sqlx::query!(
             r#"
-            SELECT EXISTS (
-                SELECT 1
-                FROM projects
-                WHERE id = ?
-            ) AS "exists!"
+            DELETE FROM project_categories
+            WHERE project_id = ? AND id = ?
             "#,
             project_id_db,
+            id,
         )
-        .fetch_one(&mut *conn)
-        .await?
-        .exists
-            != 0;
-        Ok(exists)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM report_custom_categories
+            WHERE category_id = ? AND report_id IN (SELECT id FROM reports WHERE project_id = ?)
+            "#,
+            id,
+            project_id_db,
+        )
+        .execute(&mut *tx)
+        .await?;
+        Self::bump_categories_version(&mut tx, project_id_db).await?;
+        tx.commit().await?;
+        self.invalidate_project_reports(project_id);
+        Ok(result.rows_affected() > 0)
     }
 
-    pub async fn get_project(&self, owner: &str, repo: &str) -> Result<Option<Project>> {
-        let mut conn = self.pool.acquire().await?;
-        self.get_project_inner(&mut conn, owner, repo).await
+    /// Marks responses that include project categories as outdated.
+    async fn bump_categories_version(conn: &mut SqliteConnection, project_id: i64) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE projects
+            SET categories_version = categories_version + 1
+            WHERE id = ?
+            "#,
+            project_id,
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
     }
 
-    pub async fn get_project_by_id(&self, project_id: u64) -> Result<Option<Project>> {
+    /// Recomputes the measures of every project category for all reports of a project.
+    pub async fn rebuild_custom_categories(&self, project_id: u64) -> Result<()> {
         let mut conn = self.pool.acquire().await?;
-        self.get_project_by_id_inner(&mut conn, project_id).await
+        let project_id_db = project_id as i64;
+        let categories = Self::fetch_project_categories(&mut conn, project_id_db).await?;
+        let matchers = custom_category_matchers(&categories);
+        let report_ids = sqlx::query!(
+            r#"
+            SELECT id AS "id!"
+            FROM reports
+            WHERE project_id = ?
+            "#,
+            project_id_db,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        for row in &report_ids {
+            let units = Self::fetch_report_units(&mut conn, row.id).await?;
+            let mut tx = conn.begin().await?;
+            Self::store_custom_categories(&mut tx, row.id, &matchers, &units).await?;
+            tx.commit().await?;
+        }
+        Self::bump_categories_version(&mut conn, project_id_db).await?;
+        self.invalidate_project_reports(project_id);
+        tracing::info!(
+            "Computed {} categories for {} reports of project {}",
+            matchers.len(),
+            report_ids.len(),
+            project_id
+        );
+        Ok(())
     }
 
-    async fn get_project_inner(
-        &self,
+    /// Replaces the project category measures of a report.
+    async fn store_custom_categories(
         conn: &mut SqliteConnection,
-        owner: &str,
-        repo: &str,
-    ) -> Result<Option<Project>> {
-        Ok(sqlx::query!(
+        report_id: i64,
+        matchers: &[(&str, UnitMatcher)],
+        units: &[ReportUnit],
+    ) -> Result<()> {
+        sqlx::query!(
             r#"
-            SELECT id AS "id!", owner, repo, name, short_name, default_category, default_version, platform, workflow_id, enable_pr_comments, pr_report_style AS "pr_report_style!", header_image_id, enabled
-            FROM projects
-            WHERE owner = ? COLLATE NOCASE AND repo = ? COLLATE NOCASE
+            DELETE FROM report_custom_categories
+            WHERE report_id = ?
             "#,
-            owner,
-            repo
+            report_id,
         )
-        .fetch_optional(&mut *conn)
-        .await?
-        .map(|row| {
-            Project {
-                id: row.id as u64,
-                owner: row.owner,
-                repo: row.repo,
-                name: row.name,
-                short_name: row.short_name,
-                default_category: row.default_category,
-                default_version: row.default_version,
-                platform: row.platform,
-                workflow_id: row.workflow_id,
-                enable_pr_comments: row.enable_pr_comments,
-                pr_report_style: row.pr_report_style.parse().unwrap_or_default(),
-                header_image_id: row.header_image_id.and_then(|b| b.try_into().ok()),
-                enabled: row.enabled,
-            }
-        }))
+        .execute(&mut *conn)
+        .await?;
+        for (id, matcher) in matchers {
+            let Some(measures) = matcher.measures(units) else {
+                continue;
+            };
+            let measures_db = measures.encode_to_vec();
+            sqlx::query!(
+                r#"
+                INSERT INTO report_custom_categories (report_id, category_id, measures)
+                VALUES (?, ?, ?)
+                "#,
+                report_id,
+                id,
+                measures_db,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
     }
 
-    async fn get_project_by_id_inner(
-        &self,
-        conn: &mut SqliteConnection,
-        project_id: u64,
-    ) -> Result<Option<Project>> {
-        let project_id_db = project_id as i64;
-        Ok(sqlx::query!(
+    /// Drops cached reports of a project, e.g. after its categories changed.
+    fn invalidate_project_reports(&self, project_id: u64) {
+        if let Err(e) =
+            self.report_cache.invalidate_entries_if(move |k, _| k.project_id == project_id)
+        {
+            tracing::warn!("Failed to invalidate cached reports: {}", e);
+        }
+    }
+
+    /// Indexes the latest report of every project version that has no symbol index entries,
+    /// e.g. after the latest report was deleted.
+    pub async fn rebuild_symbol_index(&self) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let reports = sqlx::query!(
             r#"
-            SELECT id AS "id!", owner, repo, name, short_name, default_category, default_version, platform, workflow_id, enable_pr_comments, pr_report_style AS "pr_report_style!", header_image_id, enabled
-            FROM projects
-            WHERE id = ?
+            SELECT r.id AS "id!", r.project_id, r.version
+            FROM reports r
+            WHERE r.id = (
+                SELECT id
+                FROM reports
+                WHERE project_id = r.project_id AND version = r.version COLLATE NOCASE
+                ORDER BY timestamp DESC
+                LIMIT 1
+            )
+            AND NOT EXISTS (SELECT 1 FROM symbol_index WHERE report_id = r.id)
             "#,
-            project_id_db
         )
-        .fetch_optional(&mut *conn)
-        .await?
-        .map(|row| {
-            Project {
-                id: row.id as u64,
-                owner: row.owner,
-                repo: row.repo,
-                name: row.name,
-                short_name: row.short_name,
-                default_category: row.default_category,
-                default_version: row.default_version,
-                platform: row.platform,
-                workflow_id: row.workflow_id,
-                enable_pr_comments: row.enable_pr_comments,
-                pr_report_style: row.pr_report_style.parse().unwrap_or_default(),
-                header_image_id: row.header_image_id.and_then(|b| b.try_into().ok()),
-                enabled: row.enabled,
+        .fetch_all(&mut *conn)
+        .await?;
+        let mut indexed = 0;
+        for row in reports {
+            let units = Self::fetch_report_units(&mut conn, row.id).await?;
+            if units.is_empty() {
+                continue;
             }
-        }))
+            let mut tx = conn.begin().await?;
+            Self::index_symbols(&mut tx, row.id, row.project_id, &row.version, &units).await?;
+            tx.commit().await?;
+            indexed += 1;
+        }
+        if indexed > 0 {
+            tracing::info!("Indexed symbols for {} reports", indexed);
+        }
+        Ok(())
     }
 
-    pub async fn get_project_info(
+    /// Searches unit and function names in the latest report of every enabled project version.
+    /// Exact matches are returned first, then the largest symbols. The trigram index only
+    /// matches queries of at least three characters.
+    pub async fn search_symbols(
         &self,
-        owner: &str,
-        repo: &str,
-        commit: Option<&str>,
-    ) -> Result<Option<ProjectInfo>> {
+        query: &str,
+        kind: Option<SymbolKind>,
+        limit: u32,
+    ) -> Result<Vec<SymbolMatch>> {
         let mut conn = self.pool.acquire().await?;
-        let Some(project) = self.get_project_inner(&mut conn, owner, repo).await? else {
-            return Ok(None);
-        };
-        self.get_project_info_inner(&mut conn, project, commit).await
+        let pattern = fts_phrase(query);
+        let kind = kind.map(|k| k.as_str());
+        let limit_db = limit as i64;
+        let rows = sqlx::query!(
+            r#"
+            SELECT s.project_id, p.owner, p.repo || coalesce(':' || pr.slug, '') AS "repo_path!",
+                   p.name AS project_name, s.version, r.git_commit, s.kind, s.unit_name, s.name,
+                   s.demangled_name, s.size, s.fuzzy_match_percent
+            FROM symbol_index_fts f
+                JOIN symbol_index s ON s.rowid = f.rowid
+                JOIN projects p ON p.id = s.project_id
+                JOIN project_repositories pr ON pr.project_id = s.project_id
+                JOIN reports r ON r.id = s.report_id
+            WHERE symbol_index_fts MATCH ?
+              AND p.enabled = TRUE
+              AND (? IS NULL OR s.kind = ?)
+            ORDER BY (s.name = ? OR s.demangled_name = ?) DESC, s.size DESC, s.name
+            LIMIT ?
+            "#,
+            pattern,
+            kind,
+            kind,
+            query,
+            query,
+            limit_db,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(SymbolMatch {
+                    project_id: row.project_id as u64,
+                    project_owner: row.owner,
+                    project_repo: row.repo_path,
+                    project_name: row.project_name,
+                    version: row.version,
+                    commit_sha: row.git_commit,
+                    kind: row.kind.parse().ok()?,
+                    unit_name: row.unit_name,
+                    name: row.name,
+                    demangled_name: row.demangled_name,
+                    size: row.size as u64,
+                    fuzzy_match_percent: row.fuzzy_match_percent as f32,
+                })
+            })
+            .collect())
     }
 
-    pub async fn get_project_info_by_id(
+    pub async fn get_versions_for_commit(
         &self,
         project_id: u64,
-        commit: Option<&str>,
-    ) -> Result<Option<ProjectInfo>> {
-        let mut conn = self.pool.acquire().await?;
+        commit: &str,
+    ) -> Result<Vec<String>> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let versions = sqlx::query!(
+            r#"
+            SELECT version
+            FROM reports JOIN projects ON reports.project_id = projects.id
+            WHERE projects.id = ? AND git_commit = ? COLLATE NOCASE
+            ORDER BY version
+            "#,
+            project_id_db,
+            commit
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| row.version)
+        .collect();
+        Ok(versions)
+    }
+
+    pub async fn get_report(
+        &self,
+        project_id: u64,
+        commit: &str,
+        version: &str,
+    ) -> Result<Option<CachedReportFile>> {
+        let key = ReportKey {
+            project_id,
+            commit: commit.to_ascii_lowercase(),
+            version: version.to_ascii_lowercase(),
+        };
+        let cached = self.report_cache.get(&key).await;
+        self.report_cache_counters.record(cached.is_some());
+        if let Some(report) = cached {
+            return Ok(Some(report));
+        }
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let (report_id, commit, version, mut report) = match sqlx::query!(
+            r#"
+            SELECT
+                reports.id as "report_id!",
+                git_commit,
+                git_commit_message,
+                timestamp,
+                version,
+                data
+            FROM reports JOIN projects ON reports.project_id = projects.id
+            WHERE projects.id = ? AND version = ? COLLATE NOCASE AND git_commit = ? COLLATE NOCASE
+            "#,
+            project_id_db,
+            version,
+            commit
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        {
+            Some(row) => {
+                let data = decompress(&row.data).context("Failed to decompress report data")?;
+                let report = Report::decode(data.as_ref()).context("Failed to decode report")?;
+                (
+                    row.report_id,
+                    Commit {
+                        sha: row.git_commit,
+                        message: row.git_commit_message,
+                        timestamp: row.timestamp.to_utc(),
+                    },
+                    row.version,
+                    CachedReport {
+                        version: report.version,
+                        measures: report.measures.unwrap_or_default(),
+                        units: vec![],
+                        categories: report.categories.clone(),
+                    },
+                )
+            }
+            None => return Ok(None),
+        };
+        let mut stream = sqlx::query!(
+            r#"
+            SELECT ru.id AS "id!", ru.data, rru.unit_index
+            FROM report_report_units rru JOIN report_units ru ON rru.report_unit_id = ru.id
+            WHERE rru.report_id = ?
+            ORDER BY rru.unit_index
+            "#,
+            report_id
+        )
+        .fetch(&mut *conn);
+        while let Some(row) = stream.try_next().await? {
+            let idx = row.unit_index as usize;
+            if idx != report.units.len() {
+                bail!("Report unit index mismatch: {} but expected {}", idx, report.units.len());
+            }
+            let key: UnitKey = row.id.as_slice().try_into()?;
+            report.units.push(key);
+        }
+        drop(stream);
+        // Categories defined by the report take precedence over project categories
+        let custom_categories = sqlx::query!(
+            r#"
+            SELECT pc.id, pc.name, rcc.measures
+            FROM report_custom_categories rcc
+            JOIN reports r ON r.id = rcc.report_id
+            JOIN project_categories pc ON pc.project_id = r.project_id AND pc.id = rcc.category_id
+            WHERE rcc.report_id = ?
+            ORDER BY pc.id
+            "#,
+            report_id
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        for row in custom_categories {
+            if report.categories.iter().any(|c| c.id.eq_ignore_ascii_case(&row.id)) {
+                continue;
+            }
+            let measures = Measures::decode(row.measures.as_slice())
+                .context("Failed to decode category measures")?;
+            report.categories.push(ReportCategory {
+                id: row.id,
+                name: row.name,
+                measures: Some(measures),
+            });
+        }
+        let report_file = CachedReportFile {
+            commit: commit.clone(),
+            version: version.clone(),
+            report: Arc::new(report),
+        };
+        self.report_cache.insert(key, report_file.clone()).await;
+        Ok(Some(report_file))
+    }
+
+    pub async fn upgrade_report(&self, file: &CachedReportFile) -> Result<FullReportFile> {
+        let mut units = Vec::with_capacity(file.report.units.len());
+        let mut missing_unit_keys = Vec::with_capacity(file.report.units.len());
+        let mut missing_unit_idx = HashMap::<UnitKey, Vec<usize>>::new();
+        for (idx, &key) in file.report.units.iter().enumerate() {
+            let cached = self.report_unit_cache.get(&key).await;
+            self.report_unit_cache_counters.record(cached.is_some());
+            if let Some(unit) = cached {
+                units.push(Some(unit));
+            } else {
+                units.push(None);
+                missing_unit_keys.push(key);
+                missing_unit_idx.entry(key).or_default().push(idx);
+            }
+        }
+        if !missing_unit_keys.is_empty() {
+            let mut conn = self.pool.acquire().await?;
+            for chunk in missing_unit_keys.chunks(BIND_LIMIT) {
+                let mut builder = sqlx::QueryBuilder::<Sqlite>::new(
+                    "SELECT ru.id, ru.data FROM report_units ru WHERE ru.id IN (",
+                );
+                let mut separated = builder.separated(", ");
+                for key in chunk {
+                    separated.push_bind(&key[..]);
+                }
+                separated.push_unseparated(")");
+                let mut stream = conn.fetch(builder.build());
+                while let Some(row) = stream.try_next().await? {
+                    let row_id: Box<[u8]> = row.get(0);
+                    let row_data: Box<[u8]> = row.get(1);
+                    let key = row_id.as_ref().try_into()?;
+                    let data =
+                        decompress(&row_data).context("Failed to decompress report unit data")?;
+                    // Skip hash check since we're rehydrating a cached report
+                    // Units were checked when the report was initially loaded
+                    let unit = Arc::new(
+                        ReportUnit::decode(data.as_ref())
+                            .context("Failed to decode report unit")?,
+                    );
+                    self.report_unit_cache.insert(key, unit.clone()).await;
+                    if let Some(v) = missing_unit_idx.get(&key) {
+                        for &idx in v {
+                            units[idx] = Some(unit.clone());
+                        }
+                    } else {
+                        tracing::error!("Unexpected unit index: {:?}", hex::encode(key));
+                    }
+                }
+            }
+        }
+        let mut out_units = Vec::with_capacity(file.report.units.len());
+        for (unit, &unit_key) in units.into_iter().zip(&file.report.units) {
+            if let Some(unit) = unit {
+                out_units.push(unit);
+            } else {
+                tracing::error!("Failed to load report unit: {}", hex::encode(unit_key));
+            }
+        }
+        Ok(FullReportFile {
+            commit: file.commit.clone(),
+            version: file.version.clone(),
+            report: FullReport {
+                version: file.report.version,
+                measures: file.report.measures,
+                units: out_units,
+                categories: file.report.categories.clone(),
+            },
+        })
+    }
+
+    pub async fn report_exists(&self, project_id: u64, commit: &str) -> Result<bool> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let exists = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM reports JOIN projects ON reports.project_id = projects.id
+                WHERE projects.id = ? AND git_commit = ? COLLATE NOCASE
+            ) AS "exists!"
+            "#,
+            project_id_db,
+            commit
+        )
+        .fetch_one(&mut *conn)
+        .await?
+        .exists
+            != 0;
+        Ok(exists)
+    }
+
+    /// Whether reports of the commit have no author, e.g. because they were imported before
+    /// authors were recorded.
+    pub async fn commit_author_missing(&self, project_id: u64, commit: &str) -> Result<bool> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let exists = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM reports
+                WHERE project_id = ? AND git_commit = ? COLLATE NOCASE
+                  AND git_author_name IS NULL AND git_author_login IS NULL
+            ) AS "exists!"
+            "#,
+            project_id_db,
+            commit
+        )
+        .fetch_one(&mut *conn)
+        .await?
+        .exists
+            != 0;
+        Ok(exists)
+    }
+
+    pub async fn project_exists(&self, project_id: u64) -> Result<bool> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let exists = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM projects
+                WHERE id = ?
+            ) AS "exists!"
+            "#,
+            project_id_db,
+        )
+        .fetch_one(&mut *conn)
+        .await?
+        .exists
+            != 0;
+        Ok(exists)
+    }
+
+    pub async fn get_project(&self, owner: &str, repo: &str) -> Result<Option<Project>> {
+        let mut conn = self.pool.acquire().await?;
+        self.get_project_inner(&mut conn, owner, repo).await
+    }
+
+    pub async fn get_project_by_id(&self, project_id: u64) -> Result<Option<Project>> {
+        let mut conn = self.pool.acquire().await?;
+        self.get_project_by_id_inner(&mut conn, project_id).await
+    }
+
+    /// Looks up a project by the owner and repository segments of its URL. The repository
+    /// segment selects a project sharing the repository with `{repo}:{slug}`.
+    async fn get_project_inner(
+        &self,
+        conn: &mut SqliteConnection,
+        owner: &str,
+        repo_path: &str,
+    ) -> Result<Option<Project>> {
+        let (repo, slug) = split_repo_path(repo_path);
+        let slug = slug.unwrap_or_default();
+        Ok(sqlx::query!(
+            r#"
+            SELECT id AS "id!", repository_id, owner, repo, slug, artifact_prefix, version_pattern, name, short_name, default_category, default_version, platform, workflow_id, enable_pr_comments, pr_report_style AS "pr_report_style!", header_image_id, enabled
+            FROM projects JOIN project_repositories ON project_repositories.project_id = projects.id
+            WHERE owner = ? COLLATE NOCASE AND repo = ? COLLATE NOCASE AND coalesce(slug, '') = ? COLLATE NOCASE
+            "#,
+            owner,
+            repo,
+            slug,
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        .map(|row| {
+            Project {
+                id: row.id as u64,
+                repository_id: row.repository_id as u64,
+                owner: row.owner,
+                repo: row.repo,
+                slug: row.slug,
+                artifact_prefix: row.artifact_prefix,
+                version_pattern: row.version_pattern,
+                name: row.name,
+                short_name: row.short_name,
+                default_category: row.default_category,
+                default_version: row.default_version,
+                platform: row.platform,
+                workflow_id: row.workflow_id,
+                enable_pr_comments: row.enable_pr_comments,
+                pr_report_style: row.pr_report_style.parse().unwrap_or_default(),
+                header_image_id: row.header_image_id.and_then(|b| b.try_into().ok()),
+                enabled: row.enabled,
+            }
+        }))
+    }
+
+    async fn get_project_by_id_inner(
+        &self,
+        conn: &mut SqliteConnection,
+        project_id: u64,
+    ) -> Result<Option<Project>> {
+        let project_id_db = project_id as i64;
+        Ok(sqlx::query!(
+            r#"
+            SELECT id AS "id!", repository_id, owner, repo, slug, artifact_prefix, version_pattern, name, short_name, default_category, default_version, platform, workflow_id, enable_pr_comments, pr_report_style AS "pr_report_style!", header_image_id, enabled
+            FROM projects JOIN project_repositories ON project_repositories.project_id = projects.id
+            WHERE id = ?
+            "#,
+            project_id_db
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        .map(|row| {
+            Project {
+                id: row.id as u64,
+                repository_id: row.repository_id as u64,
+                owner: row.owner,
+                repo: row.repo,
+                slug: row.slug,
+                artifact_prefix: row.artifact_prefix,
+                version_pattern: row.version_pattern,
+                name: row.name,
+                short_name: row.short_name,
+                default_category: row.default_category,
+                default_version: row.default_version,
+                platform: row.platform,
+                workflow_id: row.workflow_id,
+                enable_pr_comments: row.enable_pr_comments,
+                pr_report_style: row.pr_report_style.parse().unwrap_or_default(),
+                header_image_id: row.header_image_id.and_then(|b| b.try_into().ok()),
+                enabled: row.enabled,
+            }
+        }))
+    }
+
+    pub async fn get_project_info(
+        &self,
+        owner: &str,
+        repo: &str,
+        commit: Option<&str>,
+    ) -> Result<Option<ProjectInfo>> {
+        let mut conn = self.pool.acquire().await?;
+        let Some(project) = self.get_project_inner(&mut conn, owner, repo).await? else {
+            return Ok(None);
+        };
+        self.get_project_info_inner(&mut conn, project, commit).await
+    }
+
+    pub async fn get_project_info_by_id(
+        &self,
+        project_id: u64,
+        commit: Option<&str>,
+    ) -> Result<Option<ProjectInfo>> {
+        let mut conn = self.pool.acquire().await?;
         let Some(project) = self.get_project_by_id_inner(&mut conn, project_id).await? else {
             return Ok(None);
         };
-        self.get_project_info_inner(&mut conn, project, commit).await
+        self.get_project_info_inner(&mut conn, project, commit).await
+    }
+
+    /// Fetches every project built from a GitHub repository, primary project first.
+    pub async fn get_repository_projects(&self, repository_id: u64) -> Result<Vec<ProjectInfo>> {
+        let mut conn = self.pool.acquire().await?;
+        let repository_id_db = repository_id as i64;
+        let project_ids = sqlx::query!(
+            r#"
+            SELECT project_id AS "project_id!"
+            FROM project_repositories
+            WHERE repository_id = ?
+            ORDER BY slug IS NOT NULL, slug, project_id
+            "#,
+            repository_id_db,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        let mut projects = Vec::with_capacity(project_ids.len());
+        for row in project_ids {
+            let Some(project) =
+                self.get_project_by_id_inner(&mut conn, row.project_id as u64).await?
+            else {
+                continue;
+            };
+            if let Some(info) = self.get_project_info_inner(&mut conn, project, None).await? {
+                projects.push(info);
+            }
+        }
+        Ok(projects)
+    }
+
+    async fn get_project_info_inner(
+        &self,
+        conn: &mut SqliteConnection,
+        project: Project,
+        commit: Option<&str>,
+    ) -> Result<Option<ProjectInfo>> {
+        let project_id = project.id as i64;
+        struct ReportInfo {
+            git_commit: String,
+            git_commit_message: Option<String>,
+            timestamp: OffsetDateTime,
+            version: String,
+        }
+        let reports = if let Some(commit) = commit {
+            // Fetch all reports for the specified commit
+            sqlx::query!(
+                r#"
+                SELECT git_commit, git_commit_message, timestamp, version
+                FROM reports
+                WHERE project_id = ? AND git_commit = ? COLLATE NOCASE
+                ORDER BY version
+                "#,
+                project_id,
+                commit,
+            )
+            .fetch_all(&mut *conn)
+            .await?
+            .into_iter()
+            .map(|row| ReportInfo {
+                git_commit: row.git_commit,
+                git_commit_message: row.git_commit_message,
+                timestamp: row.timestamp,
+                version: row.version,
+            })
+            .collect::<Vec<_>>()
+        } else {
+            // Fetch the latest report for each version
+            sqlx::query!(
+                r#"
+                SELECT git_commit, git_commit_message, timestamp, version
+                FROM reports
+                WHERE project_id = ? AND timestamp = (
+                    SELECT MAX(timestamp)
+                    FROM reports
+                    WHERE project_id = ?
+                )
+                ORDER BY version
+                "#,
+                project_id,
+                project_id,
+            )
+            .fetch_all(&mut *conn)
+            .await?
+            .into_iter()
+            .map(|row| ReportInfo {
+                git_commit: row.git_commit,
+                git_commit_message: row.git_commit_message,
+                timestamp: row.timestamp,
+                version: row.version,
+            })
+            .collect::<Vec<_>>()
+        };
+        let mut info = ProjectInfo {
+            project,
+            commit: None,
+            report_versions: reports.iter().map(|r| r.version.clone()).collect(),
+            prev_commit: None,
+            next_commit: None,
+            tags: Self::fetch_project_tags(conn, Some(project_id))
+                .await?
+                .remove(&(project_id as u64))
+                .unwrap_or_default(),
+        };
+        if let Some(first_report) = reports.first() {
+            // Fetch previous and next commits
+            let timestamp = to_primitive_date_time(first_report.timestamp.to_utc());
+            let prev_commit = sqlx::query!(
+                r#"
+                SELECT git_commit
+                FROM reports
+                WHERE project_id = ? AND timestamp < ?
+                ORDER BY timestamp DESC
+                LIMIT 1
+                "#,
+                project_id,
+                timestamp,
+            )
+            .fetch_optional(&mut *conn)
+            .await?
+            .map(|row| row.git_commit);
+            let next_commit = sqlx::query!(
+                r#"
+                SELECT git_commit
+                FROM reports
+                WHERE project_id = ? AND timestamp > ?
+                ORDER BY timestamp
+                LIMIT 1
+                "#,
+                project_id,
+                timestamp,
+            )
+            .fetch_optional(&mut *conn)
+            .await?
+            .map(|row| row.git_commit);
+
+            info.commit = Some(Commit {
+                sha: first_report.git_commit.clone(),
+                timestamp: first_report.timestamp.to_utc(),
+                message: first_report.git_commit_message.clone(),
+            });
+            info.prev_commit = prev_commit;
+            info.next_commit = next_commit;
+        }
+        Ok(Some(info))
+    }
+
+    pub async fn get_projects(&self) -> Result<Vec<ProjectInfo>> {
+        let mut conn = self.pool.acquire().await?;
+        let mut projects = sqlx::query!(
+            r#"
+            SELECT
+                projects.id AS "project_id!",
+                repository_id AS "repository_id!",
+                owner AS "owner!",
+                repo AS "repo!",
+                slug,
+                artifact_prefix,
+                version_pattern,
+                name,
+                short_name,
+                default_category,
+                default_version,
+                platform,
+                workflow_id,
+                enable_pr_comments AS "enable_pr_comments!",
+                pr_report_style AS "pr_report_style!",
+                header_image_id,
+                enabled AS "enabled!",
+                git_commit,
+                git_commit_message,
+                MAX(timestamp) AS "timestamp: time::OffsetDateTime",
+                JSON_GROUP_ARRAY(version ORDER BY version)
+                    FILTER (WHERE version IS NOT NULL) AS versions
+            FROM projects
+            JOIN project_repositories ON project_repositories.project_id = projects.id
+            LEFT JOIN reports ON (
+                reports.project_id = projects.id
+                AND reports.timestamp = (
+                    SELECT MAX(timestamp)
+                    FROM reports
+                    WHERE project_id = projects.id
+                )
+            )
+            GROUP BY projects.id
+            ORDER BY MAX(timestamp) DESC
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| ProjectInfo {
+            project: Project {
+                id: row.project_id as u64,
+                repository_id: row.repository_id as u64,
+                owner: row.owner,
+                repo: row.repo,
+                slug: row.slug,
+                artifact_prefix: row.artifact_prefix,
+                version_pattern: row.version_pattern,
+                name: row.name,
+                short_name: row.short_name,
+                default_category: row.default_category,
+                default_version: row.default_version,
+                platform: row.platform,
+                workflow_id: row.workflow_id,
+                enable_pr_comments: row.enable_pr_comments,
+                pr_report_style: row.pr_report_style.parse().unwrap_or_default(),
+                header_image_id: row.header_image_id.and_then(|b| b.try_into().ok()),
+                enabled: row.enabled,
+            },
+            commit: match (row.git_commit, row.timestamp) {
+                (Some(sha), Some(timestamp)) => Some(Commit {
+                    sha,
+                    timestamp: timestamp.to_utc(),
+                    message: row.git_commit_message,
+                }),
+                _ => None,
+            },
+            report_versions: row
+                .versions
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default(),
+            prev_commit: None,
+            next_commit: None,
+            tags: vec![],
+        })
+        .collect::<Vec<_>>();
+        let mut tags = Self::fetch_project_tags(&mut conn, None).await?;
+        for info in &mut projects {
+            info.tags = tags.remove(&info.project.id).unwrap_or_default();
+        }
+        Ok(projects)
+    }
+
+    async fn fixup_report_units(&self) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        for row in sqlx::query!(
+            r#"
+            SELECT id, data
+            FROM report_units
+            WHERE name IS NULL
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        {
+            let data = decompress(&row.data).context("Failed to decompress report unit data")?;
+            let unit = ReportUnit::decode(data.as_ref()).context("Failed to decode report unit")?;
+            sqlx::query!(
+                r#"
+                UPDATE report_units
+                SET name = ?
+                WHERE id = ?
+                "#,
+                unit.name,
+                row.id,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn fetch_all_reports(
+        &self,
+        project: &Project,
+        version: &str,
+    ) -> Result<Vec<CachedReportFile>> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project.id as i64;
+        let commits = sqlx::query!(
+            r#"
+            SELECT git_commit, git_commit_message, timestamp
+            FROM reports
+            WHERE project_id = ? AND version = ? COLLATE NOCASE
+            ORDER BY timestamp DESC
+            "#,
+            project_id_db,
+            version
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| Commit {
+            sha: row.git_commit,
+            timestamp: row.timestamp.to_utc(),
+            message: row.git_commit_message,
+        })
+        .collect::<Vec<_>>();
+        let mut reports = Vec::with_capacity(commits.len());
+        for commit in commits {
+            let report = self.get_report(project.id, &commit.sha, version).await?;
+            if let Some(report) = report {
+                reports.push(report);
+            } else {
+                bail!(
+                    "Report not found for {}/{}@{}:{}",
+                    project.owner,
+                    project.repo,
+                    commit.sha,
+                    version
+                );
+            }
+        }
+        Ok(reports)
+    }
+
+    /// Returns the most recent commits with a report for the given version, newest first.
+    pub async fn get_report_commits(
+        &self,
+        project_id: u64,
+        version: &str,
+        limit: u32,
+    ) -> Result<Vec<Commit>> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let limit_db = limit as i64;
+        let commits = sqlx::query!(
+            r#"
+            SELECT git_commit, git_commit_message, timestamp
+            FROM reports
+            WHERE project_id = ? AND version = ? COLLATE NOCASE
+            ORDER BY timestamp DESC
+            LIMIT ?
+            "#,
+            project_id_db,
+            version,
+            limit_db
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| Commit {
+            sha: row.git_commit,
+            timestamp: row.timestamp.to_utc(),
+            message: row.git_commit_message,
+        })
+        .collect();
+        Ok(commits)
+    }
+
+    /// Returns up to `limit` commits with a report for the version, newest first, continuing
+    /// after the commit `after`. Returns `None` if `after` has no report for the version.
+    pub async fn get_report_commits_after(
+        &self,
+        project_id: u64,
+        version: &str,
+        after: Option<&str>,
+        limit: u32,
+    ) -> Result<Option<Vec<Commit>>> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        if let Some(after) = after
+            && !Self::version_report_exists(&mut conn, project_id_db, version, after).await?
+        {
+            return Ok(None);
+        }
+        let limit_db = limit as i64;
+        let commits = sqlx::query!(
+            r#"
+            SELECT git_commit, git_commit_message, timestamp
+            FROM reports
+            WHERE project_id = ? AND version = ? COLLATE NOCASE AND (? IS NULL OR (timestamp, git_commit) < (
+                SELECT timestamp, git_commit
+                FROM reports
+                WHERE project_id = ? AND version = ? COLLATE NOCASE AND git_commit = ? COLLATE NOCASE
+            ))
+            ORDER BY timestamp DESC, git_commit DESC
+            LIMIT ?
+            "#,
+            project_id_db,
+            version,
+            after,
+            project_id_db,
+            version,
+            after,
+            limit_db
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| Commit {
+            sha: row.git_commit,
+            timestamp: row.timestamp.to_utc(),
+            message: row.git_commit_message,
+        })
+        .collect();
+        Ok(Some(commits))
+    }
+
+    /// Returns the measures of up to `limit` reports of the version, newest first, continuing
+    /// after the commit `after`. Only the report summaries are decoded, not their units.
+    /// `category` selects a report or project category, falling back to the whole report.
+    /// Returns `None` if `after` has no report for the version.
+    pub async fn get_report_measures_after(
+        &self,
+        project_id: u64,
+        version: &str,
+        category: Option<&str>,
+        after: Option<&str>,
+        limit: u32,
+    ) -> Result<Option<Vec<(Commit, Measures)>>> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        if let Some(after) = after
+            && !Self::version_report_exists(&mut conn, project_id_db, version, after).await?
+        {
+            return Ok(None);
+        }
+        let limit_db = limit as i64;
+        let rows = sqlx::query!(
+            r#"
+            SELECT git_commit, git_commit_message, timestamp, data, rcc.measures AS "category_measures?"
+            FROM reports
+            LEFT JOIN report_custom_categories rcc
+                ON rcc.report_id = reports.id AND rcc.category_id = ? COLLATE NOCASE
+            WHERE project_id = ? AND version = ? COLLATE NOCASE AND (? IS NULL OR (timestamp, git_commit) < (
+                SELECT timestamp, git_commit
+                FROM reports
+                WHERE project_id = ? AND version = ? COLLATE NOCASE AND git_commit = ? COLLATE NOCASE
+            ))
+            ORDER BY timestamp DESC, git_commit DESC
+            LIMIT ?
+            "#,
+            category,
+            project_id_db,
+            version,
+            after,
+            project_id_db,
+            version,
+            after,
+            limit_db
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows {
+            let (measures, category_measures) =
+                decode_category_measures(&row.data, category, row.category_measures.as_deref())?;
+            let commit = Commit {
+                sha: row.git_commit,
+                timestamp: row.timestamp.to_utc(),
+                message: row.git_commit_message,
+            };
+            entries.push((commit, category_measures.unwrap_or(measures)));
+        }
+        Ok(Some(entries))
+    }
+
+    /// Returns the matched code percent of every report of the version, oldest first, for
+    /// `category` or the whole report. Reports without the category are skipped. Only the
+    /// report summaries are decoded, not their units.
+    pub async fn get_progress_history(
+        &self,
+        project_id: u64,
+        version: &str,
+        category: Option<&str>,
+    ) -> Result<Vec<(UtcDateTime, f32)>> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let rows = sqlx::query!(
+            r#"
+            SELECT timestamp, data, rcc.measures AS "category_measures?"
+            FROM reports
+            LEFT JOIN report_custom_categories rcc
+                ON rcc.report_id = reports.id AND rcc.category_id = ? COLLATE NOCASE
+            WHERE project_id = ? AND version = ? COLLATE NOCASE
+            ORDER BY timestamp
+            "#,
+            category,
+            project_id_db,
+            version,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        let mut samples = Vec::with_capacity(rows.len());
+        for row in rows {
+            let (measures, category_measures) =
+                decode_category_measures(&row.data, category, row.category_measures.as_deref())?;
+            let measures = match category {
+                Some(_) => match category_measures {
+                    Some(measures) => measures,
+                    None => continue,
+                },
+                None => measures,
+            };
+            samples.push((row.timestamp.to_utc(), measures.matched_code_percent));
+        }
+        Ok(samples)
+    }
+
+    /// Whether the commit has a report for the version.
+    async fn version_report_exists(
+        conn: &mut SqliteConnection,
+        project_id: i64,
+        version: &str,
+        commit: &str,
+    ) -> Result<bool> {
+        let exists = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM reports
+                WHERE project_id = ? AND version = ? COLLATE NOCASE AND git_commit = ? COLLATE NOCASE
+            ) AS "exists!"
+            "#,
+            project_id,
+            version,
+            commit
+        )
+        .fetch_one(&mut *conn)
+        .await?
+        .exists
+            != 0;
+        Ok(exists)
+    }
+
+    /// Returns the commit `count` reports before the given commit, or the oldest commit if
+    /// there are fewer reports.
+    pub async fn get_previous_commit(
+        &self,
+        project_id: u64,
+        version: &str,
+        commit_sha: &str,
+        count: u32,
+    ) -> Result<Option<String>> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let count_db = count as i64;
+        let commits = sqlx::query!(
+            r#"
+            SELECT git_commit
+            FROM reports
+            WHERE project_id = ? AND version = ? COLLATE NOCASE AND timestamp < (
+                SELECT timestamp
+                FROM reports
+                WHERE project_id = ? AND version = ? COLLATE NOCASE AND git_commit = ?
+            )
+            ORDER BY timestamp DESC
+            LIMIT ?
+            "#,
+            project_id_db,
+            version,
+            project_id_db,
+            version,
+            commit_sha,
+            count_db,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        Ok(commits.into_iter().last().map(|row| row.git_commit))
+    }
+
+    async fn migrate_reports(&self) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let mut stream = sqlx::query!(
+            r#"
+            SELECT id, data
+            FROM reports
+            WHERE data_version < ?
+            "#,
+            REPORT_VERSION,
+        )
+        .fetch(&mut *conn);
+        let mut reports = Vec::new();
+        while let Some(row) = stream.try_next().await? {
+            let report_id = row.id;
+            let data = decompress(&row.data).context("Failed to decompress report data")?;
+            let report = Report::decode(data.as_ref()).context("Failed to decode report")?;
+            reports.push((report_id, report));
+        }
+        drop(stream);
+        for (report_id, mut report) in reports {
+            if report.version == REPORT_VERSION {
+                // Report is already up-to-date
+                sqlx::query!(
+                    r#"
+                    UPDATE reports
+                    SET data_version = ?
+                    WHERE id = ?
+                    "#,
+                    REPORT_VERSION,
+                    report_id,
+                )
+                .execute(&mut *conn)
+                .await?;
+                continue;
+            }
+            tracing::info!("Migrating report {} from version {}", report_id, report.version);
+            // Fetch all report units
+            report.units = Self::fetch_report_units(&mut conn, report_id).await?;
+            // Migrate report
+            report.migrate()?;
+            let units = mem::take(&mut report.units);
+            let data = compress(&report.encode_to_vec());
+            // Persist updated report
+            let mut tx = conn.begin().await?;
+            sqlx::query!(
+                r#"
+                UPDATE reports
+                SET data = ?, data_version = ?
+                WHERE id = ?
+                "#,
+                data,
+                REPORT_VERSION,
+                report_id,
+            )
+            .execute(&mut *tx)
+            .await?;
+            // Delete existing report units
+            sqlx::query!(
+                r#"
+                DELETE FROM report_report_units
+                WHERE report_id = ?
+                "#,
+                report_id,
+            )
+            .execute(&mut *tx)
+            .await?;
+            // Insert updated report units
+            Self::insert_report_units(&mut tx, &units, report_id).await?;
+            tx.commit().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn cleanup_report_units(&self) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let mut tx = conn.begin().await?;
+        let deleted_reports = sqlx::query!(
+            r#"
+            DELETE FROM reports
+            WHERE project_id NOT IN (SELECT id FROM projects)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        let deleted_report_units = sqlx::query!(
+            r#"
+            DELETE FROM report_units
+            WHERE id NOT IN (SELECT report_unit_id FROM report_report_units)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        tx.commit().await?;
+        if deleted_reports > 0 || deleted_report_units > 0 {
+            tracing::info!(
+                "Deleted {} orphaned reports and {} orphaned report units",
+                deleted_reports,
+                deleted_report_units,
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn get_frogress_mappings(&self) -> Result<Vec<FrogressMapping>> {
+        let mut conn = self.pool.acquire().await?;
+        let mappings = sqlx::query!(
+            r#"
+            SELECT frogress_slug,
+                   frogress_version,
+                   frogress_category,
+                   frogress_measure,
+                   project_id,
+                   version,
+                   category,
+                   category_name,
+                   measure
+            FROM frogress_mappings
+            ORDER BY frogress_slug, frogress_version, frogress_category
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|row| FrogressMapping {
+            frogress_slug: row.frogress_slug,
+            frogress_version: row.frogress_version,
+            frogress_category: row.frogress_category,
+            frogress_measure: row.frogress_measure,
+            project_id: row.project_id as u64,
+            project_version: row.version,
+            project_category: row.category,
+            project_category_name: row.category_name,
+            project_measure: row.measure,
+        })
+        .collect();
+        Ok(mappings)
+    }
+
+    pub async fn update_report_message(
+        &self,
+        project_id: u64,
+        commit: &str,
+        message: &str,
+    ) -> Result<()> {
+        let count = {
+            let mut conn = self.pool.acquire().await?;
+            let project_id_db = project_id as i64;
+            sqlx::query!(
+                r#"
+                UPDATE reports
+                SET git_commit_message = ?
+                WHERE project_id = ? AND git_commit = ? COLLATE NOCASE
+                "#,
+                message,
+                project_id_db,
+                commit,
+            )
+            .execute(&mut *conn)
+            .await?
+            .rows_affected()
+        };
+        if count == 0 {
+            bail!("Report not found for project ID {} and commit {}", project_id, commit);
+        }
+        let keys = {
+            let mut conn = self.pool.acquire().await?;
+            let project_id_db = project_id as i64;
+            let result = sqlx::query!(
+                r#"
+                SELECT version FROM reports WHERE project_id = ? AND git_commit = ? COLLATE NOCASE
+                "#,
+                project_id_db,
+                commit,
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+            result
+                .iter()
+                .map(|r| ReportKey {
+                    project_id,
+                    commit: commit.to_ascii_lowercase(),
+                    version: r.version.to_ascii_lowercase(),
+                })
+                .collect::<Vec<_>>()
+        };
+        for key in keys {
+            if let Some(mut report) = self.report_cache.get(&key).await {
+                report.commit.message = Some(message.to_owned());
+                self.report_cache.insert(key, report).await;
+            }
+        }
+        Ok(())
     }
 
-    async fn get_project_info_inner(
+    pub async fn update_project_workflow_id(
         &self,
-        conn: &mut SqliteConnection,
-        project: Project,
-        commit: Option<&str>,
-    ) -> Result<Option<ProjectInfo>> {
+        project_id: u64,
+        workflow_id: &str,
+    ) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        sqlx::query!(
+            r#"
+            UPDATE projects
+            SET workflow_id = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?
+            "#,
+            workflow_id,
+            project_id_db,
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Updates the owner and name of a renamed or transferred repository for all of its projects.
+    pub async fn update_project_owner_repo(
+        &self,
+        repository_id: u64,
+        owner: &str,
+        repo: &str,
+    ) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let repository_id_db = repository_id as i64;
+        sqlx::query!(
+            r#"
+            UPDATE projects
+            SET owner = ?, repo = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE id IN (SELECT project_id FROM project_repositories WHERE repository_id = ?)
+            "#,
+            owner,
+            repo,
+            repository_id_db,
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Updates a project, replacing its tags and group memberships when given, and records the
+    /// changes in the audit log in the same transaction.
+    pub async fn update_project(
+        &self,
+        project: &Project,
+        tags: Option<&[String]>,
+        group_ids: Option<&[String]>,
+        user_id: u64,
+        user_login: &str,
+        changes: &[FieldChange],
+    ) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let mut tx = conn.begin().await?;
         let project_id = project.id as i64;
-        struct ReportInfo {
-            git_commit: String,
-            git_commit_message: Option<String>,
-            timestamp: OffsetDateTime,
-            version: String,
+        let header_image_id = project.header_image_id.as_ref().map(|b| b.as_slice());
+        let pr_report_style = project.pr_report_style.as_str();
+        sqlx::query!(
+            r#"
+            UPDATE projects
+            SET owner = ?, repo = ?, name = ?, short_name = ?, default_category = ?, default_version = ?, platform = ?, workflow_id = ?, enable_pr_comments = ?, pr_report_style = ?, header_image_id = ?, enabled = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?
+            "#,
+            project.owner,
+            project.repo,
+            project.name,
+            project.short_name,
+            project.default_category,
+            project.default_version,
+            project.platform,
+            project.workflow_id,
+            project.enable_pr_comments,
+            pr_report_style,
+            header_image_id,
+            project.enabled,
+            project_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            UPDATE project_repositories
+            SET slug = ?, artifact_prefix = ?, version_pattern = ?
+            WHERE project_id = ?
+            "#,
+            project.slug,
+            project.artifact_prefix,
+            project.version_pattern,
+            project_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        if let Some(tags) = tags {
+            Self::replace_project_tags(&mut tx, project_id, tags).await?;
         }
-        let reports = if let Some(commit) = commit {
-            // Fetch all reports for the specified commit
+        if let Some(group_ids) = group_ids {
+            Self::replace_project_group_memberships(&mut tx, project_id, group_ids).await?;
+        }
+        if !changes.is_empty() {
+            Self::insert_audit_log(
+                &mut tx,
+                user_id,
+                user_login,
+                project.id,
+                AuditAction::UpdateProject,
+                changes,
+                None,
+            )
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Creates a project and returns its ID. The first project of a repository uses the
+    /// repository ID, while projects sharing a repository are assigned a new ID. The creation is
+    /// recorded in the audit log in the same transaction.
+    pub async fn create_project(
+        &self,
+        project: &Project,
+        user_id: u64,
+        user_login: &str,
+    ) -> Result<u64> {
+        let mut conn = self.pool.acquire().await?;
+        let mut tx = conn.begin().await?;
+        let repository_id = project.repository_id as i64;
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM projects WHERE id = ?) AS "taken!: bool",
+                   MAX(id) AS max_id
+            FROM projects
+            "#,
+            repository_id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        let project_id = next_project_id(repository_id, row.taken, row.max_id);
+        let header_image_id = project.header_image_id.as_ref().map(|b| b.as_slice());
+        let pr_report_style = project.pr_report_style.as_str();
+        sqlx::query!(
+            r#"
+            INSERT INTO projects (id, owner, repo, name, short_name, default_category, default_version, platform, workflow_id, enable_pr_comments, pr_report_style, header_image_id, enabled, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+            "#,
+            project_id,
+            project.owner,
+            project.repo,
+            project.name,
+            project.short_name,
+            project.default_category,
+            project.default_version,
+            project.platform,
+            project.workflow_id,
+            project.enable_pr_comments,
+            pr_report_style,
+            header_image_id,
+            project.enabled,
+        )
+        .execute(&mut *tx)
+        .await?;
+        let project = Project { id: project_id as u64, ..project.clone() };
+        Self::insert_project_repository(&mut tx, &project, false).await?;
+        Self::insert_audit_log(
+            &mut tx,
+            user_id,
+            user_login,
+            project.id,
+            AuditAction::CreateProject,
+            &Project::diff(None, &project),
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        // Maintainer rows of a deleted project are removed along with it, but cached roles
+        // would otherwise carry over to a new project that is assigned the same ID
+        self.maintainer_role_cache.invalidate_all();
+        Ok(project.id)
+    }
+
+    async fn insert_project_repository(
+        conn: &mut SqliteConnection,
+        project: &Project,
+        ignore_existing: bool,
+    ) -> Result<()> {
+        let project_id = project.id as i64;
+        let repository_id = project.repository_id as i64;
+        if ignore_existing {
             sqlx::query!(
                 r#"
-                SELECT git_commit, git_commit_message, timestamp, version
-                FROM reports
-                WHERE project_id = ? AND git_commit = ? COLLATE NOCASE
-                ORDER BY version
+                INSERT INTO project_repositories (project_id, repository_id, slug, artifact_prefix, version_pattern)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT (project_id) DO NOTHING
                 "#,
                 project_id,
-                commit,
+                repository_id,
+                project.slug,
+                project.artifact_prefix,
+                project.version_pattern,
             )
-            .fetch_all(&mut *conn)
-            .await?
+            .execute(&mut *conn)
+            .await?;
+        } else {
+            sqlx::query!(
+                r#"
+                INSERT INTO project_repositories (project_id, repository_id, slug, artifact_prefix, version_pattern)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+                project_id,
+                repository_id,
+                project.slug,
+                project.artifact_prefix,
+                project.version_pattern,
+            )
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the most recent report ingest time for each project.
+    pub async fn get_last_ingest_times(&self) -> Result<HashMap<u64, UtcDateTime>> {
+        let mut conn = self.pool.acquire().await?;
+        let rows = sqlx::query!(
+            r#"
+            SELECT project_id, MAX(ingested_at) AS "ingested_at: time::OffsetDateTime"
+            FROM reports
+            WHERE ingested_at IS NOT NULL
+            GROUP BY project_id
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        Ok(rows
             .into_iter()
-            .map(|row| ReportInfo {
-                git_commit: row.git_commit,
-                git_commit_message: row.git_commit_message,
-                timestamp: row.timestamp,
-                version: row.version,
+            .filter_map(|row| Some((row.project_id as u64, row.ingested_at?.to_utc())))
+            .collect())
+    }
+
+    /// Returns the current revision of a project and its reports.
+    pub async fn get_project_revision(&self, project_id: u64) -> Result<ProjectRevision> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                projects.updated_at AS "updated_at: time::OffsetDateTime",
+                projects.categories_version,
+                MAX(reports.id) AS last_report_id,
+                MAX(reports.ingested_at) AS "last_ingested_at: time::OffsetDateTime",
+                COUNT(reports.git_author_name) + COUNT(reports.git_author_login) AS authored_reports
+            FROM projects
+            LEFT JOIN reports ON reports.project_id = projects.id
+            WHERE projects.id = ?
+            GROUP BY projects.id
+            "#,
+            project_id_db,
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+        Ok(row
+            .map(|row| ProjectRevision {
+                updated_at: Some(row.updated_at.to_utc()),
+                last_report_id: row.last_report_id,
+                last_ingested_at: row.last_ingested_at.map(|t| t.to_utc()),
+                authored_reports: row.authored_reports,
+                categories_version: row.categories_version,
             })
-            .collect::<Vec<_>>()
-        } else {
-            // Fetch the latest report for each version
+            .unwrap_or_default())
+    }
+
+    pub async fn get_stats(&self) -> Result<DatabaseStats> {
+        let mut conn = self.pool.acquire().await?;
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()) AS "size_bytes!: i64",
+                (SELECT COUNT(*) FROM projects) AS "projects!: i64",
+                (SELECT COUNT(*) FROM reports) AS "reports!: i64",
+                (SELECT COUNT(*) FROM report_units) AS "report_units!: i64",
+                (SELECT COUNT(*) FROM images) AS "images!: i64"
+            "#,
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+        Ok(DatabaseStats {
+            size_bytes: row.size_bytes as u64,
+            projects: row.projects as u64,
+            reports: row.reports as u64,
+            report_units: row.report_units as u64,
+            images: row.images as u64,
+        })
+    }
+
+    /// Returns usage of the report and report unit caches, in that order.
+    pub fn cache_stats(&self) -> (CacheStats, CacheStats) {
+        (
+            CacheStats::new(&self.report_cache, &self.report_cache_counters),
+            CacheStats::new(&self.report_unit_cache, &self.report_unit_cache_counters),
+        )
+    }
+
+    pub async fn create_image(
+        &self,
+        mime_type: &str,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Result<ImageId> {
+        let mut conn = self.pool.acquire().await?;
+        let key: ImageId = blake3::hash(data).into();
+        let key_db = &key[..];
+        sqlx::query!(
+            r#"
+            INSERT INTO images (id, mime_type, width, height, data, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+            key_db,
+            mime_type,
+            width,
+            height,
+            data,
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(key)
+    }
+
+    pub async fn get_image(&self, id: ImageId) -> Result<Option<(String, u32, u32, Vec<u8>)>> {
+        let mut conn = self.pool.acquire().await?;
+        let id_db = &id[..];
+        let row = sqlx::query!(
+            r#"
+            SELECT mime_type, width, height, data
+            FROM images
+            WHERE id = ?
+            "#,
+            id_db,
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+        Ok(row.map(|row| (row.mime_type, row.width as u32, row.height as u32, row.data)))
+    }
+
+    /// Fetches the format and dimensions of an image without its data.
+    pub async fn get_image_info(&self, id: ImageId) -> Result<Option<(String, u32, u32)>> {
+        let mut conn = self.pool.acquire().await?;
+        let id_db = &id[..];
+        let row = sqlx::query!(
+            r#"
+            SELECT mime_type, width, height
+            FROM images
+            WHERE id = ?
+            "#,
+            id_db,
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+        Ok(row.map(|row| (row.mime_type, row.width as u32, row.height as u32)))
+    }
+
+    pub async fn create_image_variants(
+        &self,
+        image_id: ImageId,
+        variants: &[ImageVariant],
+    ) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let mut tx = conn.begin().await?;
+        let image_id_db = &image_id[..];
+        for variant in variants {
+            let blur_db = variant.blur.map(|b| b as f64);
             sqlx::query!(
                 r#"
-                SELECT git_commit, git_commit_message, timestamp, version
-                FROM reports
-                WHERE project_id = ? AND timestamp = (
-                    SELECT MAX(timestamp)
-                    FROM reports
-                    WHERE project_id = ?
-                )
-                ORDER BY version
-                "#,
-                project_id,
-                project_id,
-            )
-            .fetch_all(&mut *conn)
-            .await?
-            .into_iter()
-            .map(|row| ReportInfo {
-                git_commit: row.git_commit,
-                git_commit_message: row.git_commit_message,
-                timestamp: row.timestamp,
-                version: row.version,
-            })
-            .collect::<Vec<_>>()
-        };
-        let mut info = ProjectInfo {
-            project,
-            commit: None,
-            report_versions: reports.iter().map(|r| r.version.clone()).collect(),
-            prev_commit: None,
-            next_commit: None,
-        };
-        if let Some(first_report) = reports.first() {
-            // Fetch previous and next commits
-            let timestamp = to_primitive_date_time(first_report.timestamp.to_utc());
-            let prev_commit = sqlx::query!(
-                r#"
-                SELECT git_commit
-                FROM reports
-                WHERE project_id = ? AND timestamp < ?
-                ORDER BY timestamp DESC
-                LIMIT 1
-                "#,
-                project_id,
-                timestamp,
-            )
-            .fetch_optional(&mut *conn)
-            .await?
-            .map(|row| row.git_commit);
-            let next_commit = sqlx::query!(
-                r#"
-                SELECT git_commit
-                FROM reports
-                WHERE project_id = ? AND timestamp > ?
-                ORDER BY timestamp
-                LIMIT 1
+                INSERT INTO image_variants (image_id, name, mime_type, width, height, blur, data, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+                ON CONFLICT (image_id, name, mime_type) DO NOTHING
                 "#,
-                project_id,
-                timestamp,
+                image_id_db,
+                variant.name,
+                variant.mime_type,
+                variant.width,
+                variant.height,
+                blur_db,
+                variant.data,
             )
-            .fetch_optional(&mut *conn)
-            .await?
-            .map(|row| row.git_commit);
-
-            info.commit = Some(Commit {
-                sha: first_report.git_commit.clone(),
-                timestamp: first_report.timestamp.to_utc(),
-                message: first_report.git_commit_message.clone(),
-            });
-            info.prev_commit = prev_commit;
-            info.next_commit = next_commit;
+            .execute(&mut *tx)
+            .await?;
         }
-        Ok(Some(info))
+        tx.commit().await?;
+        Ok(())
     }
 
-    pub async fn get_projects(&self) -> Result<Vec<ProjectInfo>> {
+    /// Returns the IDs of project header images that are missing the named pre-generated variant.
+    pub async fn get_header_images_missing_variant(&self, name: &str) -> Result<Vec<ImageId>> {
         let mut conn = self.pool.acquire().await?;
-        let projects = sqlx::query!(
+        let rows = sqlx::query!(
             r#"
-            SELECT
-                projects.id AS "project_id!",
-                owner AS "owner!",
-                repo AS "repo!",
-                name,
-                short_name,
-                default_category,
-                default_version,
-                platform,
-                workflow_id,
-                enable_pr_comments AS "enable_pr_comments!",
-                pr_report_style AS "pr_report_style!",
-                header_image_id,
-                enabled AS "enabled!",
-                git_commit,
-                git_commit_message,
-                MAX(timestamp) AS "timestamp: time::OffsetDateTime",
-                JSON_GROUP_ARRAY(version ORDER BY version)
-                    FILTER (WHERE version IS NOT NULL) AS versions
-            FROM projects LEFT JOIN reports ON (
-                reports.project_id = projects.id
-                AND reports.timestamp = (
-                    SELECT MAX(timestamp)
-                    FROM reports
-                    WHERE project_id = projects.id
-                )
+            SELECT id AS "id!"
+            FROM images
+            WHERE id IN (SELECT header_image_id FROM projects)
+            AND NOT EXISTS (
+                SELECT 1 FROM image_variants
+                WHERE image_variants.image_id = images.id AND image_variants.name = ?
             )
-            GROUP BY projects.id
-            ORDER BY MAX(timestamp) DESC
             "#,
+            name,
         )
         .fetch_all(&mut *conn)
-        .await?
-        .into_iter()
-        .map(|row| ProjectInfo {
-            project: Project {
-                id: row.project_id as u64,
-                owner: row.owner,
-                repo: row.repo,
-                name: row.name,
-                short_name: row.short_name,
-                default_category: row.default_category,
-                default_version: row.default_version,
-                platform: row.platform,
-                workflow_id: row.workflow_id,
-                enable_pr_comments: row.enable_pr_comments,
-                pr_report_style: row.pr_report_style.parse().unwrap_or_default(),
-                header_image_id: row.header_image_id.and_then(|b| b.try_into().ok()),
-                enabled: row.enabled,
-            },
-            commit: match (row.git_commit, row.timestamp) {
-                (Some(sha), Some(timestamp)) => Some(Commit {
-                    sha,
-                    timestamp: timestamp.to_utc(),
-                    message: row.git_commit_message,
-                }),
-                _ => None,
-            },
-            report_versions: row
-                .versions
-                .and_then(|s| serde_json::from_str(&s).ok())
-                .unwrap_or_default(),
-            prev_commit: None,
-            next_commit: None,
-        })
-        .collect();
-        Ok(projects)
+        .await?;
+        Ok(rows.into_iter().filter_map(|row| row.id.as_slice().try_into().ok()).collect())
     }
 
-    async fn fixup_report_units(&self) -> Result<()> {
+    /// Returns the IDs of platform icons that are missing the named pre-generated variant.
+    pub async fn get_platform_icons_missing_variant(&self, name: &str) -> Result<Vec<ImageId>> {
         let mut conn = self.pool.acquire().await?;
-        for row in sqlx::query!(
+        let rows = sqlx::query!(
             r#"
-            SELECT id, data
-            FROM report_units
-            WHERE name IS NULL
+            SELECT id AS "id!"
+            FROM images
+            WHERE id IN (SELECT icon_image_id FROM platforms)
+            AND NOT EXISTS (
+                SELECT 1 FROM image_variants
+                WHERE image_variants.image_id = images.id AND image_variants.name = ?
+            )
             "#,
+            name,
         )
         .fetch_all(&mut *conn)
+        .await?;
+        Ok(rows.into_iter().filter_map(|row| row.id.as_slice().try_into().ok()).collect())
+    }
+
+    /// Fetches the smallest pre-generated variant that is at least as large as the requested
+    /// dimensions, with a matching blur and format.
+    pub async fn get_image_variant(
+        &self,
+        image_id: ImageId,
+        width: u32,
+        height: u32,
+        blur: Option<f32>,
+        mime_type: &str,
+    ) -> Result<Option<(u32, u32, Vec<u8>)>> {
+        let mut conn = self.pool.acquire().await?;
+        let image_id_db = &image_id[..];
+        let blur_db = blur.map(|b| b as f64);
+        let row = sqlx::query!(
+            r#"
+            SELECT width, height, data
+            FROM image_variants
+            WHERE image_id = ? AND width >= ? AND height >= ? AND blur IS ? AND mime_type = ?
+            ORDER BY width * height
+            LIMIT 1
+            "#,
+            image_id_db,
+            width,
+            height,
+            blur_db,
+            mime_type,
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+        Ok(row.map(|row| (row.width as u32, row.height as u32, row.data)))
+    }
+
+    pub async fn update_project_header(&self, project_id: u64, image_id: ImageId) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let image_id = &image_id[..];
+        let project_id_db = project_id as i64;
+        sqlx::query!(
+            r#"
+            UPDATE projects
+            SET header_image_id = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?
+            "#,
+            image_id,
+            project_id_db,
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn cleanup_images(&self) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let mut tx = conn.begin().await?;
+        let deleted_variants = sqlx::query!(
+            r#"
+            DELETE FROM image_variants
+            WHERE image_id NOT IN (
+                SELECT header_image_id FROM projects
+                WHERE header_image_id IS NOT NULL
+                UNION
+                SELECT icon_image_id FROM platforms
+                WHERE icon_image_id IS NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
         .await?
-        {
-            let data = decompress(&row.data).context("Failed to decompress report unit data")?;
-            let unit = ReportUnit::decode(data.as_ref()).context("Failed to decode report unit")?;
+        .rows_affected();
+        let deleted_images = sqlx::query!(
+            r#"
+            DELETE FROM images
+            WHERE id NOT IN (
+                SELECT header_image_id FROM projects
+                WHERE header_image_id IS NOT NULL
+                UNION
+                SELECT icon_image_id FROM platforms
+                WHERE icon_image_id IS NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        tx.commit().await?;
+        if deleted_images > 0 || deleted_variants > 0 {
+            tracing::info!(
+                "Deleted {} orphaned images ({} variants)",
+                deleted_images,
+                deleted_variants
+            );
+        }
+        Ok(())
+    }
+
+    /// Fetches the tags of one project, or of all projects.
+    async fn fetch_project_tags(
+        conn: &mut SqliteConnection,
+        project_id: Option<i64>,
+    ) -> Result<HashMap<u64, Vec<String>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT project_id, tag
+            FROM project_tags
+            WHERE ? IS NULL OR project_id = ?
+            ORDER BY tag
+            "#,
+            project_id,
+            project_id,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        let mut tags = HashMap::<u64, Vec<String>>::new();
+        for row in rows {
+            tags.entry(row.project_id as u64).or_default().push(row.tag);
+        }
+        Ok(tags)
+    }
+
+    /// Replaces the tags of a project.
+    async fn replace_project_tags(
+        conn: &mut SqliteConnection,
+        project_id: i64,
+        tags: &[String],
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM project_tags
+            WHERE project_id = ?
+            "#,
+            project_id,
+        )
+        .execute(&mut *conn)
+        .await?;
+        for tag in tags {
             sqlx::query!(
                 r#"
-                UPDATE report_units
-                SET name = ?
-                WHERE id = ?
+                INSERT INTO project_tags (project_id, tag)
+                VALUES (?, ?)
+                ON CONFLICT DO NOTHING
                 "#,
-                unit.name,
-                row.id,
+                project_id,
+                tag,
             )
             .execute(&mut *conn)
             .await?;
@@ -716,447 +2712,609 @@ impl Database {
         Ok(())
     }
 
-    pub async fn fetch_all_reports(
-        &self,
-        project: &Project,
-        version: &str,
-    ) -> Result<Vec<CachedReportFile>> {
+    pub async fn get_project_groups(&self) -> Result<Vec<ProjectGroup>> {
         let mut conn = self.pool.acquire().await?;
-        let project_id_db = project.id as i64;
-        let commits = sqlx::query!(
+        let mut groups = sqlx::query!(
             r#"
-            SELECT git_commit, git_commit_message, timestamp
-            FROM reports
-            WHERE project_id = ? AND version = ? COLLATE NOCASE
-            ORDER BY timestamp DESC
+            SELECT id AS "id!", name, description
+            FROM project_groups
+            ORDER BY name
             "#,
-            project_id_db,
-            version
         )
         .fetch_all(&mut *conn)
         .await?
         .into_iter()
-        .map(|row| Commit {
-            sha: row.git_commit,
-            timestamp: row.timestamp.to_utc(),
-            message: row.git_commit_message,
+        .map(|row| ProjectGroup {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            project_ids: vec![],
         })
         .collect::<Vec<_>>();
-        let mut reports = Vec::with_capacity(commits.len());
-        for commit in commits {
-            let report = self.get_report(project.id, &commit.sha, version).await?;
-            if let Some(report) = report {
-                reports.push(report);
-            } else {
-                bail!(
-                    "Report not found for {}/{}@{}:{}",
-                    project.owner,
-                    project.repo,
-                    commit.sha,
-                    version
-                );
+        let members = sqlx::query!(
+            r#"
+            SELECT group_id, project_id
+            FROM project_group_members
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        for row in members {
+            if let Some(group) =
+                groups.iter_mut().find(|g| g.id.eq_ignore_ascii_case(&row.group_id))
+            {
+                group.project_ids.push(row.project_id as u64);
             }
         }
-        Ok(reports)
+        Ok(groups)
     }
 
-    async fn migrate_reports(&self) -> Result<()> {
+    /// Adds a group, or updates its name and description if it already exists.
+    pub async fn upsert_project_group(
+        &self,
+        id: &str,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<()> {
         let mut conn = self.pool.acquire().await?;
-        let mut stream = sqlx::query!(
+        sqlx::query!(
             r#"
-            SELECT id, data
-            FROM reports
-            WHERE data_version < ?
+            INSERT INTO project_groups (id, name, description, created_at, updated_at)
+            VALUES (?, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+            ON CONFLICT (id) DO UPDATE
+            SET name = EXCLUDED.name, description = EXCLUDED.description,
+                updated_at = CURRENT_TIMESTAMP
             "#,
-            REPORT_VERSION,
+            id,
+            name,
+            description,
         )
-        .fetch(&mut *conn);
-        let mut reports = Vec::new();
-        while let Some(row) = stream.try_next().await? {
-            let report_id = row.id;
-            let data = decompress(&row.data).context("Failed to decompress report data")?;
-            let report = Report::decode(data.as_ref()).context("Failed to decode report")?;
-            reports.push((report_id, report));
-        }
-        drop(stream);
-        for (report_id, mut report) in reports {
-            if report.version == REPORT_VERSION {
-                // Report is already up-to-date
-                sqlx::query!(
-                    r#"
-                    UPDATE reports
-                    SET data_version = ?
-                    WHERE id = ?
-                    "#,
-                    REPORT_VERSION,
-                    report_id,
-                )
-                .execute(&mut *conn)
-                .await?;
-                continue;
-            }
-            tracing::info!("Migrating report {} from version {}", report_id, report.version);
-            // Fetch all report units
-            let mut unit_stream = sqlx::query!(
-                r#"
-                SELECT ru.id AS "id!", ru.data
-                FROM report_report_units rru JOIN report_units ru ON rru.report_unit_id = ru.id
-                WHERE rru.report_id = ?
-                ORDER BY rru.unit_index
-                "#,
-                report_id,
-            )
-            .fetch(&mut *conn);
-            while let Some(unit_row) = unit_stream.try_next().await? {
-                let data =
-                    decompress(&unit_row.data).context("Failed to decompress report unit data")?;
-                let unit =
-                    ReportUnit::decode(data.as_ref()).context("Failed to decode report unit")?;
-                report.units.push(unit);
-            }
-            drop(unit_stream);
-            // Migrate report
-            report.migrate()?;
-            let units = mem::take(&mut report.units);
-            let data = compress(&report.encode_to_vec());
-            // Persist updated report
-            let mut tx = conn.begin().await?;
-            sqlx::query!(
-                r#"
-                UPDATE reports
-                SET data = ?, data_version = ?
-                WHERE id = ?
-                "#,
-                data,
-                REPORT_VERSION,
-                report_id,
-            )
-            .execute(&mut *tx)
-            .await?;
-            // Delete existing report units
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_project_group(&self, id: &str) -> Result<bool> {
+        let mut conn = self.pool.acquire().await?;
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM project_groups
+            WHERE id = ?
+            "#,
+            id,
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Replaces the groups a project belongs to.
+    async fn replace_project_group_memberships(
+        conn: &mut SqliteConnection,
+        project_id: i64,
+        group_ids: &[String],
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM project_group_members
+            WHERE project_id = ?
+            "#,
+            project_id,
+        )
+        .execute(&mut *conn)
+        .await?;
+        for group_id in group_ids {
             sqlx::query!(
                 r#"
-                DELETE FROM report_report_units
-                WHERE report_id = ?
+                INSERT INTO project_group_members (group_id, project_id)
+                SELECT id, ? FROM project_groups WHERE id = ?
+                ON CONFLICT DO NOTHING
                 "#,
-                report_id,
+                project_id,
+                group_id,
             )
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
-            // Insert updated report units
-            Self::insert_report_units(&mut tx, &units, report_id).await?;
-            tx.commit().await?;
         }
         Ok(())
     }
 
-    pub async fn cleanup_report_units(&self) -> Result<()> {
+    pub async fn get_platforms(&self) -> Result<Vec<Platform>> {
         let mut conn = self.pool.acquire().await?;
-        let mut tx = conn.begin().await?;
-        let deleted_reports = sqlx::query!(
+        let rows = sqlx::query!(
             r#"
-            DELETE FROM reports
-            WHERE project_id NOT IN (SELECT id FROM projects)
+            SELECT id AS "id!", name, year, icon_image_id, sort_order
+            FROM platforms
+            ORDER BY sort_order, name
             "#,
         )
-        .execute(&mut *tx)
-        .await?
-        .rows_affected();
-        let deleted_report_units = sqlx::query!(
+        .fetch_all(&mut *conn)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| Platform {
+                id: row.id,
+                name: row.name,
+                year: row.year.and_then(|y| u16::try_from(y).ok()),
+                icon_image_id: row.icon_image_id.and_then(|b| b.try_into().ok()),
+                sort_order: row.sort_order,
+            })
+            .collect())
+    }
+
+    /// Adds a platform, or updates it if one with the same ID exists.
+    pub async fn upsert_platform(&self, platform: &Platform) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        let year_db = platform.year.map(|y| y as i64);
+        let icon_image_id = platform.icon_image_id.as_ref().map(|b| b.as_slice());
+        sqlx::query!(
             r#"
-            DELETE FROM report_units
-            WHERE id NOT IN (SELECT report_unit_id FROM report_report_units)
+            INSERT INTO platforms (id, name, year, icon_image_id, sort_order, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+            ON CONFLICT (id) DO UPDATE
+            SET name = EXCLUDED.name, year = EXCLUDED.year, icon_image_id = EXCLUDED.icon_image_id,
+                sort_order = EXCLUDED.sort_order, updated_at = CURRENT_TIMESTAMP
             "#,
+            platform.id,
+            platform.name,
+            year_db,
+            icon_image_id,
+            platform.sort_order,
         )
-        .execute(&mut *tx)
-        .await?
-        .rows_affected();
-        tx.commit().await?;
-        if deleted_reports > 0 || deleted_report_units > 0 {
-            tracing::info!(
-                "Deleted {} orphaned reports and {} orphaned report units",
-                deleted_reports,
-                deleted_report_units,
-            );
-        }
+        .execute(&mut *conn)
+        .await?;
         Ok(())
     }
 
-    pub async fn get_frogress_mappings(&self) -> Result<Vec<FrogressMapping>> {
+    /// Deletes a platform, unless a project still uses it. Returns whether it was deleted.
+    pub async fn delete_platform(&self, id: &str) -> Result<bool> {
         let mut conn = self.pool.acquire().await?;
-        let mappings = sqlx::query!(
+        let result = sqlx::query!(
             r#"
-            SELECT frogress_slug,
-                   frogress_version,
-                   frogress_category,
-                   frogress_measure,
-                   project_id,
-                   version,
-                   category,
-                   category_name,
-                   measure
-            FROM frogress_mappings
-            ORDER BY frogress_slug, frogress_version, frogress_category
+            DELETE FROM platforms
+            WHERE id = ? AND NOT EXISTS (
+                SELECT 1 FROM projects WHERE platform = platforms.id COLLATE NOCASE
+            )
             "#,
+            id,
         )
-        .fetch_all(&mut *conn)
-        .await?
-        .into_iter()
-        .map(|row| FrogressMapping {
-            frogress_slug: row.frogress_slug,
-            frogress_version: row.frogress_version,
-            frogress_category: row.frogress_category,
-            frogress_measure: row.frogress_measure,
-            project_id: row.project_id as u64,
-            project_version: row.version,
-            project_category: row.category,
-            project_category_name: row.category_name,
-            project_measure: row.measure,
-        })
-        .collect();
-        Ok(mappings)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
     }
 
-    pub async fn update_report_message(
-        &self,
-        project_id: u64,
-        commit: &str,
-        message: &str,
-    ) -> Result<()> {
-        let count = {
-            let mut conn = self.pool.acquire().await?;
-            let project_id_db = project_id as i64;
-            sqlx::query!(
-                r#"
-                UPDATE reports
-                SET git_commit_message = ?
-                WHERE project_id = ? AND git_commit = ? COLLATE NOCASE
-                "#,
-                message,
-                project_id_db,
-                commit,
-            )
-            .execute(&mut *conn)
-            .await?
-            .rows_affected()
-        };
-        if count == 0 {
-            bail!("Report not found for project ID {} and commit {}", project_id, commit);
-        }
-        let keys = {
-            let mut conn = self.pool.acquire().await?;
-            let project_id_db = project_id as i64;
-            let result = sqlx::query!(
-                r#"
-                SELECT version FROM reports WHERE project_id = ? AND git_commit = ? COLLATE NOCASE
-                "#,
-                project_id_db,
-                commit,
-            )
-            .fetch_all(&mut *conn)
-            .await?;
-            result
-                .iter()
-                .map(|r| ReportKey {
-                    project_id,
-                    commit: commit.to_ascii_lowercase(),
-                    version: r.version.to_ascii_lowercase(),
-                })
-                .collect::<Vec<_>>()
-        };
-        for key in keys {
-            if let Some(mut report) = self.report_cache.get(&key).await {
-                report.commit.message = Some(message.to_owned());
-                self.report_cache.insert(key, report).await;
-            }
-        }
-        Ok(())
+    pub async fn get_project_maintainers(&self, project_id: u64) -> Result<Vec<ProjectMaintainer>> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let rows = sqlx::query!(
+            r#"
+            SELECT project_id, user_id, user_login, role, created_at
+            FROM project_maintainers
+            WHERE project_id = ?
+            ORDER BY user_login COLLATE NOCASE
+            "#,
+            project_id_db,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| ProjectMaintainer {
+                project_id: row.project_id as u64,
+                user_id: row.user_id as u64,
+                user_login: row.user_login,
+                role: row.role.parse().unwrap_or_default(),
+                created_at: row.created_at.to_utc(),
+            })
+            .collect())
     }
 
-    pub async fn update_project_workflow_id(
+    /// Fetches the maintainer roles of a user, keyed by project ID.
+    pub async fn get_maintainer_roles(
+        &self,
+        user_id: u64,
+    ) -> Result<Arc<Vec<(u64, MaintainerRole)>>> {
+        self.maintainer_role_cache
+            .try_get_with(user_id, async {
+                self.fetch_maintainer_roles(user_id).await.map(Arc::new)
+            })
+            .await
+            .map_err(|e| anyhow!("Failed to fetch maintainer roles: {e:?}"))
+    }
+
+    async fn fetch_maintainer_roles(&self, user_id: u64) -> Result<Vec<(u64, MaintainerRole)>> {
+        let mut conn = self.pool.acquire().await?;
+        let user_id_db = user_id as i64;
+        let rows = sqlx::query!(
+            r#"
+            SELECT project_id, role
+            FROM project_maintainers
+            WHERE user_id = ?
+            "#,
+            user_id_db,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.project_id as u64, row.role.parse().unwrap_or_default()))
+            .collect())
+    }
+
+    /// Adds a maintainer to a project, or updates their role if they already exist.
+    pub async fn upsert_project_maintainer(
         &self,
         project_id: u64,
-        workflow_id: &str,
+        user_id: u64,
+        user_login: &str,
+        role: MaintainerRole,
     ) -> Result<()> {
         let mut conn = self.pool.acquire().await?;
         let project_id_db = project_id as i64;
+        let user_id_db = user_id as i64;
+        let role_db = role.as_str();
         sqlx::query!(
             r#"
-            UPDATE projects
-            SET workflow_id = ?, updated_at = CURRENT_TIMESTAMP
-            WHERE id = ?
+            INSERT INTO project_maintainers (project_id, user_id, user_login, role, created_at)
+            VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT (project_id, user_id) DO UPDATE
+            SET user_login = EXCLUDED.user_login, role = EXCLUDED.role
             "#,
-            workflow_id,
             project_id_db,
+            user_id_db,
+            user_login,
+            role_db,
         )
         .execute(&mut *conn)
         .await?;
+        self.maintainer_role_cache.invalidate(&user_id).await;
         Ok(())
     }
 
-    pub async fn update_project_owner_repo(
+    pub async fn delete_project_maintainer(&self, project_id: u64, user_id: u64) -> Result<bool> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let user_id_db = user_id as i64;
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM project_maintainers
+            WHERE project_id = ? AND user_id = ?
+            "#,
+            project_id_db,
+            user_id_db,
+        )
+        .execute(&mut *conn)
+        .await?;
+        self.maintainer_role_cache.invalidate(&user_id).await;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Fetches the milestones of a project, optionally restricted to a report version.
+    pub async fn get_milestones(
         &self,
         project_id: u64,
-        owner: &str,
-        repo: &str,
-    ) -> Result<()> {
+        version: Option<&str>,
+    ) -> Result<Vec<Milestone>> {
         let mut conn = self.pool.acquire().await?;
         let project_id_db = project_id as i64;
-        sqlx::query!(
+        let rows = sqlx::query!(
             r#"
-            UPDATE projects
-            SET owner = ?, repo = ?, updated_at = CURRENT_TIMESTAMP
-            WHERE id = ?
+            SELECT id, project_id, name, version, measure, target, category, unit_prefix,
+                   due_date, achieved_at, achieved_commit, created_at
+            FROM milestones
+            WHERE project_id = ? AND (? IS NULL OR version = ?)
+            ORDER BY achieved_at IS NOT NULL, due_date IS NULL, due_date, id
             "#,
-            owner,
-            repo,
             project_id_db,
+            version,
+            version,
         )
-        .execute(&mut *conn)
+        .fetch_all(&mut *conn)
         .await?;
-        Ok(())
+        Ok(rows
+            .into_iter()
+            .map(|row| Milestone {
+                id: row.id as u64,
+                project_id: row.project_id as u64,
+                name: row.name,
+                version: row.version,
+                measure: row.measure.parse().unwrap_or_default(),
+                target: row.target as f32,
+                category: row.category,
+                unit_prefix: row.unit_prefix,
+                due_date: row.due_date.map(|d| d.to_utc()),
+                achieved_at: row.achieved_at.map(|d| d.to_utc()),
+                achieved_commit: row.achieved_commit,
+                created_at: row.created_at.to_utc(),
+            })
+            .collect())
     }
 
-    pub async fn update_project(&self, project: &Project) -> Result<()> {
+    /// Creates a milestone, returning its ID. The ID and achievement fields are ignored.
+    pub async fn create_milestone(&self, milestone: &Milestone) -> Result<u64> {
         let mut conn = self.pool.acquire().await?;
-        let project_id = project.id as i64;
-        let header_image_id = project.header_image_id.as_ref().map(|b| b.as_slice());
-        let pr_report_style = project.pr_report_style.as_str();
-        sqlx::query!(
+        let project_id_db = milestone.project_id as i64;
+        let measure_db = milestone.measure.as_str();
+        let target_db = milestone.target as f64;
+        let due_date_db = milestone.due_date.map(to_primitive_date_time);
+        let id = sqlx::query!(
             r#"
-            UPDATE projects
-            SET owner = ?, repo = ?, name = ?, short_name = ?, default_category = ?, default_version = ?, platform = ?, workflow_id = ?, enable_pr_comments = ?, pr_report_style = ?, header_image_id = ?, enabled = ?, updated_at = CURRENT_TIMESTAMP
-            WHERE id = ?
+            INSERT INTO milestones (project_id, name, version, measure, target, category, unit_prefix, due_date, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+            RETURNING id
             "#,
-            project.owner,
-            project.repo,
-            project.name,
-            project.short_name,
-            project.default_category,
-            project.default_version,
-            project.platform,
-            project.workflow_id,
-            project.enable_pr_comments,
-            pr_report_style,
-            header_image_id,
-            project.enabled,
-            project_id,
+            project_id_db,
+            milestone.name,
+            milestone.version,
+            measure_db,
+            target_db,
+            milestone.category,
+            milestone.unit_prefix,
+            due_date_db,
+        )
+        .fetch_one(&mut *conn)
+        .await?
+        .id;
+        Ok(id as u64)
+    }
+
+    pub async fn delete_milestone(&self, project_id: u64, id: u64) -> Result<bool> {
+        let mut conn = self.pool.acquire().await?;
+        let project_id_db = project_id as i64;
+        let id_db = id as i64;
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM milestones
+            WHERE project_id = ? AND id = ?
+            "#,
+            project_id_db,
+            id_db,
         )
         .execute(&mut *conn)
         .await?;
-        Ok(())
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Marks milestones reached by the report for a commit as achieved, returning the newly
+    /// achieved milestones.
+    pub async fn check_milestones(
+        &self,
+        project_id: u64,
+        commit: &Commit,
+        version: &str,
+    ) -> Result<Vec<Milestone>> {
+        let pending = self
+            .get_milestones(project_id, Some(version))
+            .await?
+            .into_iter()
+            .filter(|m| m.achieved_at.is_none())
+            .collect::<Vec<_>>();
+        if pending.is_empty() {
+            return Ok(vec![]);
+        }
+        let Some(report) = self.get_report(project_id, &commit.sha, version).await? else {
+            return Ok(vec![]);
+        };
+        let report = self.upgrade_report(&report).await?;
+        let mut conn = self.pool.acquire().await?;
+        let achieved_at_db = to_primitive_date_time(commit.timestamp);
+        let mut achieved = Vec::new();
+        for mut milestone in pending {
+            if !milestone.is_reached(&report.report) {
+                continue;
+            }
+            let id_db = milestone.id as i64;
+            sqlx::query!(
+                r#"
+                UPDATE milestones
+                SET achieved_at = ?, achieved_commit = ?
+                WHERE id = ? AND achieved_at IS NULL
+                "#,
+                achieved_at_db,
+                commit.sha,
+                id_db,
+            )
+            .execute(&mut *conn)
+            .await?;
+            milestone.achieved_at = Some(commit.timestamp);
+            milestone.achieved_commit = Some(commit.sha.clone());
+            achieved.push(milestone);
+        }
+        Ok(achieved)
     }
 
-    pub async fn create_project(&self, project: &Project) -> Result<()> {
+    /// Records a job outcome, keeping only the most recent runs for each project.
+    pub async fn insert_job_run(&self, run: &JobRun) -> Result<()> {
         let mut conn = self.pool.acquire().await?;
-        let project_id = project.id as i64;
-        let header_image_id = project.header_image_id.as_ref().map(|b| b.as_slice());
-        let pr_report_style = project.pr_report_style.as_str();
+        let mut tx = conn.begin().await?;
+        let project_id_db = run.project_id as i64;
+        let kind_db = run.kind.as_str();
+        let run_id_db = run.run_id.map(|id| id as i64);
+        let versions_db = serde_json::to_string(&run.versions)?;
+        let started_at_db = to_primitive_date_time(run.started_at);
+        let finished_at_db = to_primitive_date_time(run.finished_at);
         sqlx::query!(
             r#"
-            INSERT INTO projects (id, owner, repo, name, short_name, default_category, default_version, platform, workflow_id, enable_pr_comments, pr_report_style, header_image_id, enabled, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+            INSERT INTO job_runs (project_id, kind, run_id, event, commit_sha, artifacts, reports_inserted, versions, skip_reason, error, started_at, finished_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
-            project_id,
-            project.owner,
-            project.repo,
-            project.name,
-            project.short_name,
-            project.default_category,
-            project.default_version,
-            project.platform,
-            project.workflow_id,
-            project.enable_pr_comments,
-            pr_report_style,
-            header_image_id,
-            project.enabled,
+            project_id_db,
+            kind_db,
+            run_id_db,
+            run.event,
+            run.commit_sha,
+            run.artifacts,
+            run.reports_inserted,
+            versions_db,
+            run.skip_reason,
+            run.error,
+            started_at_db,
+            finished_at_db,
         )
-        .execute(&mut *conn)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM job_runs
+            WHERE project_id = ? AND id NOT IN (
+                SELECT id FROM job_runs
+                WHERE project_id = ?
+                ORDER BY id DESC
+                LIMIT ?
+            )
+            "#,
+            project_id_db,
+            project_id_db,
+            MAX_JOB_RUNS_PER_PROJECT,
+        )
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(())
     }
 
-    pub async fn create_image(
-        &self,
-        mime_type: &str,
-        width: u32,
-        height: u32,
-        data: &[u8],
-    ) -> Result<ImageId> {
+    pub async fn get_job_runs(&self, project_id: u64, limit: u32) -> Result<Vec<JobRun>> {
         let mut conn = self.pool.acquire().await?;
-        let key: ImageId = blake3::hash(data).into();
-        let key_db = &key[..];
-        sqlx::query!(
+        let project_id_db = project_id as i64;
+        let rows = sqlx::query_as!(
+            JobRunRow,
             r#"
-            INSERT INTO images (id, mime_type, width, height, data, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
-            ON CONFLICT (id) DO NOTHING
+            SELECT id AS "id!", project_id, kind, run_id, event, commit_sha, artifacts, reports_inserted, versions, skip_reason, error, started_at, finished_at
+            FROM job_runs
+            WHERE project_id = ?
+            ORDER BY id DESC
+            LIMIT ?
             "#,
-            key_db,
-            mime_type,
-            width,
-            height,
-            data,
+            project_id_db,
+            limit,
         )
-        .execute(&mut *conn)
+        .fetch_all(&mut *conn)
         .await?;
-        Ok(key)
+        Ok(rows.into_iter().filter_map(JobRunRow::into_job_run).collect())
     }
 
-    pub async fn get_image(&self, id: ImageId) -> Result<Option<(String, u32, u32, Vec<u8>)>> {
+    /// Returns the most recent failed job for each project.
+    pub async fn get_last_job_errors(&self) -> Result<HashMap<u64, JobRun>> {
         let mut conn = self.pool.acquire().await?;
-        let id_db = &id[..];
-        let row = sqlx::query!(
+        let rows = sqlx::query_as!(
+            JobRunRow,
             r#"
-            SELECT mime_type, width, height, data
-            FROM images
-            WHERE id = ?
+            SELECT id AS "id!", project_id, kind, run_id, event, commit_sha, artifacts, reports_inserted, versions, skip_reason, error, started_at, finished_at
+            FROM job_runs
+            WHERE id IN (
+                SELECT MAX(id) FROM job_runs
+                WHERE error IS NOT NULL
+                GROUP BY project_id
+            )
             "#,
-            id_db,
         )
-        .fetch_optional(&mut *conn)
+        .fetch_all(&mut *conn)
         .await?;
-        Ok(row.map(|row| (row.mime_type, row.width as u32, row.height as u32, row.data)))
+        Ok(rows
+            .into_iter()
+            .filter_map(JobRunRow::into_job_run)
+            .map(|run| (run.project_id, run))
+            .collect())
     }
 
-    pub async fn update_project_header(&self, project_id: u64, image_id: ImageId) -> Result<()> {
+    pub async fn create_audit_log(
+        &self,
+        user_id: u64,
+        user_login: &str,
+        project_id: u64,
+        action: AuditAction,
+        changes: &[FieldChange],
+        details: Option<&str>,
+    ) -> Result<()> {
         let mut conn = self.pool.acquire().await?;
-        let image_id = &image_id[..];
+        Self::insert_audit_log(&mut conn, user_id, user_login, project_id, action, changes, details)
+            .await
+    }
+
+    async fn insert_audit_log(
+        conn: &mut SqliteConnection,
+        user_id: u64,
+        user_login: &str,
+        project_id: u64,
+        action: AuditAction,
+        changes: &[FieldChange],
+        details: Option<&str>,
+    ) -> Result<()> {
+        let user_id_db = user_id as i64;
         let project_id_db = project_id as i64;
+        let action_db = action.as_str();
+        let changes_db = serde_json::to_string(changes)?;
         sqlx::query!(
             r#"
-            UPDATE projects
-            SET header_image_id = ?, updated_at = CURRENT_TIMESTAMP
-            WHERE id = ?
+            INSERT INTO audit_log (user_id, user_login, project_id, action, changes, details, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
             "#,
-            image_id,
+            user_id_db,
+            user_login,
             project_id_db,
+            action_db,
+            changes_db,
+            details,
         )
         .execute(&mut *conn)
         .await?;
         Ok(())
     }
 
-    pub async fn cleanup_images(&self) -> Result<()> {
+    /// Fetches the most recent audit log entries, optionally filtered by project.
+    pub async fn get_audit_log(
+        &self,
+        project_id: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<AuditLogEntry>> {
         let mut conn = self.pool.acquire().await?;
-        let mut tx = conn.begin().await?;
-        let deleted_images = sqlx::query!(
+        let project_id_db = project_id.map(|id| id as i64);
+        let rows = sqlx::query!(
             r#"
-            DELETE FROM images
-            WHERE id NOT IN (
-                SELECT header_image_id FROM projects
-                WHERE header_image_id IS NOT NULL
-            )
+            SELECT
+                audit_log.id AS "id!",
+                user_id,
+                user_login,
+                audit_log.project_id,
+                projects.owner AS "project_owner?",
+                projects.repo || coalesce(':' || project_repositories.slug, '') AS "project_repo?",
+                action,
+                changes,
+                details,
+                audit_log.created_at
+            FROM audit_log
+            LEFT JOIN projects ON projects.id = audit_log.project_id
+            LEFT JOIN project_repositories ON project_repositories.project_id = audit_log.project_id
+            WHERE ? IS NULL OR audit_log.project_id = ?
+            ORDER BY audit_log.created_at DESC, audit_log.id DESC
+            LIMIT ?
             "#,
+            project_id_db,
+            project_id_db,
+            limit,
         )
-        .execute(&mut *tx)
-        .await?
-        .rows_affected();
-        tx.commit().await?;
-        if deleted_images > 0 {
-            tracing::info!("Deleted {} orphaned images", deleted_images);
+        .fetch_all(&mut *conn)
+        .await?;
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows {
+            let Ok(action) = row.action.parse() else {
+                tracing::warn!("Unknown audit log action: {}", row.action);
+                continue;
+            };
+            entries.push(AuditLogEntry {
+                id: row.id as u64,
+                user_id: row.user_id as u64,
+                user_login: row.user_login,
+                project_id: row.project_id as u64,
+                project_owner: row.project_owner,
+                project_repo: row.project_repo,
+                action,
+                changes: serde_json::from_str(&row.changes).unwrap_or_default(),
+                details: row.details,
+                created_at: row.created_at.to_utc(),
+            });
         }
-        Ok(())
+        Ok(entries)
     }
 
     pub async fn delete_reports_by_commit(
@@ -1184,6 +3342,9 @@ impl Database {
                 project_id,
                 commit_sha
             );
+            // Index the new latest reports if the deleted ones were indexed
+            drop(conn);
+            self.rebuild_symbol_index().await?;
         }
         Ok(deleted_count as usize)
     }
@@ -1215,7 +3376,199 @@ fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>> {
     }
 }
 
+/// Decodes the overall measures from stored report data.
+fn decode_measures(data: &[u8]) -> Result<Measures> {
+    let data = decompress(data).context("Failed to decompress report data")?;
+    let report = Report::decode(data.as_ref()).context("Failed to decode report")?;
+    Ok(report.measures.unwrap_or_default())
+}
+
+/// Decodes the measures of a stored report summary, along with those of `category` if the
+/// report or project defines it. `category_measures` are the stored project category measures.
+fn decode_category_measures(
+    data: &[u8],
+    category: Option<&str>,
+    category_measures: Option<&[u8]>,
+) -> Result<(Measures, Option<Measures>)> {
+    let data = decompress(data).context("Failed to decompress report data")?;
+    let report = Report::decode(data.as_ref()).context("Failed to decode report")?;
+    let measures = report.measures.unwrap_or_default();
+    // Categories defined by the report take precedence over project categories
+    let report_category = category.and_then(|category| {
+        report.categories.iter().find(|c| c.id.eq_ignore_ascii_case(category))
+    });
+    let category_measures = match (report_category, category_measures) {
+        (Some(c), _) => c.measures,
+        (None, Some(data)) => {
+            Some(Measures::decode(data).context("Failed to decode category measures")?)
+        }
+        (None, None) => None,
+    };
+    Ok((measures, category_measures))
+}
+
 #[inline]
 fn to_primitive_date_time(date: UtcDateTime) -> String {
     date.format(format_description!("[year]-[month]-[day] [hour]:[minute]:[second]")).unwrap()
 }
+
+/// Compiles the rules of project categories, skipping any that are invalid.
+fn custom_category_matchers(categories: &[CustomCategory]) -> Vec<(&str, UnitMatcher)> {
+    categories
+        .iter()
+        .filter_map(|category| match category.matcher() {
+            Ok(matcher) => Some((category.id.as_str(), matcher)),
+            Err(e) => {
+                tracing::warn!("Invalid rules for category {}: {}", category.id, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Picks the ID of a new project: the repository ID for the repository's first project,
+/// otherwise the next ID in the shared range starting at [`SHARED_PROJECT_ID_BASE`].
+fn next_project_id(repository_id: i64, repository_id_taken: bool, max_id: Option<i64>) -> i64 {
+    if repository_id_taken {
+        max_id.map_or(SHARED_PROJECT_ID_BASE, |id| id + 1).max(SHARED_PROJECT_ID_BASE)
+    } else {
+        repository_id
+    }
+}
+
+/// Quotes a query as an FTS5 phrase. With the trigram tokenizer, a phrase matches any value
+/// containing it as a case-insensitive substring.
+fn fts_phrase(query: &str) -> String { format!("\"{}\"", query.replace('"', "\"\"")) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_project_id() {
+        assert_eq!(next_project_id(1234, false, None), 1234);
+        assert_eq!(next_project_id(1234, false, Some(5678)), 1234);
+        // The first shared project starts the shared range
+        assert_eq!(next_project_id(1234, true, Some(5678)), SHARED_PROJECT_ID_BASE);
+        assert_eq!(
+            next_project_id(1234, true, Some(SHARED_PROJECT_ID_BASE + 2)),
+            SHARED_PROJECT_ID_BASE + 3
+        );
+    }
+
+    #[test]
+    fn test_fts_phrase() {
+        assert_eq!(fts_phrase("daAlink_c"), "\"daAlink_c\"");
+        // Operators and wildcards are matched literally
+        assert_eq!(fts_phrase("a OR b*"), "\"a OR b*\"");
+        assert_eq!(fts_phrase("operator\"\""), "\"operator\"\"\"\"\"");
+    }
+
+    /// Opens a new database in a temporary file.
+    async fn test_db(name: &str) -> Arc<Database> {
+        let path =
+            std::env::temp_dir().join(format!("decomp-dev-{name}-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let url = format!("sqlite://{}", path.display());
+        Database::new(&DbConfig { url: url.clone(), jobs_url: url }).await.unwrap()
+    }
+
+    fn test_project() -> Project {
+        Project {
+            id: 1,
+            repository_id: 1,
+            owner: "owner".into(),
+            repo: "repo".into(),
+            ..Default::default()
+        }
+    }
+
+    async fn insert_test_report(db: &Database, sha: &str, timestamp: i64, matched_code: u64) {
+        let commit = Commit {
+            sha: sha.to_string(),
+            message: None,
+            timestamp: UtcDateTime::from_unix_timestamp(timestamp).unwrap(),
+        };
+        let report = Report {
+            measures: Some(Measures { total_code: 100, matched_code, ..Default::default() }),
+            version: REPORT_VERSION,
+            ..Default::default()
+        };
+        db.insert_report(&test_project(), &commit, "v1", Box::new(report)).await.unwrap();
+    }
+
+    async fn matched_code_deltas(db: &Database) -> Vec<(String, Option<i64>)> {
+        sqlx::query_as("SELECT git_commit, matched_code_delta FROM reports ORDER BY timestamp")
+            .fetch_all(&db.pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update_report_deltas() {
+        let db = test_db("report-deltas").await;
+        insert_test_report(&db, "a", 1000, 10).await;
+        insert_test_report(&db, "c", 3000, 50).await;
+        assert_eq!(matched_code_deltas(&db).await, [
+            ("a".to_string(), None),
+            ("c".to_string(), Some(40))
+        ]);
+
+        // Inserting a report in between also updates the delta of the report after it
+        insert_test_report(&db, "b", 2000, 35).await;
+        assert_eq!(matched_code_deltas(&db).await, [
+            ("a".to_string(), None),
+            ("b".to_string(), Some(25)),
+            ("c".to_string(), Some(15)),
+        ]);
+
+        // A report before the first one becomes the baseline
+        insert_test_report(&db, "0", 500, 0).await;
+        assert_eq!(matched_code_deltas(&db).await[..2], [
+            ("0".to_string(), None),
+            ("a".to_string(), Some(10)),
+        ]);
+        db.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_contributor_stats() {
+        let db = test_db("contributor-stats").await;
+        insert_test_report(&db, "base", 1000, 0).await;
+        insert_test_report(&db, "a", 2000, 10).await;
+        insert_test_report(&db, "b", 3000, 30).await;
+        insert_test_report(&db, "c", 4000, 35).await;
+        insert_test_report(&db, "d", 5000, 45).await;
+        let author = |name: &str, login: Option<&str>, pr_login: Option<&str>| CommitAuthor {
+            name: Some(name.to_string()),
+            login: login.map(str::to_string),
+            pr_number: pr_login.map(|_| 1),
+            pr_login: pr_login.map(str::to_string),
+        };
+        // Committed from an email that isn't linked to the account
+        db.update_commit_author(1, "a", &author("Alice", None, None)).await.unwrap();
+        db.update_commit_author(1, "b", &author("Alice", Some("alice"), None)).await.unwrap();
+        db.update_commit_author(1, "c", &author("Alice", None, None)).await.unwrap();
+        // Pull request authors take precedence over commit authors
+        db.update_commit_author(1, "d", &author("Alice", Some("alice"), Some("bob")))
+            .await
+            .unwrap();
+
+        let stats = db.get_contributor_stats(1, "v1", None).await.unwrap();
+        let summary = stats
+            .iter()
+            .map(|s| (s.contributor.as_str(), s.commits, s.matched_code))
+            .collect::<Vec<_>>();
+        assert_eq!(summary, [("alice", 3, 35), ("bob", 1, 10)]);
+
+        let since = UtcDateTime::from_unix_timestamp(2500).unwrap();
+        let stats = db.get_contributor_stats(1, "v1", Some(since)).await.unwrap();
+        assert_eq!(stats[0].contributor, "alice");
+        assert_eq!(stats[0].commits, 2);
+
+        let commits = db.get_contributor_commits(1, "v1", "alice", 10).await.unwrap();
+        let shas = commits.iter().map(|c| c.commit.sha.as_str()).collect::<Vec<_>>();
+        assert_eq!(shas, ["c", "b", "a"]);
+        db.close().await;
+    }
+}
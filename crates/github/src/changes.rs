@@ -13,6 +13,8 @@ use octocrab::{
     models::{RepositoryId, pulls::PullRequest},
 };
 
+use crate::counted;
+
 pub fn generate_changes(previous: &Report, current: &Report) -> Result<Changes> {
     let mut changes = Changes { from: previous.measures, to: current.measures, units: vec![] };
     for prev_unit in &previous.units {
@@ -458,6 +460,7 @@ pub fn generate_comment(
 }
 
 /// Post or update a PR comment with the report.
+#[tracing::instrument(skip_all, fields(pull = pull.number))]
 pub async fn post_pr_comment(
     client: &Octocrab,
     project: &Project,
@@ -488,17 +491,14 @@ pub async fn post_pr_comment(
             format!("{}\n\n---\n\n{}", existing_body.trim(), new_section)
         };
 
-        client
-            .pulls(&project.owner, &project.repo)
-            .update(pull.number)
-            .body(new_body)
-            .send()
-            .await
-            .context("Failed to update pull request body")?;
+        let request =
+            client.pulls(&project.owner, &project.repo).update(pull.number).body(new_body).send();
+        counted("pull_update", request).await.context("Failed to update pull request body")?;
     } else {
         let issues = client.issues_by_id(repository_id);
         // Only fetch first page for now
-        let existing_comments = issues.list_comments(pull.number).send().await?;
+        let existing_comments =
+            counted("issue_comments", issues.list_comments(pull.number).send()).await?;
 
         // Find existing report comments
         let existing_report_comments: Vec<_> = existing_comments
@@ -511,23 +511,21 @@ pub async fn post_pr_comment(
 
         if let Some(first_comment) = existing_report_comments.first() {
             // Update the first comment
-            issues
-                .update_comment(first_comment.id, combined_comment.to_string())
+            let request = issues.update_comment(first_comment.id, combined_comment.to_string());
+            counted("comment_update", request)
                 .await
                 .context("Failed to update existing comment")?;
 
             // Delete any additional report comments
             for comment in existing_report_comments.iter().skip(1) {
-                if let Err(e) = issues.delete_comment(comment.id).await {
+                if let Err(e) = counted("comment_delete", issues.delete_comment(comment.id)).await {
                     tracing::warn!("Failed to delete old comment {}: {}", comment.id, e);
                 }
             }
         } else {
             // Create new comment
-            issues
-                .create_comment(pull.number, combined_comment.to_string())
-                .await
-                .context("Failed to create comment")?;
+            let request = issues.create_comment(pull.number, combined_comment.to_string());
+            counted("comment_create", request).await.context("Failed to create comment")?;
         }
     }
 
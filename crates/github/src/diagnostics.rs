@@ -0,0 +1,119 @@
+//! Diagnostics for project setup, explaining how workflow runs and artifacts were interpreted.
+
+use decomp_dev_core::models::Project;
+use octocrab::{
+    Octocrab,
+    models::{RunId, workflows::Run},
+};
+
+use crate::{artifact_version, download_artifact, list_run_artifacts};
+
+#[derive(Debug, Clone, Default)]
+pub struct SetupDiagnostics {
+    /// Branch that workflow runs were fetched for
+    pub branch: String,
+    pub workflows: Vec<WorkflowDiagnostics>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkflowDiagnostics {
+    pub workflow_id: String,
+    /// Explanation when no run could be examined
+    pub note: Option<String>,
+    pub run: Option<RunDiagnostics>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunDiagnostics {
+    pub run_id: RunId,
+    pub html_url: String,
+    pub head_sha: String,
+    pub artifacts: Vec<ArtifactDiagnostics>,
+    /// Error listing the run's artifacts
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArtifactDiagnostics {
+    pub name: String,
+    /// Version interpreted from the artifact name, or why the artifact was skipped
+    pub version: Result<String, String>,
+    pub files: Vec<FileDiagnostics>,
+    /// Versions of the reports that were parsed successfully
+    pub reports: Vec<String>,
+    /// Error downloading or reading the artifact
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileDiagnostics {
+    pub path: String,
+    pub accepted: bool,
+    pub reason: String,
+}
+
+impl RunDiagnostics {
+    pub fn has_reports(&self) -> bool { self.artifacts.iter().any(|a| !a.reports.is_empty()) }
+}
+
+/// Examines every artifact of a workflow run, downloading the ones that look like reports for
+/// the project. Errors are recorded in the diagnostics.
+pub(crate) async fn diagnose_workflow_run(
+    client: &Octocrab,
+    project: &Project,
+    run: &Run,
+) -> RunDiagnostics {
+    let mut result = RunDiagnostics {
+        run_id: run.id,
+        html_url: run.html_url.to_string(),
+        head_sha: run.head_sha.clone(),
+        artifacts: Vec::new(),
+        error: None,
+    };
+    let (owner, repo) = (project.owner.as_str(), project.repo.as_str());
+    let artifacts = match list_run_artifacts(client, None, owner, repo, run.id).await {
+        Ok(artifacts) => artifacts,
+        Err(e) => {
+            result.error = Some(format!("{e:#}"));
+            return result;
+        }
+    };
+    let names = artifacts.iter().map(|a| a.name.as_str()).collect::<Vec<_>>();
+    for artifact in &artifacts {
+        let mut diagnostics = ArtifactDiagnostics {
+            name: artifact.name.clone(),
+            version: Err(String::new()),
+            files: Vec::new(),
+            reports: Vec::new(),
+            error: None,
+        };
+        if artifact.expired {
+            diagnostics.version = Err("Artifact has expired".to_string());
+            result.artifacts.push(diagnostics);
+            continue;
+        }
+        diagnostics.version = artifact_version(&artifact.name, &names)
+            .map_err(str::to_string)
+            .and_then(|version| match project.select_artifact_version(&version) {
+                Some(_) => Ok(version),
+                None => Err(format!(
+                    "Version {version} is not selected by the project's artifact rules"
+                )),
+            });
+        if let Ok(version) = &diagnostics.version {
+            match download_artifact(client, owner, repo, artifact.id, version).await {
+                Ok(contents) => {
+                    diagnostics.reports =
+                        contents.reports.into_iter().map(|(version, _)| version).collect();
+                    diagnostics.files = contents.files;
+                    if diagnostics.files.is_empty() {
+                        diagnostics.error = Some("Artifact contains no files".to_string());
+                    }
+                }
+                Err(e) => diagnostics.error = Some(format!("{e:#}")),
+            }
+        }
+        result.artifacts.push(diagnostics);
+    }
+    result
+}
@@ -1,11 +1,14 @@
 pub mod changes;
+pub mod diagnostics;
 pub mod graphql;
+pub mod ratelimit;
 pub mod webhook;
 
 use std::{
     collections::{HashMap, HashSet, hash_map::Entry},
     ffi::{OsStr, OsString},
     io::{Cursor, Read},
+    path::Path,
     pin::pin,
     sync::{Arc, OnceLock},
     time::Duration,
@@ -14,20 +17,24 @@ use std::{
 use anyhow::{Context, Result, anyhow, bail};
 use decomp_dev_core::{
     config::GitHubConfig,
-    models::{Commit, Project},
+    models::{Commit, CommitAuthor, Project},
 };
 use decomp_dev_db::Database;
+use diagnostics::{FileDiagnostics, SetupDiagnostics, WorkflowDiagnostics, diagnose_workflow_run};
 use futures_util::TryStreamExt;
 use http::StatusCode;
 use objdiff_core::bindings::report::Report;
 use octocrab::{
-    GitHubError, Octocrab,
+    FromResponse, GitHubError, Octocrab, Page,
     models::{
         ArtifactId, InstallationId, InstallationRepositories, Repository, RunId,
-        repos::RepoCommitPage, workflows::HeadCommit,
+        pulls::PullRequest,
+        repos::{RepoCommit, RepoCommitPage},
+        workflows::{HeadCommit, WorkflowListArtifact},
     },
     params::actions::ArchiveFormat,
 };
+use ratelimit::{RateLimitKey, RateLimitTracker, RateLimits};
 use regex::Regex;
 use time::UtcDateTime;
 use tokio::{
@@ -35,11 +42,13 @@ use tokio::{
     task::JoinSet,
     time::sleep,
 };
+use tracing::Instrument;
 
 #[derive(Clone)]
 pub struct GitHub {
     pub client: Octocrab,
     pub installations: Option<Arc<Mutex<Installations>>>,
+    pub rate_limits: Arc<RateLimits>,
 }
 
 pub struct CachedInstallation {
@@ -91,8 +100,16 @@ impl Installations {
     }
 
     pub async fn client_for_repo(&mut self, repo_id: u64) -> Result<Option<Octocrab>> {
-        if let Some(installation_id) = self.repo_to_installation.get(&repo_id) {
-            return self.client_for_installation(*installation_id).await.map(Some);
+        Ok(self.client_and_installation_for_repo(repo_id).await?.map(|(client, _)| client))
+    }
+
+    pub async fn client_and_installation_for_repo(
+        &mut self,
+        repo_id: u64,
+    ) -> Result<Option<(Octocrab, InstallationId)>> {
+        if let Some(&installation_id) = self.repo_to_installation.get(&repo_id) {
+            let client = self.client_for_installation(installation_id).await?;
+            return Ok(Some((client, installation_id)));
         }
         Ok(None)
     }
@@ -207,7 +224,11 @@ impl GitHub {
         } else {
             None
         };
-        Ok(Arc::new(Self { client, installations }))
+        let rate_limits = Arc::new(RateLimits::default());
+        if let Err(e) = rate_limits.budget(RateLimitKey::Token, &client).await {
+            tracing::warn!("Failed to fetch rate limit: {:?}", e);
+        }
+        Ok(Arc::new(Self { client, installations, rate_limits }))
     }
 
     pub async fn get_commit(
@@ -216,7 +237,8 @@ impl GitHub {
         repo: &str,
         sha: &str,
     ) -> Result<Option<RepoCommitPage>> {
-        match self.client.repos(owner, repo).list_commits().sha(sha).per_page(1).send().await {
+        let request = self.client.repos(owner, repo).list_commits().sha(sha).per_page(1).send();
+        match counted("commits", request).await {
             Ok(page) => Ok(page.items.into_iter().next().map(|c| c.commit)),
             Err(octocrab::Error::GitHub { source, .. })
                 if matches!(*source, GitHubError { status_code: StatusCode::NOT_FOUND, .. }) =>
@@ -228,35 +250,125 @@ impl GitHub {
     }
 
     pub async fn client_for(&self, repo_id: u64) -> Result<Octocrab> {
+        self.client_and_key_for(repo_id).await.map(|(client, _)| client)
+    }
+
+    /// Returns the client for a repository along with the rate limit it draws from.
+    pub async fn client_and_key_for(&self, repo_id: u64) -> Result<(Octocrab, RateLimitKey)> {
         if let Some(installations) = &self.installations {
             let mut installations = installations.lock().await;
-            if let Some(client) = installations.client_for_repo(repo_id).await? {
-                return Ok(client);
+            if let Some((client, installation_id)) =
+                installations.client_and_installation_for_repo(repo_id).await?
+            {
+                return Ok((client, RateLimitKey::Installation(installation_id)));
             }
         }
-        Ok(self.client.clone())
+        Ok((self.client.clone(), RateLimitKey::Token))
+    }
+
+    /// Returns a tracker that records the budget reported by responses drawing from `key`.
+    pub fn tracker(&self, key: RateLimitKey) -> RateLimitTracker {
+        RateLimitTracker::new(self.rate_limits.clone(), key)
+    }
+}
+
+/// Counts a GitHub API request in `github_api_requests_total`.
+pub(crate) fn count_request(endpoint: &'static str, status: String) {
+    metrics::counter!("github_api_requests_total", "endpoint" => endpoint, "status" => status)
+        .increment(1);
+}
+
+/// Awaits a request made through octocrab's typed API, counting it by endpoint and status.
+pub async fn counted<T>(
+    endpoint: &'static str,
+    request: impl Future<Output = octocrab::Result<T>>,
+) -> octocrab::Result<T> {
+    let result = request.await;
+    let status = match &result {
+        // The typed API doesn't expose the exact status of successful responses
+        Ok(_) => "2xx".to_string(),
+        Err(octocrab::Error::GitHub { source, .. }) => source.status_code.as_u16().to_string(),
+        Err(_) => "error".to_string(),
+    };
+    count_request(endpoint, status);
+    result
+}
+
+/// Sends a GET request through octocrab and parses the response into an octocrab model,
+/// recording the rate limit budget reported in its headers. The typed request builders don't
+/// expose the headers of their responses.
+async fn get_tracked<R: FromResponse>(
+    client: &Octocrab,
+    tracker: Option<&RateLimitTracker>,
+    endpoint: &'static str,
+    uri: String,
+) -> octocrab::Result<R> {
+    let response = match client._get(uri).await {
+        Ok(response) => response,
+        Err(e) => {
+            count_request(endpoint, "error".to_string());
+            return Err(e);
+        }
+    };
+    count_request(endpoint, response.status().as_u16().to_string());
+    if let Some(tracker) = tracker {
+        tracker.record(response.headers());
+    }
+    R::from_response(octocrab::map_github_error(response).await?).await
+}
+
+/// Fetches every page of a list endpoint by following the `Link` headers of its responses.
+async fn get_all_tracked<T: serde::de::DeserializeOwned>(
+    client: &Octocrab,
+    tracker: Option<&RateLimitTracker>,
+    endpoint: &'static str,
+    uri: String,
+) -> octocrab::Result<Vec<T>> {
+    let mut page: Page<T> = get_tracked(client, tracker, endpoint, uri).await?;
+    let mut items = page.take_items();
+    while let Some(next) = page.next.take() {
+        page = get_tracked(client, tracker, endpoint, next.to_string()).await?;
+        items.extend(page.take_items());
     }
+    Ok(items)
 }
 
+/// Estimated requests to list and download the artifacts of a single workflow run.
+const ARTIFACT_FETCH_COST: u64 = 3;
+
+/// Summary of a project refresh.
+#[derive(Debug, Clone, Default)]
+pub struct RefreshResult {
+    /// Number of report artifacts found in newly processed workflow runs, including those
+    /// selected by other projects in the repository
+    pub artifacts: usize,
+    pub reports_inserted: usize,
+    /// Versions of the inserted reports
+    pub versions: Vec<String>,
+}
+
+#[tracing::instrument(skip(github, db, client_override))]
 pub async fn refresh_project(
     github: &GitHub,
     db: &Database,
-    repo_id: u64,
+    project_id: u64,
     client_override: Option<&Octocrab>,
     full_refresh: bool,
-) -> Result<usize> {
+) -> Result<RefreshResult> {
     let mut project_info = db
-        .get_project_info_by_id(repo_id, None)
+        .get_project_info_by_id(project_id, None)
         .await
         .context("Failed to fetch project info")?
-        .with_context(|| format!("Failed to fetch project info for ID {repo_id}"))?;
-    let client = match client_override {
-        Some(client) => client.clone(),
-        None => github.client_for(repo_id).await?,
+        .with_context(|| format!("Failed to fetch project info for ID {project_id}"))?;
+    let repo_id = project_info.project.repository_id;
+    // Requests made with an overriding (user) client don't draw from our budgets
+    let (client, rate_limit_key) = match client_override {
+        Some(client) => (client.clone(), None),
+        None => github.client_and_key_for(repo_id).await.map(|(c, k)| (c, Some(k)))?,
     };
-    let repo = client
-        .repos_by_id(project_info.project.id)
-        .get()
+    let rate_limits = github.rate_limits.clone();
+    let tracker = rate_limit_key.map(|key| github.tracker(key));
+    let repo = counted("repo", client.repos_by_id(repo_id).get())
         .await
         .with_context(|| format!("Failed to fetch repo for ID {repo_id}"))?;
     let branch = repo.default_branch.as_deref().unwrap_or("main");
@@ -270,39 +382,52 @@ pub async fn refresh_project(
             owner.login,
             repo.name
         );
-        db.update_project_owner_repo(project_info.project.id, &owner.login, &repo.name).await?;
+        db.update_project_owner_repo(repo_id, &owner.login, &repo.name).await?;
         project_info = db
-            .get_project_info_by_id(repo_id, None)
+            .get_project_info_by_id(project_id, None)
             .await
             .context("Failed to fetch project info")?
-            .with_context(|| format!("Failed to fetch project info for ID {repo_id}"))?;
+            .with_context(|| format!("Failed to fetch project info for ID {project_id}"))?;
     }
 
     let project = &project_info.project;
     tracing::debug!("Refreshing project {}/{}", project.owner, project.repo);
+    // Only needed when the project has no artifact rules of its own
+    let siblings = if project.has_artifact_rules() {
+        vec![]
+    } else {
+        db.get_repository_projects(repo_id)
+            .await
+            .context("Failed to fetch repository projects")?
+            .into_iter()
+            .map(|info| info.project)
+            .collect::<Vec<_>>()
+    };
 
     let workflow_ids = if let Some(workflow_id) = &project.workflow_id {
         vec![workflow_id.clone()]
     } else {
-        let workflows = client
-            .workflows(&project.owner, &project.repo)
-            .list()
-            .send()
-            .await
-            .context("Failed to fetch workflows")?;
+        let workflows =
+            counted("workflows", client.workflows(&project.owner, &project.repo).list().send())
+                .await
+                .context("Failed to fetch workflows")?;
         workflows.items.into_iter().map(|w| w.path).collect()
     };
     if workflow_ids.is_empty() {
         tracing::warn!("No workflows found for {}/{}", project.owner, project.repo);
-        return Ok(0);
+        return Ok(RefreshResult::default());
     }
+    let mut refresh_result = RefreshResult::default();
     for workflow_id in workflow_ids {
         let workflow_id =
             workflow_id.strip_prefix(".github/workflows/").unwrap_or(workflow_id.as_str());
         let mut runs = vec![];
         let mut page = 1u32;
         'outer: loop {
-            let result = client
+            if let Some(key) = rate_limit_key {
+                rate_limits.acquire(key, &client, 1).await?;
+            }
+            let request = client
                 .workflows(&project.owner, &project.repo)
                 .list_runs(workflow_id)
                 .branch(branch)
@@ -310,8 +435,8 @@ pub async fn refresh_project(
                 .status("completed")
                 .exclude_pull_requests(true)
                 .page(page)
-                .send()
-                .await;
+                .send();
+            let result = counted("workflow_runs", request).await;
             let items = match result {
                 Ok(result) if result.items.is_empty() => break,
                 Ok(result) => result.items,
@@ -351,6 +476,7 @@ pub async fn refresh_project(
             run_id: RunId,
             commit: Commit,
             result: Result<WorkflowRunArtifacts>,
+            author: Option<CommitAuthor>,
         }
         let sem = Arc::new(Semaphore::new(10));
         let mut set = JoinSet::new();
@@ -361,33 +487,103 @@ pub async fn refresh_project(
             let repo = project.repo.clone();
             let client = client.clone();
             let db = db.clone();
+            let rate_limits = rate_limits.clone();
+            let tracker = tracker.clone();
             let run_id = run.id;
             let commit = commit_from_head_commit(&run.head_commit);
-            set.spawn(async move {
-                let _permit = sem.acquire().await.unwrap();
-                match db.report_exists(project_id, &commit.sha).await {
-                    Ok(true) => {
-                        return TaskResult {
-                            run_id,
-                            commit,
-                            result: Ok(WorkflowRunArtifacts { artifacts: vec![] }),
-                        };
+            set.spawn(
+                async move {
+                    let _permit = sem.acquire().await.unwrap();
+                    match db.report_exists(project_id, &commit.sha).await {
+                        Ok(true) => {
+                            // Attribute reports imported before authors were recorded, as long
+                            // as the budget allows. Otherwise a later refresh retries.
+                            let missing = db
+                                .commit_author_missing(project_id, &commit.sha)
+                                .await
+                                .unwrap_or_else(|e| {
+                                    tracing::warn!(
+                                        "Failed to check author of commit {}: {:?}",
+                                        commit.sha,
+                                        e
+                                    );
+                                    false
+                                });
+                            let fetch = match rate_limit_key {
+                                Some(key) if missing => {
+                                    rate_limits
+                                        .try_acquire(key, &client, COMMIT_AUTHOR_FETCH_COST)
+                                        .await
+                                }
+                                _ => missing,
+                            };
+                            let author = if fetch {
+                                try_fetch_commit_author(
+                                    &client,
+                                    tracker.as_ref(),
+                                    &owner,
+                                    &repo,
+                                    &commit.sha,
+                                )
+                                .await
+                            } else {
+                                None
+                            };
+                            return TaskResult {
+                                run_id,
+                                commit,
+                                result: Ok(WorkflowRunArtifacts { artifacts: vec![] }),
+                                author,
+                            };
+                        }
+                        Ok(false) => {}
+                        Err(e) => {
+                            return TaskResult { run_id, commit, result: Err(e), author: None };
+                        }
+                    }
+                    if let Some(key) = rate_limit_key
+                        && let Err(e) = rate_limits.acquire(key, &client, ARTIFACT_FETCH_COST).await
+                    {
+                        return TaskResult { run_id, commit, result: Err(e), author: None };
                     }
-                    Ok(false) => {}
-                    Err(e) => return TaskResult { run_id, commit, result: Err(e) },
+                    let result = fetch_workflow_run_artifacts(
+                        &client,
+                        tracker.as_ref(),
+                        &owner,
+                        &repo,
+                        run.id,
+                        None,
+                    )
+                    .await;
+                    let mut author = None;
+                    if result.as_ref().is_ok_and(|r| !r.artifacts.is_empty()) {
+                        if let Some(key) = rate_limit_key
+                            && let Err(e) =
+                                rate_limits.acquire(key, &client, COMMIT_AUTHOR_FETCH_COST).await
+                        {
+                            return TaskResult { run_id, commit, result: Err(e), author: None };
+                        }
+                        author = try_fetch_commit_author(
+                            &client,
+                            tracker.as_ref(),
+                            &owner,
+                            &repo,
+                            &commit.sha,
+                        )
+                        .await;
+                    }
+                    TaskResult { run_id, commit, result, author }
                 }
-                let result =
-                    fetch_workflow_run_artifacts(&client, &owner, &repo, run.id, None).await;
-                TaskResult { run_id, commit, result }
-            });
+                .in_current_span(),
+            );
         }
-        let mut imported_artifacts = 0;
         while let Some(join_result) = set.join_next().await {
             match join_result {
                 Ok(TaskResult {
                     run_id,
                     commit,
                     result: Ok(WorkflowRunArtifacts { artifacts }),
+                    author,
                 }) => {
                     tracing::debug!(
                         "Processed workflow run {} ({}) (artifacts {})",
@@ -395,21 +591,38 @@ pub async fn refresh_project(
                         commit.sha,
                         artifacts.len()
                     );
+                    refresh_result.artifacts += artifacts.len();
                     for artifact in artifacts {
+                        // Other projects in the repository may build from the same workflow
+                        let Some(version) =
+                            project.claim_artifact_version(&artifact.version, &siblings)
+                        else {
+                            continue;
+                        };
                         let start = std::time::Instant::now();
-                        db.insert_report(project, &commit, &artifact.version, artifact.report)
-                            .await?;
+                        db.insert_report(project, &commit, &version, artifact.report).await?;
                         let duration = start.elapsed();
+                        metrics::histogram!("report_insert_duration_seconds")
+                            .record(duration.as_secs_f64());
                         tracing::info!(
                             "Inserted report {} ({}) in {}ms",
-                            artifact.version,
+                            version,
                             commit.sha,
                             duration.as_millis()
                         );
-                        imported_artifacts += 1;
+                        refresh_result.reports_inserted += 1;
+                        if !refresh_result.versions.contains(&version) {
+                            refresh_result.versions.push(version);
+                        }
+                    }
+                    if let Some(author) = author
+                        && let Err(e) =
+                            db.update_commit_author(project.id, &commit.sha, &author).await
+                    {
+                        tracing::warn!("Failed to update author of commit {}: {:?}", commit.sha, e);
                     }
                 }
-                Ok(TaskResult { run_id, commit, result: Err(e) }) => {
+                Ok(TaskResult { run_id, commit, result: Err(e), .. }) => {
                     tracing::error!(
                         "Failed to process workflow run {} ({}): {:?}",
                         run_id,
@@ -423,15 +636,15 @@ pub async fn refresh_project(
             }
         }
 
-        if imported_artifacts > 0 {
+        if refresh_result.reports_inserted > 0 {
             if project.workflow_id.is_none() {
                 db.update_project_workflow_id(project.id, workflow_id).await?;
             }
-            return Ok(imported_artifacts);
+            return Ok(refresh_result);
         }
     }
 
-    Ok(0)
+    Ok(refresh_result)
 }
 
 pub struct WorkflowRunArtifacts {
@@ -451,52 +664,22 @@ struct WorkflowRunArtifactList {
 
 async fn map_workflow_run_artifacts(
     client: &Octocrab,
+    tracker: Option<&RateLimitTracker>,
     owner: &str,
     repo: &str,
     run_id: RunId,
 ) -> Result<Vec<WorkflowRunArtifactList>> {
-    let artifacts = client
-        .all_pages(
-            client
-                .actions()
-                .list_workflow_run_artifacts(owner, repo, run_id)
-                .send()
-                .await
-                .context("Failed to fetch artifacts")?
-                .value
-                .unwrap_or_default(),
-        )
-        .await?;
+    let artifacts = list_run_artifacts(client, tracker, owner, repo, run_id).await?;
     tracing::debug!("Run {} (artifacts {})", run_id, artifacts.len());
-    static REGEX: OnceLock<Regex> = OnceLock::new();
-    let regex = REGEX
-        .get_or_init(|| Regex::new(r"^(?P<version>[A-z0-9_.\-]+)[_-]report(?:[_-].*)?$").unwrap());
-    static MAPS_REGEX: OnceLock<Regex> = OnceLock::new();
-    let maps_regex =
-        MAPS_REGEX.get_or_init(|| Regex::new(r"^(?P<version>[A-z0-9_\-]+)_maps$").unwrap());
+    let names = artifacts.iter().map(|a| a.name.as_str()).collect::<Vec<_>>();
     let mut result = Vec::new();
     for artifact in &artifacts {
         if artifact.expired {
             continue;
         }
-        let version =
-            if let Some(version) = regex.captures(&artifact.name).and_then(|c| c.name("version")) {
-                version.as_str().to_string()
-            } else if artifact.name == "progress" || artifact.name == "progress.json" {
-                // bfbb compatibility
-                if let Some(version) = artifacts.iter().find_map(|a| {
-                    maps_regex
-                        .captures(&a.name)
-                        .and_then(|c| c.name("version"))
-                        .map(|m| m.as_str().to_string())
-                }) {
-                    version
-                } else {
-                    continue;
-                }
-            } else {
-                continue;
-            };
+        let Ok(version) = artifact_version(&artifact.name, &names) else {
+            continue;
+        };
         result.push(WorkflowRunArtifactList {
             version,
             name: artifact.name.clone(),
@@ -506,8 +689,76 @@ async fn map_workflow_run_artifacts(
     Ok(result)
 }
 
+/// Lists every artifact of a workflow run, including expired ones.
+pub(crate) async fn list_run_artifacts(
+    client: &Octocrab,
+    tracker: Option<&RateLimitTracker>,
+    owner: &str,
+    repo: &str,
+    run_id: RunId,
+) -> Result<Vec<WorkflowListArtifact>> {
+    get_all_tracked(
+        client,
+        tracker,
+        "artifacts",
+        format!("/repos/{owner}/{repo}/actions/runs/{run_id}/artifacts?per_page=100"),
+    )
+    .await
+    .context("Failed to fetch artifacts")
+}
+
+/// Interprets the report version from an artifact name, given the names of all artifacts in
+/// the run. Returns the reason the artifact is skipped if it doesn't contain a report.
+pub(crate) fn artifact_version(name: &str, all_names: &[&str]) -> Result<String, &'static str> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX
+        .get_or_init(|| Regex::new(r"^(?P<version>[A-z0-9_.\-]+)[_-]report(?:[_-].*)?$").unwrap());
+    static MAPS_REGEX: OnceLock<Regex> = OnceLock::new();
+    let maps_regex =
+        MAPS_REGEX.get_or_init(|| Regex::new(r"^(?P<version>[A-z0-9_\-]+)_maps$").unwrap());
+    if let Some(version) = regex.captures(name).and_then(|c| c.name("version")) {
+        Ok(version.as_str().to_string())
+    } else if name == "progress" || name == "progress.json" {
+        // bfbb compatibility
+        all_names
+            .iter()
+            .find_map(|n| {
+                maps_regex
+                    .captures(n)
+                    .and_then(|c| c.name("version"))
+                    .map(|m| m.as_str().to_string())
+            })
+            .ok_or("No {version}_maps artifact found to determine the version of progress")
+    } else {
+        Err("Name does not match {version}_report, {version}-report or progress")
+    }
+}
+
+pub(crate) enum ReportFileMatch {
+    Accepted,
+    Rejected(String),
+}
+
+/// Determines whether a file inside an artifact archive is a report.
+pub(crate) fn match_report_file(path: &Path, version: &str) -> ReportFileMatch {
+    let version_report = OsString::from(format!("{}_report", version));
+    let stem = path.file_stem();
+    if stem == Some(OsStr::new("report"))
+        || stem == Some(&version_report)
+        || stem == Some(OsStr::new("progress"))
+    {
+        ReportFileMatch::Accepted
+    } else {
+        ReportFileMatch::Rejected(format!(
+            "File name must be report.*, {version}_report.* or progress.*"
+        ))
+    }
+}
+
+#[tracing::instrument(skip(client, tracker, base_versions))]
 pub async fn fetch_workflow_run_artifacts(
     client: &Octocrab,
+    tracker: Option<&RateLimitTracker>,
     owner: &str,
     repo: &str,
     run_id: RunId,
@@ -517,7 +768,7 @@ pub async fn fetch_workflow_run_artifacts(
     // expected base_versions, retry a few times to ensure we get them all.
     let mut attempt = 0;
     let artifacts = loop {
-        let artifacts = map_workflow_run_artifacts(client, owner, repo, run_id).await?;
+        let artifacts = map_workflow_run_artifacts(client, tracker, owner, repo, run_id).await?;
         if let Some(base_versions) = &base_versions {
             if base_versions
                 .iter()
@@ -554,29 +805,49 @@ pub async fn fetch_workflow_run_artifacts(
     let mut set = JoinSet::new();
     struct TaskResult {
         artifact_name: String,
-        result: DownloadArtifactResult,
+        result: Result<ArtifactContents>,
     }
     for artifact in artifacts {
         let sem = sem.clone();
         let client = client.clone();
         let owner = owner.to_owned();
         let repo = repo.to_owned();
-        set.spawn(async move {
-            let _permit = sem.acquire().await.unwrap();
-            TaskResult {
-                artifact_name: artifact.name,
-                result: download_artifact(client, &owner, &repo, artifact.id, artifact.version)
-                    .await,
+        set.spawn(
+            async move {
+                let _permit = sem.acquire().await.unwrap();
+                TaskResult {
+                    result: download_artifact(
+                        &client,
+                        &owner,
+                        &repo,
+                        artifact.id,
+                        &artifact.version,
+                    )
+                    .await
+                    .and_then(ArtifactContents::require_parsed),
+                    artifact_name: artifact.name,
+                }
             }
-        });
+            .in_current_span(),
+        );
     }
     while let Some(join_result) = set.join_next().await {
         match join_result {
-            Ok(TaskResult { artifact_name: name, result: Ok(reports) }) => {
-                if reports.is_empty() {
-                    tracing::warn!("No report found in workflow run {} artifact {}", run_id, name);
+            Ok(TaskResult { artifact_name: name, result: Ok(contents) }) => {
+                if contents.reports.is_empty() {
+                    tracing::warn!(
+                        "No report found in workflow run {} artifact {}: {}",
+                        run_id,
+                        name,
+                        contents
+                            .files
+                            .iter()
+                            .map(|f| format!("{} ({})", f.path, f.reason))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
                 } else {
-                    for (version, report) in reports {
+                    for (version, report) in contents.reports {
                         tracing::info!(
                             "Processed workflow run {} artifact {} ({})",
                             run_id,
@@ -603,44 +874,104 @@ pub async fn fetch_workflow_run_artifacts(
     Ok(result)
 }
 
-type DownloadArtifactResult = Result<Vec<(String, Box<Report>)>>;
+/// Reports read from an artifact archive, with the outcome of each file in it.
+pub(crate) struct ArtifactContents {
+    /// Reports parsed from the first report file, split by version for combined reports
+    pub reports: Vec<(String, Box<Report>)>,
+    pub files: Vec<FileDiagnostics>,
+    /// Why the report file couldn't be parsed, if it couldn't
+    pub parse_error: Option<anyhow::Error>,
+}
 
-async fn download_artifact(
-    client: Octocrab,
+impl ArtifactContents {
+    /// Fails if the report file couldn't be parsed. Imports use this so that a broken report
+    /// fails loudly, while diagnostics only record it for the file.
+    pub(crate) fn require_parsed(self) -> Result<Self> {
+        match self.parse_error {
+            Some(e) => Err(e),
+            None => Ok(self),
+        }
+    }
+}
+
+/// Downloads an artifact archive and reads its reports. This is used both when importing
+/// reports and when diagnosing project setup, so that both interpret artifacts identically.
+#[tracing::instrument(skip(client))]
+pub(crate) async fn download_artifact(
+    client: &Octocrab,
     owner: &str,
     repo: &str,
     artifact_id: ArtifactId,
-    version: String,
-) -> DownloadArtifactResult {
+    version: &str,
+) -> Result<ArtifactContents> {
+    let request = client.actions().download_artifact(owner, repo, artifact_id, ArchiveFormat::Zip);
     let bytes =
-        client.actions().download_artifact(owner, repo, artifact_id, ArchiveFormat::Zip).await?;
-    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
-    let version_report = OsString::from(format!("{}_report", version));
+        counted("artifact_download", request).await.context("Failed to download artifact")?;
+    read_artifact(&bytes, version)
+}
+
+/// Reads the first report file in an artifact archive, recording why every other file was
+/// skipped.
+pub(crate) fn read_artifact(bytes: &[u8], version: &str) -> Result<ArtifactContents> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).context("Invalid zip archive")?;
+    let mut contents =
+        ArtifactContents { reports: Vec::new(), files: Vec::new(), parse_error: None };
+    let mut found = false;
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
         let Some(path) = file.enclosed_name() else {
+            contents.files.push(FileDiagnostics {
+                path: name,
+                accepted: false,
+                reason: "Path escapes the archive".to_string(),
+            });
             continue;
         };
-        if path.file_stem() == Some(OsStr::new("report"))
-            || path.file_stem() == Some(&version_report)
-            || path.file_stem() == Some(OsStr::new("progress"))
-        {
-            let mut contents = Vec::with_capacity(file.size() as usize);
-            file.read_to_end(&mut contents)?;
-            let mut report = Box::new(Report::parse(&contents)?);
-            report.migrate()?;
-            // Split combined reports into individual reports
-            if version.eq_ignore_ascii_case("combined") {
-                return Ok(report
-                    .split()
-                    .into_iter()
-                    .map(|(version, report)| (version, Box::new(report)))
-                    .collect());
+        let reason = match match_report_file(&path, version) {
+            ReportFileMatch::Rejected(reason) => reason,
+            ReportFileMatch::Accepted if found => {
+                "Ignored, an earlier file was already used".to_string()
             }
-            return Ok(vec![(version, report)]);
-        }
+            ReportFileMatch::Accepted => {
+                found = true;
+                let mut data = Vec::with_capacity(file.size() as usize);
+                file.read_to_end(&mut data)?;
+                match Report::parse(&data).and_then(|mut r| r.migrate().map(|_| r)) {
+                    Ok(report) => {
+                        // Split combined reports into individual reports
+                        if version.eq_ignore_ascii_case("combined") {
+                            contents.reports.extend(
+                                report
+                                    .split()
+                                    .into_iter()
+                                    .map(|(version, report)| (version, Box::new(report))),
+                            );
+                        } else {
+                            contents.reports.push((version.to_string(), Box::new(report)));
+                        }
+                        contents.files.push(FileDiagnostics {
+                            path: name,
+                            accepted: true,
+                            reason: "Parsed report".to_string(),
+                        });
+                        continue;
+                    }
+                    Err(e) => {
+                        let reason = format!("Failed to parse report: {e}");
+                        contents.parse_error =
+                            Some(e.context(format!("Failed to parse report {name}")));
+                        reason
+                    }
+                }
+            }
+        };
+        contents.files.push(FileDiagnostics { path: name, accepted: false, reason });
     }
-    Ok(vec![])
+    Ok(contents)
 }
 
 pub fn commit_from_head_commit(commit: &HeadCommit) -> Commit {
@@ -654,44 +985,117 @@ pub fn commit_from_head_commit(commit: &HeadCommit) -> Commit {
     }
 }
 
+/// Requests made by [`fetch_commit_author`].
+pub const COMMIT_AUTHOR_FETCH_COST: u64 = 2;
+
+/// Fetches the author of a commit and the author of the pull request that merged it, if any.
+#[tracing::instrument(skip(client, tracker))]
+pub async fn fetch_commit_author(
+    client: &Octocrab,
+    tracker: Option<&RateLimitTracker>,
+    owner: &str,
+    repo: &str,
+    sha: &str,
+) -> Result<CommitAuthor> {
+    let mut commits: Page<RepoCommit> = get_tracked(
+        client,
+        tracker,
+        "commits",
+        format!("/repos/{owner}/{repo}/commits?sha={sha}&per_page=1"),
+    )
+    .await
+    .context("Failed to fetch commit")?;
+    let pulls: Page<PullRequest> = get_tracked(
+        client,
+        tracker,
+        "commit_pulls",
+        format!("/repos/{owner}/{repo}/commits/{sha}/pulls"),
+    )
+    .await
+    .context("Failed to fetch pull requests for commit")?;
+    let commit = commits.take_items().into_iter().next();
+    let pulls = pulls.items;
+    // Prefer the pull request that merged the commit over ones that merely contain it
+    let pull = pulls.iter().find(|p| p.merged_at.is_some()).or(pulls.first());
+    Ok(CommitAuthor {
+        name: commit.as_ref().and_then(|c| c.commit.author.as_ref()).map(|a| a.user.name.clone()),
+        login: commit.and_then(|c| c.author).map(|u| u.login),
+        pr_number: pull.map(|p| p.number),
+        pr_login: pull.and_then(|p| p.user.as_ref()).map(|u| u.login.clone()),
+    })
+}
+
+/// Fetches the author of a commit, logging failures since attribution is best-effort.
+async fn try_fetch_commit_author(
+    client: &Octocrab,
+    tracker: Option<&RateLimitTracker>,
+    owner: &str,
+    repo: &str,
+    sha: &str,
+) -> Option<CommitAuthor> {
+    fetch_commit_author(client, tracker, owner, repo, sha)
+        .await
+        .inspect_err(|e| tracing::warn!("Failed to fetch author of commit {}: {:?}", sha, e))
+        .ok()
+}
+
+/// Finds a workflow whose latest run on the default branch contains reports, returning its ID.
+/// Diagnostics describing every workflow, run, artifact and file examined are always returned.
 pub async fn check_for_reports(
     client: &Octocrab,
     project: &Project,
     repo: &Repository,
+) -> (Result<String>, SetupDiagnostics) {
+    let branch = repo.default_branch.as_deref().unwrap_or("main");
+    let mut diagnostics = SetupDiagnostics { branch: branch.to_string(), workflows: Vec::new() };
+    let result = check_for_reports_inner(client, project, branch, &mut diagnostics).await;
+    (result, diagnostics)
+}
+
+async fn check_for_reports_inner(
+    client: &Octocrab,
+    project: &Project,
+    branch: &str,
+    diagnostics: &mut SetupDiagnostics,
 ) -> Result<String> {
     let workflow_ids = if let Some(workflow_id) = &project.workflow_id {
         vec![workflow_id.clone()]
     } else {
-        let workflows = client
-            .workflows(&project.owner, &project.repo)
-            .list()
-            .send()
-            .await
-            .context("Failed to fetch workflows")?;
+        let workflows =
+            counted("workflows", client.workflows(&project.owner, &project.repo).list().send())
+                .await
+                .context("Failed to fetch workflows")?;
         workflows.items.into_iter().map(|w| w.path).collect()
     };
     if workflow_ids.is_empty() {
         bail!("No workflows found in repository.");
     }
-    let branch = repo.default_branch.as_deref().unwrap_or("main");
     for workflow_id in workflow_ids {
         let workflow_id =
             workflow_id.strip_prefix(".github/workflows/").unwrap_or(workflow_id.as_str());
-        let result = client
+        let mut workflow =
+            WorkflowDiagnostics { workflow_id: workflow_id.to_string(), note: None, run: None };
+        let request = client
             .workflows(&project.owner, &project.repo)
             .list_runs(workflow_id)
             .branch(branch)
             .event("push")
             .status("completed")
             .exclude_pull_requests(true)
-            .send()
-            .await;
+            .send();
+        let result = counted("workflow_runs", request).await;
         let items = match result {
-            Ok(result) if result.items.is_empty() => continue,
+            Ok(result) if result.items.is_empty() => {
+                workflow.note = Some(format!("No completed push runs on branch {branch}"));
+                diagnostics.workflows.push(workflow);
+                continue;
+            }
             Ok(result) => result.items,
             Err(octocrab::Error::GitHub { source, .. })
                 if matches!(*source, GitHubError { status_code: StatusCode::NOT_FOUND, .. }) =>
             {
+                workflow.note = Some("Workflow not found".to_string());
+                diagnostics.workflows.push(workflow);
                 continue;
             }
             Err(e) => {
@@ -699,10 +1103,11 @@ pub async fn check_for_reports(
             }
         };
         let run = items.first().unwrap();
-        let result =
-            fetch_workflow_run_artifacts(client, &project.owner, &project.repo, run.id, None)
-                .await?;
-        if !result.artifacts.is_empty() {
+        let run = diagnose_workflow_run(client, project, run).await;
+        let has_reports = run.has_reports();
+        workflow.run = Some(run);
+        diagnostics.workflows.push(workflow);
+        if has_reports {
             return Ok(workflow_id.to_string());
         }
     }
@@ -724,7 +1129,16 @@ pub fn extract_github_url(url: &str) -> Option<(&str, &str)> {
 
 #[cfg(test)]
 mod tests {
-    use super::extract_github_url;
+    use std::{
+        io::{Cursor, Write},
+        path::Path,
+    };
+
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::{
+        ReportFileMatch, artifact_version, extract_github_url, match_report_file, read_artifact,
+    };
 
     #[test]
     fn test_extract_github_url() {
@@ -741,4 +1155,57 @@ mod tests {
             assert_eq!(extract_github_url(url), expected);
         }
     }
+
+    #[test]
+    fn test_artifact_version() {
+        let names = ["GALE01_maps", "progress"];
+        let cases: &[(&str, Option<&str>)] = &[
+            ("GALE01_report", Some("GALE01")),
+            ("GALE01-report", Some("GALE01")),
+            ("GALE01_report_debug", Some("GALE01")),
+            ("us.1.0_report", Some("us.1.0")),
+            ("progress", Some("GALE01")),
+            ("GALE01_maps", None),
+            ("report", None),
+            ("build", None),
+        ];
+        for &(name, expected) in cases {
+            assert_eq!(artifact_version(name, &names).ok().as_deref(), expected, "{name}");
+        }
+        assert!(artifact_version("progress", &["progress"]).is_err());
+    }
+
+    #[test]
+    fn test_match_report_file() {
+        let cases: &[(&str, bool)] = &[
+            ("report.json", true),
+            ("GALE01_report.json", true),
+            ("build/GALE01/report.binpb", true),
+            ("progress.json", true),
+            ("GALE02_report.json", false),
+            ("report_GALE01.json", false),
+            ("main.dol", false),
+        ];
+        for &(path, expected) in cases {
+            let accepted =
+                matches!(match_report_file(Path::new(path), "GALE01"), ReportFileMatch::Accepted);
+            assert_eq!(accepted, expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_read_artifact_parse_error() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("report.binpb", SimpleFileOptions::default()).unwrap();
+        writer.write_all(b"not a report").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        // Diagnostics record the failure for the file, while imports fail
+        let contents = read_artifact(&bytes, "GALE01").unwrap();
+        assert!(contents.reports.is_empty());
+        assert_eq!(contents.files.len(), 1);
+        assert!(!contents.files[0].accepted);
+        assert!(contents.files[0].reason.starts_with("Failed to parse report"));
+        assert!(contents.require_parsed().is_err());
+    }
 }
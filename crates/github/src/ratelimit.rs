@@ -0,0 +1,311 @@
+//! Tracks the GitHub API rate limit budget of each token and app installation.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, bail};
+use http::HeaderMap;
+use octocrab::{Octocrab, models::InstallationId};
+use time::UtcDateTime;
+
+/// Requests kept in reserve for webhook processing and interactive use.
+pub const MIN_REMAINING: u64 = 500;
+/// How long a budget reported by GitHub is trusted before it's fetched again.
+const STALE_AFTER: Duration = Duration::from_secs(60);
+
+/// Identifies which rate limit a client draws from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// The configured personal access token
+    Token,
+    Installation(InstallationId),
+}
+
+impl fmt::Display for RateLimitKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Token => f.write_str("token"),
+            Self::Installation(id) => write!(f, "installation {id}"),
+        }
+    }
+}
+
+/// Rate limit state as last reported by GitHub, minus requests reserved since.
+#[derive(Debug, Clone, Copy)]
+pub struct RateBudget {
+    pub limit: u64,
+    pub remaining: u64,
+    pub used: u64,
+    pub reset: UtcDateTime,
+    updated: Instant,
+}
+
+impl RateBudget {
+    /// Parses the `x-ratelimit-*` headers of a GitHub API response.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.parse().ok() };
+        let reset = UtcDateTime::from_unix_timestamp(header("x-ratelimit-reset")? as i64).ok()?;
+        Some(Self {
+            limit: header("x-ratelimit-limit")?,
+            remaining: header("x-ratelimit-remaining")?,
+            used: header("x-ratelimit-used").unwrap_or_default(),
+            reset,
+            updated: Instant::now(),
+        })
+    }
+
+    /// Remaining requests, assuming a full budget once the reset time has passed.
+    pub fn available(&self) -> u64 {
+        if self.reset <= UtcDateTime::now() { self.limit } else { self.remaining }
+    }
+
+    fn is_stale(&self) -> bool { self.updated.elapsed() > STALE_AFTER }
+}
+
+/// Returned by [`RateLimits::acquire`] when a request would leave less than [`MIN_REMAINING`]
+/// requests in the budget.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimited {
+    pub key: RateLimitKey,
+    pub remaining: u64,
+    pub limit: u64,
+    pub reset: UtcDateTime,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Rate limit for {} is low ({}/{} remaining), resets at {}",
+            self.key, self.remaining, self.limit, self.reset
+        )
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Records the budget reported by responses to requests drawing from a single rate limit.
+#[derive(Clone)]
+pub struct RateLimitTracker {
+    limits: Arc<RateLimits>,
+    key: RateLimitKey,
+}
+
+impl RateLimitTracker {
+    pub fn new(limits: Arc<RateLimits>, key: RateLimitKey) -> Self { Self { limits, key } }
+
+    pub fn record(&self, headers: &HeaderMap) { self.limits.record(self.key, headers) }
+}
+
+#[derive(Default)]
+pub struct RateLimits {
+    budgets: Mutex<HashMap<RateLimitKey, RateBudget>>,
+}
+
+impl RateLimits {
+    /// Records the budget reported in the headers of a GitHub API response.
+    pub fn record(&self, key: RateLimitKey, headers: &HeaderMap) {
+        if let Some(budget) = RateBudget::from_headers(headers) {
+            self.budgets.lock().unwrap().insert(key, budget);
+        }
+    }
+
+    /// Returns the last known budget of every tracked token and installation.
+    pub fn snapshot(&self) -> Vec<(RateLimitKey, RateBudget)> {
+        let mut budgets =
+            self.budgets.lock().unwrap().iter().map(|(k, b)| (*k, *b)).collect::<Vec<_>>();
+        budgets.sort_by_key(|(k, _)| match k {
+            RateLimitKey::Token => 0,
+            RateLimitKey::Installation(id) => id.0 + 1,
+        });
+        budgets
+    }
+
+    /// Fetches the current budget from GitHub. Requests to `/rate_limit` don't count against it.
+    async fn update(&self, key: RateLimitKey, client: &Octocrab) -> Result<RateBudget> {
+        let response = client._get("/rate_limit").await?;
+        crate::count_request("rate_limit", response.status().as_u16().to_string());
+        if !response.status().is_success() {
+            bail!("Failed to fetch rate limit: {}", response.status());
+        }
+        let Some(budget) = RateBudget::from_headers(response.headers()) else {
+            bail!("Rate limit response is missing x-ratelimit headers");
+        };
+        self.budgets.lock().unwrap().insert(key, budget);
+        Ok(budget)
+    }
+
+    /// Returns the current budget, fetching it from GitHub if it's unknown or stale.
+    pub async fn budget(&self, key: RateLimitKey, client: &Octocrab) -> Result<RateBudget> {
+        let cached = self.budgets.lock().unwrap().get(&key).copied();
+        match cached {
+            Some(budget) if !budget.is_stale() => Ok(budget),
+            _ => self.update(key, client).await,
+        }
+    }
+
+    /// Whether at least `cost` requests can be made while keeping [`MIN_REMAINING`] in reserve.
+    /// Returns `false` if the budget can't be determined.
+    pub async fn has_budget(&self, key: RateLimitKey, client: &Octocrab, cost: u64) -> bool {
+        match self.budget(key, client).await {
+            Ok(budget) => budget.available() >= cost + MIN_REMAINING,
+            Err(e) => {
+                tracing::warn!("Failed to check rate limit for {}: {:?}", key, e);
+                false
+            }
+        }
+    }
+
+    /// Reserves `cost` requests from the tracked budget if that keeps [`MIN_REMAINING`] in
+    /// reserve. `fetched` is used if the budget is no longer tracked.
+    fn reserve(&self, key: RateLimitKey, fetched: RateBudget, cost: u64) -> bool {
+        let mut budgets = self.budgets.lock().unwrap();
+        let budget = budgets.entry(key).or_insert(fetched);
+        if budget.reset <= UtcDateTime::now() {
+            budget.remaining = budget.limit;
+        }
+        if budget.remaining >= cost + MIN_REMAINING {
+            budget.remaining -= cost;
+            budget.used += cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Reserves `cost` requests from the budget if available, without waiting for the rate
+    /// limit to reset. For work that can be deferred to a later refresh.
+    pub async fn try_acquire(&self, key: RateLimitKey, client: &Octocrab, cost: u64) -> bool {
+        match self.budget(key, client).await {
+            Ok(budget) => self.reserve(key, budget, cost),
+            Err(e) => {
+                tracing::warn!("Failed to check rate limit for {}: {:?}", key, e);
+                false
+            }
+        }
+    }
+
+    /// Reserves `cost` requests from the budget, failing with [`RateLimited`] if the remaining
+    /// budget would drop below [`MIN_REMAINING`]. Callers are expected to give up and leave the
+    /// work to a later refresh rather than wait for the rate limit to reset.
+    pub async fn acquire(&self, key: RateLimitKey, client: &Octocrab, cost: u64) -> Result<()> {
+        let budget = self.budget(key, client).await?;
+        if self.reserve(key, budget, cost) {
+            return Ok(());
+        }
+        // The reserved budget may be outdated, so check with GitHub before giving up
+        let budget = self.update(key, client).await?;
+        if self.reserve(key, budget, cost) {
+            return Ok(());
+        }
+        Err(RateLimited {
+            key,
+            remaining: budget.remaining,
+            limit: budget.limit,
+            reset: budget.reset,
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    fn headers(values: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for &(name, value) in values {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_budget_from_headers() {
+        let budget = RateBudget::from_headers(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "4990"),
+            ("x-ratelimit-used", "10"),
+            ("x-ratelimit-reset", "1700000000"),
+        ]))
+        .unwrap();
+        assert_eq!(budget.limit, 5000);
+        assert_eq!(budget.remaining, 4990);
+        assert_eq!(budget.used, 10);
+        assert_eq!(budget.reset.unix_timestamp(), 1700000000);
+        // The reset time has passed, so the full budget is available again
+        assert_eq!(budget.available(), 5000);
+
+        // x-ratelimit-used is optional
+        let budget = RateBudget::from_headers(&headers(&[
+            ("x-ratelimit-limit", "60"),
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "4102444800"),
+        ]))
+        .unwrap();
+        assert_eq!(budget.used, 0);
+        assert_eq!(budget.available(), 0);
+
+        assert!(RateBudget::from_headers(&HeaderMap::new()).is_none());
+        assert!(
+            RateBudget::from_headers(&headers(&[
+                ("x-ratelimit-limit", "5000"),
+                ("x-ratelimit-remaining", "many"),
+                ("x-ratelimit-reset", "1700000000"),
+            ]))
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_tracker_records_budget() {
+        let limits = Arc::new(RateLimits::default());
+        let tracker = RateLimitTracker::new(limits.clone(), RateLimitKey::Token);
+        tracker.record(&HeaderMap::new());
+        assert!(limits.snapshot().is_empty());
+        tracker.record(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "1234"),
+            ("x-ratelimit-reset", "4102444800"),
+        ]));
+        let snapshot = limits.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].0, RateLimitKey::Token);
+        assert_eq!(snapshot[0].1.remaining, 1234);
+    }
+
+    #[test]
+    fn test_reserve_keeps_minimum() {
+        let limits = RateLimits::default();
+        let budget = RateBudget::from_headers(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "510"),
+            ("x-ratelimit-reset", "4102444800"),
+        ]))
+        .unwrap();
+        assert!(limits.reserve(RateLimitKey::Token, budget, 6));
+        assert!(limits.reserve(RateLimitKey::Token, budget, 4));
+        // Only MIN_REMAINING is left, so nothing more can be reserved
+        assert!(!limits.reserve(RateLimitKey::Token, budget, 1));
+        let snapshot = limits.snapshot();
+        assert_eq!(snapshot[0].1.remaining, MIN_REMAINING);
+        assert_eq!(snapshot[0].1.used, 10);
+
+        // Once the reset time has passed, the full budget is available again
+        let budget = RateBudget::from_headers(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "1700000000"),
+        ]))
+        .unwrap();
+        let key = RateLimitKey::Installation(InstallationId(1));
+        assert!(limits.reserve(key, budget, 100));
+        assert_eq!(limits.snapshot()[1].1.remaining, 4900);
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::{Result, anyhow, bail};
+use image::{
+    Delay, Frame, ImageFormat, RgbaImage,
+    codecs::gif::{GifEncoder, Repeat},
+};
+
+use crate::svg;
+
+/// Renders SVG frames and encodes them as a looping animated image.
+///
+/// Frames are paired with their display duration in milliseconds and rendered as they are
+/// yielded. GIF frames are encoded immediately, while WebP frames are held until the animation
+/// is assembled. Supported formats are WebP and GIF.
+pub fn render_animation(
+    frames: impl IntoIterator<Item = (String, u32)>,
+    format: ImageFormat,
+) -> Result<Vec<u8>> {
+    let frames = frames.into_iter().map(|(frame, delay)| Ok((svg::render_rgba(&frame)?, delay)));
+    match format {
+        ImageFormat::WebP => encode_webp(frames.collect::<Result<Vec<_>>>()?),
+        ImageFormat::Gif => encode_gif(frames),
+        format => bail!("Unsupported animation format {:?}", format),
+    }
+}
+
+fn encode_webp(frames: Vec<(RgbaImage, u32)>) -> Result<Vec<u8>> {
+    let Some((first, _)) = frames.first() else {
+        bail!("No frames to render");
+    };
+    let (w, h) = first.dimensions();
+    if frames.iter().any(|(image, _)| image.dimensions() != (w, h)) {
+        bail!("Animation frames must have the same dimensions");
+    }
+    let mut config =
+        webp::WebPConfig::new().map_err(|()| anyhow!("Failed to create WebP config"))?;
+    config.quality = 75.0;
+    let mut encoder = webp::AnimEncoder::new(w, h, &config);
+    encoder.set_loop_count(0);
+    let mut timestamp = 0;
+    for (image, delay) in &frames {
+        encoder.add_frame(webp::AnimFrame::from_rgba(image.as_raw(), w, h, timestamp as i32));
+        timestamp += delay;
+    }
+    // The encoder doesn't accept an end timestamp, so repeat the last frame to hold it.
+    // Identical frames are merged into a single frame with a longer duration.
+    let (last, _) = frames.last().unwrap();
+    encoder.add_frame(webp::AnimFrame::from_rgba(last.as_raw(), w, h, timestamp as i32));
+    let data =
+        encoder.try_encode().map_err(|e| anyhow!("Failed to encode WebP animation: {:?}", e))?;
+    Ok(data.to_vec())
+}
+
+fn encode_gif(frames: impl Iterator<Item = Result<(RgbaImage, u32)>>) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut empty = true;
+    {
+        let mut encoder = GifEncoder::new_with_speed(&mut buf, 10);
+        encoder.set_repeat(Repeat::Infinite)?;
+        for frame in frames {
+            let (image, delay) = frame?;
+            encoder.encode_frame(Frame::from_parts(
+                image,
+                0,
+                0,
+                Delay::from_numer_denom_ms(delay, 1),
+            ))?;
+            empty = false;
+        }
+    }
+    if empty {
+        bail!("No frames to render");
+    }
+    Ok(buf)
+}
@@ -105,12 +105,33 @@ pub fn render(
     })
 }
 
+/// Renders a badge with a fixed message, e.g. for milestones. The default color is used
+/// unless overridden by the parameters.
+pub fn render_message(
+    default_label: &str,
+    message: &str,
+    default_color: &str,
+    params: &ShieldParams,
+) -> ShieldResponse {
+    ShieldResponse {
+        schema_version: 1,
+        label: params.label.clone().unwrap_or_else(|| default_label.to_string()),
+        message: message.to_string(),
+        color: Some(params.color.clone().unwrap_or_else(|| default_color.to_string())),
+        style: params.style.clone(),
+        label_color: params.label_color.clone(),
+    }
+}
+
 pub fn render_svg(
     measures: &Measures,
     default_label: &str,
     params: &ShieldParams,
 ) -> Result<String> {
-    let response = render(measures, default_label, params)?;
+    response_svg(&render(measures, default_label, params)?)
+}
+
+pub fn response_svg(response: &ShieldResponse) -> Result<String> {
     let mut builder = badge_maker::BadgeBuilder::new();
     builder.label(&response.label).message(&response.message);
     if let Some(color) = &response.color {
@@ -135,3 +156,8 @@ pub fn render_image(
     let svg = render_svg(measures, default_label, params)?;
     svg::render_image(&svg, format)
 }
+
+pub fn response_image(response: &ShieldResponse, format: ImageFormat) -> Result<Vec<u8>> {
+    let svg = response_svg(response)?;
+    svg::render_image(&svg, format)
+}
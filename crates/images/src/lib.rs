@@ -1,6 +1,8 @@
+pub mod animation;
 pub mod badge;
 pub mod svg;
 pub mod treemap;
+pub mod upload;
 
 use std::{io::Cursor, str::FromStr};
 
@@ -17,6 +17,24 @@ use tracing::info;
 static FONT_DB: OnceLock<Arc<fontdb::Database>> = OnceLock::new();
 
 pub fn render_image(svg: &str, format: ImageFormat) -> Result<Vec<u8>> {
+    let image = render_rgba(svg)?;
+    let mut bytes = Vec::new();
+    match image.write_to(&mut Cursor::new(&mut bytes), format) {
+        Ok(()) => {}
+        Err(ImageError::Unsupported(e))
+            if matches!(e.kind(), UnsupportedErrorKind::Color(ExtendedColorType::Rgba8)) =>
+        {
+            // Convert to RGB and try again
+            let image: RgbImage = image.convert();
+            image.write_to(&mut Cursor::new(&mut bytes), format)?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+    Ok(bytes)
+}
+
+/// Renders an SVG to an RGBA buffer.
+pub fn render_rgba(svg: &str) -> Result<RgbaImage> {
     let fontdb = FONT_DB
         .get_or_init(|| {
             let mut db = fontdb::Database::new();
@@ -34,17 +52,5 @@ pub fn render_image(svg: &str, format: ImageFormat) -> Result<Vec<u8>> {
     let mut pixmap = PixmapMut::from_bytes(image.as_mut(), w, h)
         .ok_or_else(|| anyhow!("Failed to create pixmap"))?;
     resvg::render(&tree, Transform::identity(), &mut pixmap);
-    let mut bytes = Vec::new();
-    match image.write_to(&mut Cursor::new(&mut bytes), format) {
-        Ok(()) => {}
-        Err(ImageError::Unsupported(e))
-            if matches!(e.kind(), UnsupportedErrorKind::Color(ExtendedColorType::Rgba8)) =>
-        {
-            // Convert to RGB and try again
-            let image: RgbImage = image.convert();
-            image.write_to(&mut Cursor::new(&mut bytes), format)?;
-        }
-        Err(e) => return Err(e.into()),
-    }
-    Ok(bytes)
+    Ok(image)
 }
@@ -30,16 +30,22 @@ pub fn hsl(h: u16, s: u8, l: u8) -> Srgb {
 
 pub fn color_mix(c1: Srgb, c2: Srgb, percent: f32) -> Srgb { c1.mix(c2, percent) }
 
-pub fn unit_color(fuzzy_match_percent: f32) -> String {
-    html_color(if fuzzy_match_percent == 100.0 {
-        hsl(120, 100, 39)
+/// Returns the inner and outer gradient colors for a match percentage.
+pub fn progress_colors(percent: f32) -> (Srgb, Srgb) {
+    if percent == 100.0 {
+        (hsl(120, 100, 39), hsl(120, 100, 17))
     } else {
-        let nonmatch = hsl(221, 0, 21);
-        let nearmatch = hsl(221, 100, 35);
-        nonmatch.mix(nearmatch, fuzzy_match_percent / 100.0)
-    })
+        let t = percent / 100.0;
+        (
+            color_mix(hsl(221, 0, 21), hsl(221, 100, 35), t),
+            color_mix(hsl(200, 0, 15), hsl(200, 100, 15), t),
+        )
+    }
 }
 
+/// Returns the inner and outer gradient colors for an arbitrary hue.
+pub fn hue_colors(hue: u16) -> (Srgb, Srgb) { (hsl(hue, 70, 40), hsl(hue, 70, 17)) }
+
 pub fn html_color(c: Srgb) -> String {
     let (r, g, b) = c.into_components();
     format!("#{:02x}{:02x}{:02x}", (r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8)
@@ -0,0 +1,210 @@
+use std::io::Cursor;
+
+use anyhow::{Context, Result, bail};
+use decomp_dev_core::{models::ImageVariant, util::size};
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, imageops::FilterType};
+
+use crate::encode_image;
+
+/// Maximum size of an uploaded image in bytes.
+pub const MAX_UPLOAD_BYTES: usize = 20 * 1000 * 1000;
+/// Maximum width or height of an uploaded image in pixels.
+pub const MAX_UPLOAD_DIMENSION: u32 = 8192;
+/// Formats accepted for uploads.
+pub const ALLOWED_FORMATS: &[ImageFormat] =
+    &[ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::WebP, ImageFormat::Gif];
+
+/// A variant that is pre-generated for uploaded images.
+pub struct VariantSpec {
+    pub name: &'static str,
+    pub max_width: u32,
+    pub max_height: u32,
+    pub blur: Option<f32>,
+    pub formats: &'static [ImageFormat],
+}
+
+/// Variants generated for project header images.
+pub const HEADER_VARIANTS: &[VariantSpec] = &[
+    // Project list header
+    VariantSpec {
+        name: "header",
+        max_width: 1024,
+        max_height: 256,
+        blur: None,
+        formats: &[ImageFormat::Avif, ImageFormat::WebP, ImageFormat::Jpeg],
+    },
+    // Hero image preview on the manage page
+    VariantSpec {
+        name: "thumbnail",
+        max_width: 256,
+        max_height: 256,
+        blur: None,
+        formats: &[ImageFormat::WebP, ImageFormat::Jpeg],
+    },
+    // Project list backdrop, shown until the card is hovered
+    VariantSpec {
+        name: "backdrop",
+        max_width: 256,
+        max_height: 64,
+        blur: Some(4.0),
+        formats: &[ImageFormat::WebP, ImageFormat::Jpeg],
+    },
+];
+
+/// Variants generated for platform icons.
+pub const ICON_VARIANTS: &[VariantSpec] = &[
+    // Platform icon, see `platform_icon`
+    VariantSpec {
+        name: "icon",
+        max_width: 64,
+        max_height: 64,
+        blur: None,
+        formats: &[ImageFormat::Png],
+    },
+];
+
+pub struct EncodedImage {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+pub struct ProcessedImage {
+    pub original: EncodedImage,
+    pub variants: Vec<ImageVariant>,
+}
+
+/// Validates an uploaded image, strips its metadata and generates the given variants.
+///
+/// Errors are suitable for displaying to the user.
+pub fn process_upload(data: &[u8], specs: &[VariantSpec]) -> Result<ProcessedImage> {
+    if data.len() > MAX_UPLOAD_BYTES {
+        bail!(
+            "Image is too large ({}, maximum {})",
+            size(data.len() as u64),
+            size(MAX_UPLOAD_BYTES as u64)
+        );
+    }
+    let format = image::guess_format(data).context("Unrecognized image format")?;
+    if !ALLOWED_FORMATS.contains(&format) {
+        bail!("Unsupported image format {}", format.to_mime_type());
+    }
+    let mut decoder = ImageReader::with_format(Cursor::new(data), format)
+        .into_decoder()
+        .context("Failed to read image")?;
+    let (width, height) = decoder.dimensions();
+    if width > MAX_UPLOAD_DIMENSION || height > MAX_UPLOAD_DIMENSION {
+        bail!(
+            "Image is too large ({width}x{height}, maximum {MAX_UPLOAD_DIMENSION}x{MAX_UPLOAD_DIMENSION})"
+        );
+    }
+    let orientation = decoder.orientation().context("Failed to read image orientation")?;
+    let mut image = DynamicImage::from_decoder(decoder).context("Failed to decode image")?;
+    // Apply the EXIF orientation, since re-encoding discards all metadata
+    image.apply_orientation(orientation);
+
+    // Keep JPEG photos lossy, and store everything else losslessly
+    let original_format =
+        if format == ImageFormat::Jpeg { ImageFormat::Jpeg } else { ImageFormat::Png };
+    let original = encode(&image, original_format)?;
+    let variants = encode_variants(&image, specs)?;
+    Ok(ProcessedImage { original, variants })
+}
+
+/// Generates the given variants of a previously stored image.
+pub fn generate_variants(data: &[u8], specs: &[VariantSpec]) -> Result<Vec<ImageVariant>> {
+    let image = image::load_from_memory(data).context("Failed to decode image")?;
+    encode_variants(&image, specs)
+}
+
+fn encode_variants(image: &DynamicImage, specs: &[VariantSpec]) -> Result<Vec<ImageVariant>> {
+    let mut variants = Vec::new();
+    for spec in specs {
+        let (width, height) = fit_dimensions(
+            image.width(),
+            image.height(),
+            Some(spec.max_width),
+            Some(spec.max_height),
+        );
+        let mut resized = if (width, height) != (image.width(), image.height()) {
+            image.resize_exact(width, height, FilterType::Lanczos3)
+        } else {
+            image.clone()
+        };
+        if let Some(blur) = spec.blur {
+            resized = resized.blur(blur);
+        }
+        for &format in spec.formats {
+            variants.push(ImageVariant {
+                name: spec.name.to_string(),
+                mime_type: format.to_mime_type().to_string(),
+                width: resized.width(),
+                height: resized.height(),
+                blur: spec.blur,
+                data: encode_image(&resized, format)?,
+            });
+        }
+    }
+    Ok(variants)
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat) -> Result<EncodedImage> {
+    let data = encode_image(image, format)?;
+    Ok(EncodedImage { format, width: image.width(), height: image.height(), data })
+}
+
+/// Scales dimensions down to fit within the given bounds, maintaining aspect ratio.
+/// Images are never scaled up.
+pub fn fit_dimensions(
+    width: u32,
+    height: u32,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> (u32, u32) {
+    let mut current_width = width;
+    let mut current_height = height;
+    if let Some(max_width) = max_width
+        && current_width > max_width
+    {
+        current_height = (current_height as f32 * (max_width as f32 / current_width as f32)) as u32;
+        current_width = max_width;
+    }
+    if let Some(max_height) = max_height
+        && current_height > max_height
+    {
+        current_width = (current_width as f32 * (max_height as f32 / current_height as f32)) as u32;
+        current_height = max_height;
+    }
+    (current_width, current_height)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fit_dimensions() {
+        assert_eq!(fit_dimensions(2048, 1024, None, None), (2048, 1024));
+        assert_eq!(fit_dimensions(2048, 1024, Some(1024), None), (1024, 512));
+        assert_eq!(fit_dimensions(2048, 1024, None, Some(256)), (512, 256));
+        assert_eq!(fit_dimensions(2048, 1024, Some(1024), Some(256)), (512, 256));
+        assert_eq!(fit_dimensions(1000, 4000, Some(1024), Some(256)), (64, 256));
+        // Never scaled up
+        assert_eq!(fit_dimensions(100, 50, Some(1024), Some(256)), (100, 50));
+    }
+
+    #[test]
+    fn test_process_upload_icon() {
+        let image = DynamicImage::new_rgba8(128, 128);
+        let data = encode_image(&image, ImageFormat::Png).unwrap();
+        let processed = process_upload(&data, ICON_VARIANTS).unwrap();
+        assert_eq!((processed.original.width, processed.original.height), (128, 128));
+        // Matches the size and format requested by platform_icon
+        assert_eq!(processed.variants.len(), 1);
+        let variant = &processed.variants[0];
+        assert_eq!(variant.name, "icon");
+        assert_eq!(variant.mime_type, "image/png");
+        assert_eq!((variant.width, variant.height), (64, 64));
+    }
+}
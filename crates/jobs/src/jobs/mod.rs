@@ -1,5 +1,21 @@
 mod refresh_project;
 mod workflow_run;
 
+use decomp_dev_core::models::JobRun;
 pub use refresh_project::*;
 pub use workflow_run::*;
+
+use crate::JobContext;
+
+/// Records a finished job run in the job history and metrics.
+async fn record_job_run(ctx: &JobContext, run: &JobRun) {
+    let kind = run.kind.as_str();
+    let duration = (run.finished_at - run.started_at).as_seconds_f64();
+    metrics::histogram!("job_duration_seconds", "job" => kind).record(duration);
+    if run.error.is_some() {
+        metrics::counter!("job_failures_total", "job" => kind).increment(1);
+    }
+    if let Err(e) = ctx.db.insert_job_run(run).await {
+        tracing::error!("Failed to record job run: {:?}", e);
+    }
+}
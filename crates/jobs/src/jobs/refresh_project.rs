@@ -1,9 +1,12 @@
 use anyhow::Result;
 use apalis::prelude::*;
-use decomp_dev_github::refresh_project;
+use decomp_dev_core::models::{JobKind, JobRun};
+use decomp_dev_github::{ratelimit::RateLimited, refresh_project};
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 
-use crate::JobContext;
+use super::record_job_run;
+use crate::{JobContext, TraceContext};
 
 /// Job to refresh a project's reports from GitHub Actions.
 ///
@@ -11,10 +14,21 @@ use crate::JobContext;
 /// parses reports, and inserts them into the database.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RefreshProjectJob {
-    /// The repository ID to refresh.
-    pub repository_id: u64,
+    /// The project ID to refresh. Jobs queued before projects were decoupled from
+    /// repositories used the repository ID, which is also the ID of its primary project.
+    #[serde(alias = "repository_id")]
+    pub project_id: u64,
     /// Whether to do a full refresh (fetch all runs) or partial (stop at known commit).
     pub full_refresh: bool,
+    /// Trace context of the request that queued the job.
+    #[serde(default)]
+    pub trace_context: TraceContext,
+}
+
+impl RefreshProjectJob {
+    pub fn new(project_id: u64, full_refresh: bool) -> Self {
+        Self { project_id, full_refresh, trace_context: TraceContext::current() }
+    }
 }
 
 /// Process a project refresh job.
@@ -24,21 +38,48 @@ pub struct RefreshProjectJob {
 pub async fn process_refresh_project_job(
     job: RefreshProjectJob,
     ctx: Data<JobContext>,
+) -> Result<()> {
+    let span = tracing::info_span!(
+        "process_refresh_project_job",
+        project_id = job.project_id,
+        full_refresh = job.full_refresh,
+    );
+    job.trace_context.attach(&span);
+    process_refresh_project_job_inner(job, ctx).instrument(span).await
+}
+
+async fn process_refresh_project_job_inner(
+    job: RefreshProjectJob,
+    ctx: Data<JobContext>,
 ) -> Result<()> {
     tracing::info!(
-        "Processing refresh project job: repo={} full_refresh={}",
-        job.repository_id,
+        "Processing refresh project job: project={} full_refresh={}",
+        job.project_id,
         job.full_refresh
     );
 
-    match refresh_project(&ctx.github, &ctx.db, job.repository_id, None, job.full_refresh).await {
-        Ok(count) => {
-            tracing::info!("Refreshed project {} with {} new reports", job.repository_id, count);
-            Ok(())
-        }
-        Err(e) => {
-            tracing::error!("Failed to refresh project {}: {:?}", job.repository_id, e);
-            Err(e)
-        }
+    let mut run = JobRun::new(job.project_id, JobKind::RefreshProject);
+    let result = refresh_project(&ctx.github, &ctx.db, job.project_id, None, job.full_refresh)
+        .await
+        .map(|result| {
+            tracing::info!(
+                "Refreshed project {} with {} new reports",
+                job.project_id,
+                result.reports_inserted
+            );
+            run.artifacts = Some(result.artifacts as u32);
+            run.reports_inserted = result.reports_inserted as u32;
+            run.versions = result.versions;
+        });
+    if let Err(e) = &result {
+        tracing::error!("Failed to refresh project {}: {:?}", job.project_id, e);
+    }
+    run.finish(&result);
+    record_job_run(&ctx, &run).await;
+    match result {
+        // Retrying won't help until the rate limit resets, so leave it to the next scheduled
+        // refresh instead
+        Err(e) if e.downcast_ref::<RateLimited>().is_some() => Ok(()),
+        result => result,
     }
 }
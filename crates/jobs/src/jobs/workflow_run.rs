@@ -1,22 +1,25 @@
 use std::time::Instant;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use apalis::prelude::*;
-use decomp_dev_core::models::Commit;
+use decomp_dev_core::models::{Commit, JobKind, JobRun, Project, ProjectInfo};
 use decomp_dev_github::{
+    WorkflowRunArtifact,
     changes::{
         generate_changes, generate_combined_comment, generate_comment,
         generate_missing_report_comment, post_pr_comment,
     },
-    commit_from_head_commit, fetch_workflow_run_artifacts,
+    commit_from_head_commit, counted, fetch_commit_author, fetch_workflow_run_artifacts,
 };
 use octocrab::{
     Octocrab,
     models::{RepositoryId, RunId, pulls::PullRequest, workflows::Run},
 };
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 
-use crate::JobContext;
+use super::record_job_run;
+use crate::{JobContext, TraceContext};
 
 /// Job to process a completed GitHub Actions workflow run.
 ///
@@ -25,7 +28,7 @@ use crate::JobContext;
 /// PR comments (for pull request events).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessWorkflowRunJob {
-    /// The repository ID (used to look up the project).
+    /// The repository ID (used to look up the projects built from it).
     pub repository_id: RepositoryId,
     /// The workflow run ID to process.
     pub run_id: RunId,
@@ -37,6 +40,9 @@ pub struct ProcessWorkflowRunJob {
     pub head_branch: String,
     /// The repository that triggered the workflow (may be a fork).
     pub head_repository_owner: Option<String>,
+    /// Trace context of the request that queued the job.
+    #[serde(default)]
+    pub trace_context: TraceContext,
 }
 
 impl From<&Run> for ProcessWorkflowRunJob {
@@ -51,6 +57,7 @@ impl From<&Run> for ProcessWorkflowRunJob {
                 .head_repository
                 .as_ref()
                 .and_then(|repo| repo.owner.as_ref().map(|o| o.login.clone())),
+            trace_context: TraceContext::current(),
         }
     }
 }
@@ -58,7 +65,7 @@ impl From<&Run> for ProcessWorkflowRunJob {
 /// Process a completed workflow run job.
 ///
 /// This handles:
-/// - Fetching the project info from the database
+/// - Fetching the info of every project built from the repository
 /// - Getting the appropriate GitHub client (installation or personal token)
 /// - Processing workflow run artifacts
 /// - Inserting reports for push events
@@ -66,6 +73,20 @@ impl From<&Run> for ProcessWorkflowRunJob {
 pub async fn process_workflow_run_job(
     job: ProcessWorkflowRunJob,
     ctx: Data<JobContext>,
+) -> Result<()> {
+    let span = tracing::info_span!(
+        "process_workflow_run_job",
+        repository_id = %job.repository_id,
+        run_id = %job.run_id,
+        event = %job.event,
+    );
+    job.trace_context.attach(&span);
+    process_workflow_run_job_inner(job, ctx).instrument(span).await
+}
+
+async fn process_workflow_run_job_inner(
+    job: ProcessWorkflowRunJob,
+    ctx: Data<JobContext>,
 ) -> Result<()> {
     tracing::info!(
         "Processing workflow run job: repo={} run={} event={}",
@@ -74,35 +95,87 @@ pub async fn process_workflow_run_job(
         job.event,
     );
 
-    match job.event.as_str() {
-        "push" => {
-            process_workflow_run_push(&ctx, &job).await?;
-        }
-        "pull_request" | "pull_request_target" => {
-            process_workflow_run_pull_request(&ctx, &job).await?;
-        }
-        _ => {}
+    if !matches!(job.event.as_str(), "push" | "pull_request" | "pull_request_target") {
+        return Ok(());
     }
-
-    Ok(())
-}
-
-async fn process_workflow_run_push(ctx: &JobContext, job: &ProcessWorkflowRunJob) -> Result<()> {
-    let project_id = job.repository_id.0;
-    let Some(project_info) = ctx
+    // A repository can build multiple projects, each selecting its own artifacts
+    let projects = ctx
         .db
-        .get_project_info_by_id(project_id, None)
+        .get_repository_projects(job.repository_id.0)
         .await
-        .context("Failed to fetch project info")?
-    else {
+        .context("Failed to fetch projects")?;
+    if projects.is_empty() {
         tracing::warn!("No project found for repository ID {}", job.repository_id);
         return Ok(());
+    }
+
+    let mut runs = projects
+        .iter()
+        .map(|info| {
+            let mut run = JobRun::new(info.project.id, JobKind::WorkflowRun);
+            run.run_id = Some(job.run_id.0);
+            run.event = Some(job.event.clone());
+            run.commit_sha = Some(job.head_commit.sha.clone());
+            run
+        })
+        .collect::<Vec<_>>();
+    // Failures shared by every project are returned, while failures of a single project are
+    // recorded on its run so that the remaining projects are still processed
+    let result = if job.event == "push" {
+        process_workflow_run_push(&ctx, &job, &projects, &mut runs).await
+    } else {
+        process_workflow_run_pull_request(&ctx, &job, &projects, &mut runs).await
     };
+    for run in &mut runs {
+        run.finish(&result);
+        record_job_run(&ctx, run).await;
+    }
+    result?;
+    let failed = runs.iter().filter(|run| run.error.is_some()).count();
+    if failed > 0 {
+        bail!("Failed to process workflow run for {} of {} projects", failed, runs.len());
+    }
+    Ok(())
+}
 
+/// Maps the artifacts of a workflow run to the versions stored for a project, given every
+/// project in the repository.
+fn select_artifacts<'a>(
+    project: &Project,
+    projects: &[ProjectInfo],
+    artifacts: &'a [WorkflowRunArtifact],
+) -> Vec<(String, &'a WorkflowRunArtifact)> {
+    artifacts
+        .iter()
+        .filter_map(|a| {
+            project
+                .claim_artifact_version(&a.version, projects.iter().map(|info| &info.project))
+                .map(|v| (v, a))
+        })
+        .collect()
+}
+
+/// Explains why a project imported nothing from a workflow run.
+fn no_artifacts_reason(artifacts: &[WorkflowRunArtifact]) -> &'static str {
+    if artifacts.is_empty() {
+        "No report artifacts found"
+    } else {
+        "No report artifacts selected for this project"
+    }
+}
+
+async fn process_workflow_run_push(
+    ctx: &JobContext,
+    job: &ProcessWorkflowRunJob,
+    projects: &[ProjectInfo],
+    runs: &mut [JobRun],
+) -> Result<()> {
     // Fetch repository info
-    let client = ctx.github.client_for(job.repository_id.0).await?;
-    let repository =
-        client.repos_by_id(job.repository_id).get().await.context("Failed to fetch repository")?;
+    let (client, key) = ctx.github.client_and_key_for(job.repository_id.0).await?;
+    let tracker = ctx.github.tracker(key);
+    let repository = counted("repo", client.repos_by_id(job.repository_id).get())
+        .await
+        .context("Failed to fetch repository")?;
 
     let Some(owner) = repository.owner.as_ref().map(|o| o.login.as_str()) else {
         tracing::warn!("No owner found for repository ID {}", job.repository_id);
@@ -122,24 +195,36 @@ async fn process_workflow_run_push(ctx: &JobContext, job: &ProcessWorkflowRunJob
             job.run_id,
             job.head_branch,
         );
+        for run in runs.iter_mut() {
+            run.skip_reason = Some(format!("Branch {} is not the default branch", job.head_branch));
+        }
         return Ok(());
     }
 
     // Get all versions that exist on the base branch
-    let base_versions = if let Some(base_commit) = &project_info.commit {
-        ctx.db
-            .get_versions_for_commit(project_id, &base_commit.sha)
-            .await
-            .context("Failed to get base versions")?
-    } else {
-        Vec::new()
-    };
+    let mut base_versions = Vec::new();
+    for info in projects {
+        if let Some(base_commit) = &info.commit {
+            let versions = ctx
+                .db
+                .get_versions_for_commit(info.project.id, &base_commit.sha)
+                .await
+                .context("Failed to get base versions")?;
+            base_versions.extend(versions.iter().map(|v| info.project.artifact_version(v)));
+        }
+    }
 
     // Process the workflow run to get artifacts
-    let result =
-        fetch_workflow_run_artifacts(&client, owner, repo, job.run_id, Some(&base_versions))
-            .await
-            .context("Failed to process workflow run")?;
+    let result = fetch_workflow_run_artifacts(
+        &client,
+        Some(&tracker),
+        owner,
+        repo,
+        job.run_id,
+        Some(&base_versions),
+    )
+    .await
+    .context("Failed to process workflow run")?;
 
     tracing::debug!(
         "Processed workflow run {} ({}) (artifacts {})",
@@ -147,72 +232,167 @@ async fn process_workflow_run_push(ctx: &JobContext, job: &ProcessWorkflowRunJob
         job.head_commit.sha,
         result.artifacts.len()
     );
-    if result.artifacts.is_empty() {
+
+    // Insert reports into the database
+    let mut updated_projects = Vec::new();
+    for (info, run) in projects.iter().zip(runs.iter_mut()) {
+        let project = &info.project;
+        run.artifacts = Some(result.artifacts.len() as u32);
+        let artifacts = select_artifacts(project, projects, &result.artifacts);
+        if artifacts.is_empty() {
+            run.skip_reason = Some(no_artifacts_reason(&result.artifacts).to_string());
+            continue;
+        }
+        if let Err(e) = insert_project_reports(ctx, job, owner, project, run, artifacts).await {
+            tracing::error!("Failed to insert reports for project {}: {:?}", project.id, e);
+            run.fail(&e);
+        }
+        if run.reports_inserted > 0 {
+            updated_projects.push(project.id);
+        }
+    }
+    if updated_projects.is_empty() {
         return Ok(());
     }
 
-    // Insert reports into the database
-    for artifact in result.artifacts {
+    // Attribution is best-effort, the reports are already stored
+    match fetch_commit_author(&client, Some(&tracker), owner, repo, &job.head_commit.sha).await {
+        Ok(author) => {
+            for project_id in updated_projects {
+                if let Err(e) =
+                    ctx.db.update_commit_author(project_id, &job.head_commit.sha, &author).await
+                {
+                    tracing::warn!(
+                        "Failed to update author of commit {} for project {}: {:?}",
+                        job.head_commit.sha,
+                        project_id,
+                        e
+                    );
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Failed to fetch author of commit {}: {:?}", job.head_commit.sha, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Inserts the reports selected for a project, recording them on its run.
+async fn insert_project_reports(
+    ctx: &JobContext,
+    job: &ProcessWorkflowRunJob,
+    owner: &str,
+    project: &Project,
+    run: &mut JobRun,
+    artifacts: Vec<(String, &WorkflowRunArtifact)>,
+) -> Result<()> {
+    for (version, artifact) in artifacts {
         let start = Instant::now();
         ctx.db
-            .insert_report(
-                &project_info.project,
-                &job.head_commit,
-                &artifact.version,
-                artifact.report,
-            )
+            .insert_report(project, &job.head_commit, &version, artifact.report.clone())
             .await
             .context("Failed to insert report")?;
+        run.reports_inserted += 1;
+        run.versions.push(version.clone());
         let duration = start.elapsed();
+        metrics::histogram!("report_insert_duration_seconds").record(duration.as_secs_f64());
         tracing::info!(
-            "Inserted report {} ({}) in {}ms",
-            artifact.version,
+            "Inserted report {} ({}) for project {} in {}ms",
+            version,
             job.head_commit.sha,
+            project.id,
             duration.as_millis()
         );
+        // Milestones are best-effort, the report is already stored
+        let achieved = match ctx.db.check_milestones(project.id, &job.head_commit, &version).await {
+            Ok(achieved) => achieved,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to check milestones for project {} ({}): {:?}",
+                    project.id,
+                    version,
+                    e
+                );
+                Vec::new()
+            }
+        };
+        for milestone in achieved {
+            tracing::info!(
+                "Milestone \"{}\" achieved for {}/{} at {}",
+                milestone.name,
+                owner,
+                project.repo_path(),
+                job.head_commit.sha
+            );
+        }
     }
-
     Ok(())
 }
 
+/// A project that receives a section of the PR comment.
+struct Target<'a> {
+    project: &'a Project,
+    run: &'a mut JobRun,
+    base_commit: &'a Commit,
+    base_versions: Vec<String>,
+}
+
 async fn process_workflow_run_pull_request(
     ctx: &JobContext,
     job: &ProcessWorkflowRunJob,
+    projects: &[ProjectInfo],
+    runs: &mut [JobRun],
 ) -> Result<()> {
-    let project_id = job.repository_id.0;
-    let Some(project_info) = ctx
-        .db
-        .get_project_info_by_id(project_id, None)
-        .await
-        .context("Failed to fetch project info")?
-    else {
-        tracing::warn!("No project found for repository ID {}", job.repository_id);
-        return Ok(());
-    };
-
-    if !project_info.project.enable_pr_comments {
-        return Ok(());
-    }
-
     // Actions pull_request builds always merge with the latest commit on the base branch.
     // We can't use the base commit from the workflow run or pull request APIs, those are
     // both lies. For simplicity, we'll just always compare against the latest commit that
     // we have stored.
-    let Some(base_commit) = project_info.commit else {
-        tracing::warn!("No base commit found for project ID {}", project_id);
+    let mut targets = Vec::new();
+    for (info, run) in projects.iter().zip(runs.iter_mut()) {
+        if !info.project.enable_pr_comments {
+            run.skip_reason = Some("PR comments are disabled".to_string());
+            continue;
+        }
+        let Some(base_commit) = &info.commit else {
+            tracing::warn!("No base commit found for project ID {}", info.project.id);
+            run.skip_reason = Some("No base commit found".to_string());
+            continue;
+        };
+        // Get all versions that exist on the base branch
+        let base_versions = match ctx
+            .db
+            .get_versions_for_commit(info.project.id, &base_commit.sha)
+            .await
+            .context("Failed to get base versions")
+        {
+            Ok(versions) => versions,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to get base versions for project {}: {:?}",
+                    info.project.id,
+                    e
+                );
+                run.fail(&e);
+                continue;
+            }
+        };
+        targets.push(Target { project: &info.project, run, base_commit, base_versions });
+    }
+    if targets.is_empty() {
         return Ok(());
-    };
-
-    // Get all versions that exist on the base branch
-    let base_versions = ctx
-        .db
-        .get_versions_for_commit(project_id, &base_commit.sha)
+    }
+    let expected_versions = targets
+        .iter()
+        .flat_map(|t| t.base_versions.iter().map(|v| t.project.artifact_version(v)))
+        .collect::<Vec<_>>();
+
+    let (client, key) = ctx.github.client_and_key_for(job.repository_id.0).await?;
+    let tracker = ctx.github.tracker(key);
+    let repository = counted("repo", client.repos_by_id(job.repository_id).get())
         .await
-        .context("Failed to get base versions")?;
-
-    let client = ctx.github.client_for(job.repository_id.0).await?;
-    let repository =
-        client.repos_by_id(job.repository_id).get().await.context("Failed to fetch repository")?;
+        .context("Failed to fetch repository")?;
 
     let Some(owner) = repository.owner.as_ref().map(|o| o.login.as_str()) else {
         tracing::warn!("No owner found for repository ID {}", job.repository_id);
@@ -220,10 +400,16 @@ async fn process_workflow_run_pull_request(
     };
     let repo = repository.name.as_str();
 
-    let result =
-        fetch_workflow_run_artifacts(&client, owner, repo, job.run_id, Some(&base_versions))
-            .await
-            .context("Failed to process workflow run")?;
+    let result = fetch_workflow_run_artifacts(
+        &client,
+        Some(&tracker),
+        owner,
+        repo,
+        job.run_id,
+        Some(&expected_versions),
+    )
+    .await
+    .context("Failed to process workflow run")?;
 
     tracing::debug!(
         "Processed workflow run {} ({}) (artifacts {})",
@@ -231,17 +417,88 @@ async fn process_workflow_run_pull_request(
         job.head_commit.sha,
         result.artifacts.len()
     );
-    if result.artifacts.is_empty() {
-        return Ok(());
+
+    // Versions are labeled with the project when several projects share the comment
+    let labeled = targets.len() > 1;
+    let mut version_comments = Vec::new();
+    for target in &mut targets {
+        let project = target.project;
+        target.run.artifacts = Some(result.artifacts.len() as u32);
+        let artifacts = select_artifacts(project, projects, &result.artifacts);
+        if artifacts.is_empty() {
+            target.run.skip_reason = Some(no_artifacts_reason(&result.artifacts).to_string());
+            continue;
+        }
+        // A failed project is left out of the comment, rather than holding back the others
+        match project_comments(ctx, job, target, &artifacts, labeled).await {
+            Ok(comments) => version_comments.extend(comments),
+            Err(e) => {
+                tracing::error!("Failed to generate changes for project {}: {:?}", project.id, e);
+                target.run.fail(&e);
+            }
+        }
     }
 
+    if !version_comments.is_empty() {
+        let combined_comment = generate_combined_comment(version_comments);
+
+        let pull_requests = fetch_workflow_run_pull_requests(
+            &client,
+            job,
+            owner,
+            repo,
+            repository.default_branch.as_deref(),
+        )
+        .await
+        .context("Failed to fetch pull requests for workflow run")?;
+
+        if pull_requests.is_empty() {
+            tracing::info!(
+                "No associated pull requests found for workflow run {} in {}/{}",
+                job.run_id,
+                owner,
+                repo
+            );
+            return Ok(());
+        }
+
+        // Post/update comments for each associated PR, using the settings of the primary
+        // project (the one without a slug), even if it has no section of its own
+        let primary = projects
+            .iter()
+            .map(|info| &info.project)
+            .find(|project| project.slug.is_none())
+            .unwrap_or(targets[0].project);
+        for pull_request in &pull_requests {
+            post_pr_comment(&client, primary, job.repository_id, pull_request, &combined_comment)
+                .await
+                .context("Failed to post PR comment")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the PR comment sections for a project's artifacts.
+async fn project_comments(
+    ctx: &JobContext,
+    job: &ProcessWorkflowRunJob,
+    target: &Target<'_>,
+    artifacts: &[(String, &WorkflowRunArtifact)],
+    labeled: bool,
+) -> Result<Vec<String>> {
+    let project = target.project;
+    let base_commit = target.base_commit;
+    let label = |version: &str| {
+        if labeled { format!("{} {}", project.short_name(), version) } else { version.to_string() }
+    };
     let mut version_comments = Vec::new();
 
     // Process existing artifacts from PR
-    for artifact in &result.artifacts {
+    for (version, artifact) in artifacts {
         let cached_report = ctx
             .db
-            .get_report(project_id, &base_commit.sha, &artifact.version)
+            .get_report(project.id, &base_commit.sha, version)
             .await
             .context("Failed to get cached report")?;
 
@@ -254,7 +511,7 @@ async fn process_workflow_run_pull_request(
             version_comments.push(generate_comment(
                 &report,
                 &artifact.report,
-                Some(&report_file.version),
+                Some(&label(&report_file.version)),
                 Some(&report_file.commit),
                 Some(&job.head_commit),
                 changes,
@@ -262,66 +519,28 @@ async fn process_workflow_run_pull_request(
         } else {
             tracing::warn!(
                 "No base report found for version {} (base {})",
-                artifact.version,
+                version,
                 base_commit.sha
             );
             version_comments.push(generate_missing_report_comment(
-                &artifact.version,
-                Some(&base_commit),
+                &label(version),
+                Some(base_commit),
                 Some(&job.head_commit),
             ));
         }
     }
 
     // Check for versions that exist on base but are missing from PR
-    for base_version in &base_versions {
-        if !result.artifacts.iter().any(|a| a.version == *base_version) {
+    for base_version in &target.base_versions {
+        if !artifacts.iter().any(|(v, _)| v == base_version) {
             version_comments.push(generate_missing_report_comment(
-                base_version,
-                Some(&base_commit),
+                &label(base_version),
+                Some(base_commit),
                 Some(&job.head_commit),
             ));
         }
     }
-
-    if !version_comments.is_empty() {
-        let combined_comment = generate_combined_comment(version_comments);
-
-        let pull_requests = fetch_workflow_run_pull_requests(
-            &client,
-            job,
-            owner,
-            repo,
-            repository.default_branch.as_deref(),
-        )
-        .await
-        .context("Failed to fetch pull requests for workflow run")?;
-
-        if pull_requests.is_empty() {
-            tracing::info!(
-                "No associated pull requests found for workflow run {} in {}/{}",
-                job.run_id,
-                owner,
-                repo
-            );
-            return Ok(());
-        }
-
-        // Post/update comments for each associated PR
-        for pull_request in &pull_requests {
-            post_pr_comment(
-                &client,
-                &project_info.project,
-                job.repository_id,
-                pull_request,
-                &combined_comment,
-            )
-            .await
-            .context("Failed to post PR comment")?;
-        }
-    }
-
-    Ok(())
+    Ok(version_comments)
 }
 
 async fn fetch_workflow_run_pull_requests(
@@ -336,8 +555,9 @@ async fn fetch_workflow_run_pull_requests(
     } else {
         job.head_branch.clone()
     };
-    let mut pull_requests =
-        client.all_pages(client.pulls(owner, repo).list().head(&head).send().await?).await?;
+    let mut pull_requests = client
+        .all_pages(counted("pulls", client.pulls(owner, repo).list().head(&head).send()).await?)
+        .await?;
     tracing::info!("Found {} pull requests for {}", pull_requests.len(), head);
 
     pull_requests.retain(|pull_request| {
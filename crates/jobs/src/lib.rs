@@ -1,4 +1,5 @@
 mod jobs;
+mod trace;
 
 use std::{sync::Arc, time::Duration};
 
@@ -19,6 +20,7 @@ pub use jobs::{
     ProcessWorkflowRunJob, RefreshProjectJob, process_refresh_project_job, process_workflow_run_job,
 };
 use sqlx::{Sqlite, migrate::MigrateDatabase, sqlite::SqlitePool};
+pub use trace::TraceContext;
 
 /// Shared context available to all job handlers.
 #[derive(Clone)]
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+
+use opentelemetry::global;
+use serde::{Deserialize, Serialize};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Trace context carried in a job payload, linking the job's spans to the request that queued it.
+///
+/// Empty when trace export is disabled.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TraceContext(HashMap<String, String>);
+
+impl TraceContext {
+    /// Captures the context of the current span.
+    pub fn current() -> Self {
+        let context = Span::current().context();
+        let mut carrier = HashMap::new();
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut carrier)
+        });
+        Self(carrier)
+    }
+
+    /// Sets the captured context as the parent of the given span.
+    pub fn attach(&self, span: &Span) {
+        if self.0.is_empty() {
+            return;
+        }
+        let context = global::get_text_map_propagator(|propagator| propagator.extract(&self.0));
+        span.set_parent(context);
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use apalis::prelude::TaskSink;
+use decomp_dev_github::ratelimit::RateLimitKey;
 use decomp_dev_jobs::RefreshProjectJob;
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tower_sessions::ExpiredDeletion;
@@ -80,9 +81,14 @@ pub async fn create(
     Ok(sched)
 }
 
+/// Rate limit budget required to queue a refresh job. Projects whose token or installation is
+/// below this are deferred until the next scheduled refresh.
+const REFRESH_BUDGET: u64 = 100;
+
 /// Queue refresh jobs for all enabled projects.
 async fn queue_refresh_jobs(state: &AppState, full_refresh: bool) -> Result<()> {
     let mut queued = 0;
+    let mut deferred: Vec<RateLimitKey> = Vec::new();
     for project_info in state.db.get_projects().await? {
         if !project_info.project.enabled {
             log::debug!(
@@ -96,13 +102,38 @@ async fn queue_refresh_jobs(state: &AppState, full_refresh: bool) -> Result<()>
             // Skip projects with active app installations (they get updates via webhooks)
             if let Some(installations) = &state.github.installations {
                 let installations = installations.lock().await;
-                if installations.repo_to_installation.contains_key(&project_info.project.id) {
+                if installations
+                    .repo_to_installation
+                    .contains_key(&project_info.project.repository_id)
+                {
                     continue;
                 }
             }
         }
 
-        let job = RefreshProjectJob { repository_id: project_info.project.id, full_refresh };
+        let (client, key) =
+            match state.github.client_and_key_for(project_info.project.repository_id).await {
+                Ok(result) => result,
+                Err(e) => {
+                    log::error!(
+                        "Failed to get client for {}/{}: {:?}",
+                        project_info.project.owner,
+                        project_info.project.repo,
+                        e
+                    );
+                    continue;
+                }
+            };
+        if deferred.contains(&key) {
+            continue;
+        }
+        if !state.github.rate_limits.has_budget(key, &client, REFRESH_BUDGET).await {
+            log::warn!("Rate limit for {} is low, deferring refresh jobs", key);
+            deferred.push(key);
+            continue;
+        }
+
+        let job = RefreshProjectJob::new(project_info.project.id, full_refresh);
 
         let mut storage = state.jobs.refresh_project();
         if let Err(e) = storage.push(job).await {
@@ -41,6 +41,8 @@ pub async fn migrate_data(state: &mut AppState) -> Result<()> {
             .get_project_info_by_id(project_id, None)
             .await?
             .ok_or_else(|| anyhow!("No project ID {}", project_id))?;
+        // Mappings may target categories defined on the manage page
+        let custom_categories = state.db.get_project_categories(project_id).await?;
         let data =
             reqwest::get(&format!("https://progress.decomp.club/data/{slug}/{version}?mode=all"))
                 .await?;
@@ -147,9 +149,13 @@ pub async fn migrate_data(state: &mut AppState) -> Result<()> {
                             .iter_mut()
                             .find(|c| c.name == mapping.project_category);
                         if category.is_none() {
+                            let name = custom_categories
+                                .iter()
+                                .find(|c| c.id.eq_ignore_ascii_case(&mapping.project_category))
+                                .map_or(&mapping.project_category_name, |c| &c.name);
                             report.categories.push(ReportCategory {
                                 id: mapping.project_category.clone(),
-                                name: mapping.project_category_name.clone(),
+                                name: name.clone(),
                                 measures: None,
                             });
                             category = report.categories.last_mut();
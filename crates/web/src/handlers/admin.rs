@@ -0,0 +1,585 @@
+use anyhow::Result;
+use apalis::prelude::TaskSink;
+use axum::{
+    Form,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+};
+use axum_typed_multipart::{TryFromMultipart, TypedMultipart};
+use bytes::Bytes;
+use decomp_dev_auth::CurrentUser;
+use decomp_dev_core::{
+    AppError,
+    models::{AuditAction, FieldChange, JobRun, Platform, ProjectInfo, SITE_AUDIT_PROJECT_ID},
+    util::size,
+};
+use decomp_dev_db::CacheStats;
+use decomp_dev_images::upload::ICON_VARIANTS;
+use decomp_dev_jobs::RefreshProjectJob;
+use itertools::Itertools;
+use maud::{DOCTYPE, Markup, html};
+use serde::Deserialize;
+use time::UtcDateTime;
+use tower_sessions::Session;
+
+use crate::{
+    AppState,
+    handlers::{
+        common::{Load, TemplateContext, date, nav_links, platform_icon, timeago},
+        manage::{Message, render_message, upload_image},
+    },
+};
+
+const ADMIN_MESSAGE: &str = "admin_message";
+
+pub async fn admin(
+    mut ctx: TemplateContext,
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+) -> Result<Response, AppError> {
+    if !current_user.super_admin {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let projects = state
+        .db
+        .get_projects()
+        .await?
+        .into_iter()
+        .sorted_by(|a, b| lexicmp::natural_lexical_cmp(&a.project.name(), &b.project.name()))
+        .collect::<Vec<_>>();
+    let ingest_times = state.db.get_last_ingest_times().await?;
+    let stats = state.db.get_stats().await?;
+    let (report_cache, report_unit_cache) = state.db.cache_stats();
+    let installed = if let Some(installations) = &state.github.installations {
+        let installations = installations.lock().await;
+        projects
+            .iter()
+            .map(|p| installations.repo_to_installation.contains_key(&p.project.repository_id))
+            .collect::<Vec<_>>()
+    } else {
+        vec![false; projects.len()]
+    };
+    let job_errors = state.db.get_last_job_errors().await?;
+    let rate_limits = state.github.rate_limits.snapshot();
+    let groups = state.db.get_project_groups().await?;
+    let platforms = state.db.get_platforms().await?;
+    let next_sort_order = platforms.iter().map(|p| p.sort_order).max().unwrap_or(0) + 10;
+    let message = session.remove::<Message>(ADMIN_MESSAGE).await?.unwrap_or_default();
+
+    let rendered = html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                title { "Admin • decomp.dev" }
+                (ctx.header().await)
+                (ctx.chunks("main", Load::Deferred).await)
+                (ctx.chunks("manage", Load::Deferred).await)
+            }
+            body {
+                header {
+                    nav {
+                        ul {
+                            li {
+                                a href="/" { strong { "decomp.dev" } }
+                            }
+                            li {
+                                a href="/manage" { "Manage" }
+                            }
+                            li {
+                                a href="/admin" { "Admin" }
+                            }
+                        }
+                        (nav_links())
+                    }
+                }
+                main {
+                    h3 { "Admin" }
+                    (render_message(&message))
+                    .grid {
+                        article {
+                            header { "Database" }
+                            ul {
+                                li { "Size: " (size(stats.size_bytes)) }
+                                li { "Projects: " (stats.projects) }
+                                li { "Reports: " (stats.reports) }
+                                li { "Report units: " (stats.report_units) }
+                                li { "Images: " (stats.images) }
+                            }
+                        }
+                        article {
+                            header { "Caches" }
+                            ul {
+                                li { "Reports: " (cache_summary(&report_cache, false)) }
+                                li { "Report units: " (cache_summary(&report_unit_cache, true)) }
+                            }
+                        }
+                        article {
+                            header { "GitHub rate limits" }
+                            @if rate_limits.is_empty() {
+                                p.muted { "No requests tracked yet." }
+                            } @else {
+                                ul {
+                                    @for (key, budget) in &rate_limits {
+                                        li {
+                                            (key) ": " (budget.available()) " / " (budget.limit) " remaining, resets at "
+                                            (date(budget.reset))
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    .grid {
+                        form action="/admin/cleanup" method="post" data-loading="Starting..." {
+                            button .outline .secondary type="submit" { "Run cleanup" }
+                            small { "Removes orphaned report units and images in the background." }
+                        }
+                        p {
+                            a href="/manage/audit" role="button" .outline .secondary { "Audit log" }
+                        }
+                    }
+                    h4 { "Projects" }
+                    .overflow-auto {
+                        table.striped {
+                            thead {
+                                tr {
+                                    th { "Project" }
+                                    th { "Last ingest" }
+                                    th { "Last job error" }
+                                    th { "App" }
+                                    th {}
+                                }
+                            }
+                            tbody {
+                                @for (info, &installed) in projects.iter().zip(&installed) {
+                                    (project_row(info, installed, ingest_times.get(&info.project.id).copied(), job_errors.get(&info.project.id)))
+                                }
+                            }
+                        }
+                    }
+                    h4 { "Groups" }
+                    p {
+                        small.muted {
+                            "Groups collect related projects, e.g. a game series, on a page with "
+                            "aggregate progress. Maintainers add their projects from the manage page."
+                        }
+                    }
+                    @if groups.is_empty() {
+                        p.muted { "No groups." }
+                    } @else {
+                        .overflow-auto {
+                            table.striped {
+                                thead {
+                                    tr {
+                                        th { "ID" }
+                                        th { "Name" }
+                                        th { "Projects" }
+                                        th {}
+                                    }
+                                }
+                                tbody {
+                                    @for group in &groups {
+                                        tr {
+                                            td { a href=(format!("/groups/{}", group.id)) { code { (group.id) } } }
+                                            td { (group.name) }
+                                            td { (group.project_ids.len()) }
+                                            td {
+                                                form action="/admin/groups/remove" method="post" {
+                                                    input type="hidden" name="id" value=(group.id);
+                                                    button.outline.secondary type="submit" { "Remove" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    form action="/admin/groups" method="post" data-loading="Saving..." {
+                        fieldset {
+                            .grid {
+                                label {
+                                    "ID"
+                                    input name="id" type="text" placeholder="e.g. zelda" pattern="[a-z0-9_\\-]+" required;
+                                }
+                                label {
+                                    "Name"
+                                    input name="name" type="text" placeholder="e.g. The Legend of Zelda" required;
+                                }
+                            }
+                            label {
+                                "Description "
+                                small { "(optional)" }
+                                textarea name="description" rows="2" {}
+                            }
+                        }
+                        button.outline type="submit" { "Save group" }
+                        small { "Saving an existing ID updates it." }
+                    }
+                    h4 { "Platforms" }
+                    .overflow-auto {
+                        table.striped {
+                            thead {
+                                tr {
+                                    th {}
+                                    th { "ID" }
+                                    th { "Name" }
+                                    th { "Year" }
+                                    th { "Order" }
+                                    th { "Projects" }
+                                    th {}
+                                }
+                            }
+                            tbody {
+                                @for platform in &platforms {
+                                    @let count = projects.iter().filter(|p| p.project.platform.as_deref().is_some_and(|id| platform.id.eq_ignore_ascii_case(id))).count();
+                                    tr {
+                                        td { (platform_icon(platform)) }
+                                        td { code { (platform.id) } }
+                                        td { (platform.name) }
+                                        td { @if let Some(year) = platform.year { (year) } }
+                                        td { (platform.sort_order) }
+                                        td { (count) }
+                                        td {
+                                            form action="/admin/platforms/remove" method="post" {
+                                                input type="hidden" name="id" value=(platform.id);
+                                                button.outline.secondary type="submit" disabled[count > 0] { "Remove" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    form action="/admin/platforms" method="post" enctype="multipart/form-data" data-loading="Saving..." {
+                        fieldset {
+                            .grid {
+                                label {
+                                    "ID"
+                                    input name="id" type="text" placeholder="e.g. dc" pattern="[a-z0-9_\\-]+" required;
+                                }
+                                label {
+                                    "Name"
+                                    input name="name" type="text" placeholder="e.g. Dreamcast" required;
+                                }
+                                label {
+                                    "Year "
+                                    small { "(optional)" }
+                                    input name="year" type="number" min="1970" max="2100";
+                                }
+                                label {
+                                    "Order"
+                                    input name="sort_order" type="number" value=(next_sort_order) required;
+                                }
+                            }
+                            label {
+                                "Icon "
+                                small { "(optional)" }
+                                input name="icon" type="file" accept="image/png,image/jpeg,image/webp,image/gif";
+                                small { "Square images work best. Saving without an icon keeps the existing one." }
+                            }
+                        }
+                        button.outline type="submit" { "Save platform" }
+                        small { "Saving an existing ID updates it." }
+                    }
+                }
+            }
+            (ctx.footer(Some(&current_user)))
+        }
+    };
+    Ok((ctx, rendered).into_response())
+}
+
+fn cache_summary(stats: &CacheStats, weighted: bool) -> String {
+    let hit_rate = stats
+        .hit_rate()
+        .map(|r| format!("{:.1}% hit rate", r * 100.0))
+        .unwrap_or_else(|| "no requests".to_string());
+    if weighted {
+        format!("{} entries ({}), {}", stats.entries, size(stats.weighted_size), hit_rate)
+    } else {
+        format!("{} entries, {}", stats.entries, hit_rate)
+    }
+}
+
+fn project_row(
+    info: &ProjectInfo,
+    installed: bool,
+    last_ingest: Option<UtcDateTime>,
+    job_error: Option<&JobRun>,
+) -> Markup {
+    html! {
+        tr {
+            td {
+                a href=(format!("/manage/{}/{}", info.project.owner, info.project.repo_path())) {
+                    (info.project.name())
+                }
+                @if !info.project.enabled {
+                    " "
+                    small.muted { "(disabled)" }
+                }
+            }
+            td {
+                @if let Some(last_ingest) = last_ingest {
+                    span title=(date(last_ingest)) { (timeago(last_ingest)) }
+                } @else {
+                    span.muted { "Never" }
+                }
+            }
+            td {
+                @if let Some(error) = job_error {
+                    details {
+                        summary { span title=(date(error.finished_at)) { (timeago(error.finished_at)) } }
+                        small { code { (error.error.as_deref().unwrap_or_default()) } }
+                    }
+                } @else {
+                    span.muted { "None" }
+                }
+            }
+            td { @if installed { "Yes" } @else { "No" } }
+            td {
+                form action="/admin/refresh" method="post" data-loading="Queueing..." {
+                    input type="hidden" name="project_id" value=(info.project.id);
+                    button .outline .secondary type="submit" { "Full refresh" }
+                }
+            }
+        }
+    }
+}
+
+pub async fn admin_cleanup(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+) -> Result<Response, AppError> {
+    if !current_user.super_admin {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    tokio::spawn(async move {
+        if let Err(e) = state.db.cleanup_report_units().await {
+            tracing::error!("Failed to clean up report units: {:?}", e);
+        }
+        if let Err(e) = state.db.cleanup_images().await {
+            tracing::error!("Failed to clean up images: {:?}", e);
+        }
+    });
+    let message = Message::Info("Cleanup started. Results will be logged.".to_string());
+    session.insert(ADMIN_MESSAGE, message).await?;
+    Ok(Redirect::to("/admin").into_response())
+}
+
+#[derive(Deserialize)]
+pub struct AdminRefreshForm {
+    project_id: u64,
+}
+
+pub async fn admin_refresh(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<AdminRefreshForm>,
+) -> Result<Response, AppError> {
+    if !current_user.super_admin {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let Some(info) = state.db.get_project_info_by_id(form.project_id, None).await? else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    let job = RefreshProjectJob::new(info.project.id, true);
+    let mut storage = state.jobs.refresh_project();
+    let message = match storage.push(job).await {
+        Ok(()) => {
+            state
+                .db
+                .create_audit_log(
+                    current_user.data.id,
+                    &current_user.data.login,
+                    info.project.id,
+                    AuditAction::RefreshProject,
+                    &[],
+                    None,
+                )
+                .await?;
+            Message::Info(format!("Queued full refresh for {}", info.project.name()))
+        }
+        Err(e) => {
+            tracing::error!("Failed to enqueue refresh job: {:?}", e);
+            Message::Error(format!("Failed to queue refresh: {e}"))
+        }
+    };
+    session.insert(ADMIN_MESSAGE, message).await?;
+    Ok(Redirect::to("/admin").into_response())
+}
+
+#[derive(Debug, TryFromMultipart)]
+pub struct PlatformForm {
+    pub id: String,
+    pub name: String,
+    pub year: Option<String>,
+    pub sort_order: i64,
+    pub icon: Option<Bytes>,
+}
+
+pub async fn admin_save_platform(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    TypedMultipart(form): TypedMultipart<PlatformForm>,
+) -> Result<Response, AppError> {
+    if !current_user.super_admin {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let id = form.id.trim().to_ascii_lowercase();
+    let name = form.name.trim();
+    if id.is_empty()
+        || name.is_empty()
+        || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(AppError::Status(StatusCode::BAD_REQUEST));
+    }
+    let year = match form.year.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(year) => {
+            Some(year.parse::<u16>().map_err(|_| AppError::Status(StatusCode::BAD_REQUEST))?)
+        }
+        None => None,
+    };
+    let existing = state.db.get_platforms().await?.into_iter().find(|p| p.id == id);
+    let mut icon_image_id = existing.as_ref().and_then(|p| p.icon_image_id);
+    if let Some(icon) = form.icon.filter(|b| !b.is_empty()) {
+        match upload_image(&state, icon, ICON_VARIANTS).await? {
+            Ok(image_id) => icon_image_id = Some(image_id),
+            Err(e) => {
+                let message = Message::Error(format!("Failed to process icon: {e}"));
+                session.insert(ADMIN_MESSAGE, message).await?;
+                return Ok(Redirect::to("/admin").into_response());
+            }
+        }
+    }
+    let platform =
+        Platform { id, name: name.to_string(), year, icon_image_id, sort_order: form.sort_order };
+    state.db.upsert_platform(&platform).await?;
+    state
+        .db
+        .create_audit_log(
+            current_user.data.id,
+            &current_user.data.login,
+            SITE_AUDIT_PROJECT_ID,
+            AuditAction::SavePlatform,
+            &platform_changes(existing.as_ref(), &platform),
+            Some(&platform.id),
+        )
+        .await?;
+    let message = Message::Info(format!("Saved {}", platform.name));
+    session.insert(ADMIN_MESSAGE, message).await?;
+    Ok(Redirect::to("/admin").into_response())
+}
+
+fn platform_changes(before: Option<&Platform>, after: &Platform) -> Vec<FieldChange> {
+    let fields = |p: &Platform| {
+        [
+            ("name", Some(p.name.clone())),
+            ("year", p.year.map(|y| y.to_string())),
+            ("sort_order", Some(p.sort_order.to_string())),
+            ("icon_image_id", p.icon_image_id.map(hex::encode)),
+        ]
+    };
+    let before = before.map(fields);
+    fields(after)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, (field, after))| {
+            let before = before.as_ref().and_then(|b| b[i].1.clone());
+            (before != after).then(|| FieldChange { field: field.to_string(), before, after })
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+pub struct RemovePlatformForm {
+    id: String,
+}
+
+pub async fn admin_remove_platform(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<RemovePlatformForm>,
+) -> Result<Response, AppError> {
+    if !current_user.super_admin {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let message = if state.db.delete_platform(&form.id).await? {
+        state
+            .db
+            .create_audit_log(
+                current_user.data.id,
+                &current_user.data.login,
+                SITE_AUDIT_PROJECT_ID,
+                AuditAction::RemovePlatform,
+                &[],
+                Some(&form.id),
+            )
+            .await?;
+        Message::Info(format!("Removed {}", form.id))
+    } else {
+        Message::Error("Platform not found or still in use by a project".to_string())
+    };
+    session.insert(ADMIN_MESSAGE, message).await?;
+    Ok(Redirect::to("/admin").into_response())
+}
+
+#[derive(Deserialize)]
+pub struct GroupForm {
+    id: String,
+    name: String,
+    description: String,
+}
+
+pub async fn admin_save_group(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<GroupForm>,
+) -> Result<Response, AppError> {
+    if !current_user.super_admin {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let id = form.id.trim().to_ascii_lowercase();
+    let name = form.name.trim();
+    if id.is_empty()
+        || name.is_empty()
+        || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(AppError::Status(StatusCode::BAD_REQUEST));
+    }
+    let description = Some(form.description.trim()).filter(|s| !s.is_empty());
+    state.db.upsert_project_group(&id, name, description).await?;
+    session.insert(ADMIN_MESSAGE, Message::Info(format!("Saved {name}"))).await?;
+    Ok(Redirect::to("/admin").into_response())
+}
+
+#[derive(Deserialize)]
+pub struct RemoveGroupForm {
+    id: String,
+}
+
+pub async fn admin_remove_group(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<RemoveGroupForm>,
+) -> Result<Response, AppError> {
+    if !current_user.super_admin {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let message = if state.db.delete_project_group(&form.id).await? {
+        Message::Info(format!("Removed {}", form.id))
+    } else {
+        Message::Error("Group not found".to_string())
+    };
+    session.insert(ADMIN_MESSAGE, message).await?;
+    Ok(Redirect::to("/admin").into_response())
+}
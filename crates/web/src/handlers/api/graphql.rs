@@ -0,0 +1,339 @@
+use std::sync::{Arc, OnceLock};
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Result, Schema};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::extract::State;
+use decomp_dev_core::models::{CachedReportFile, ProjectInfo};
+use decomp_dev_db::Database;
+use objdiff_core::bindings::report::ReportUnit;
+
+use super::v1::{ApiCategory, ApiCommit, ApiFunction, ApiMeasures};
+use crate::AppState;
+
+/// Maximum complexity of a single query. List fields multiply the complexity of their children
+/// by the number of requested items.
+const MAX_COMPLEXITY: usize = 20_000;
+const MAX_DEPTH: usize = 12;
+const DEFAULT_FIRST: usize = 10;
+const MAX_FIRST: usize = 100;
+/// Complexity of fetching a report summary, which may not be cached.
+const REPORT_COMPLEXITY: usize = 100;
+/// Complexity of loading the units of a report, which usually means fetching them all from the
+/// database.
+const UNITS_COMPLEXITY: usize = 1000;
+/// Versions aren't paginated, so their complexity assumes a project with this many versions.
+const VERSIONS_ESTIMATE: usize = 4;
+
+type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+fn schema() -> &'static ApiSchema {
+    static SCHEMA: OnceLock<ApiSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .limit_complexity(MAX_COMPLEXITY)
+            .limit_depth(MAX_DEPTH)
+            .finish()
+    })
+}
+
+pub async fn graphql(State(state): State<AppState>, request: GraphQLRequest) -> GraphQLResponse {
+    schema().execute(request.into_inner().data(state.db.clone())).await.into()
+}
+
+/// Returns the GraphQL schema in SDL format.
+pub async fn graphql_schema() -> String { schema().sdl() }
+
+fn page_size(first: Option<usize>) -> usize { first.unwrap_or(DEFAULT_FIRST).min(MAX_FIRST) }
+
+/// Complexity of a list field returning up to `first` items.
+pub(crate) fn list_complexity(first: Option<usize>, child_complexity: usize) -> usize {
+    page_size(first).saturating_mul(child_complexity.max(1))
+}
+
+/// Complexity of a field that fetches a report. Since the cost is added before any parent list
+/// multiplies it, listing reports of many projects or versions quickly exceeds the limit.
+pub(crate) fn report_complexity(child_complexity: usize) -> usize {
+    REPORT_COMPLEXITY.saturating_add(child_complexity)
+}
+
+/// Complexity of listing up to `first` reports.
+pub(crate) fn reports_complexity(first: Option<usize>, child_complexity: usize) -> usize {
+    list_complexity(first, report_complexity(child_complexity))
+}
+
+/// Complexity of listing up to `first` units of a report.
+pub(crate) fn units_complexity(first: Option<usize>, child_complexity: usize) -> usize {
+    UNITS_COMPLEXITY.saturating_add(list_complexity(first, child_complexity))
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Enabled projects, optionally filtered by platform.
+    #[graphql(
+        complexity = "crate::handlers::api::graphql::list_complexity(first, child_complexity)"
+    )]
+    async fn projects(
+        &self,
+        ctx: &Context<'_>,
+        platform: Option<String>,
+        first: Option<usize>,
+    ) -> Result<Vec<ProjectObject>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        Ok(db
+            .get_projects()
+            .await?
+            .into_iter()
+            .filter(|info| info.project.enabled)
+            .filter(|info| {
+                platform.as_deref().is_none_or(|p| info.project.platform.as_deref() == Some(p))
+            })
+            .take(page_size(first))
+            .map(|info| ProjectObject(Arc::new(info)))
+            .collect())
+    }
+
+    async fn project(
+        &self,
+        ctx: &Context<'_>,
+        owner: String,
+        repo: String,
+    ) -> Result<Option<ProjectObject>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        Ok(db
+            .get_project_info(&owner, &repo, None)
+            .await?
+            .filter(|info| info.project.enabled)
+            .map(|info| ProjectObject(Arc::new(info))))
+    }
+}
+
+pub struct ProjectObject(Arc<ProjectInfo>);
+
+#[Object(name = "Project")]
+impl ProjectObject {
+    async fn id(&self) -> u64 { self.0.project.id }
+
+    async fn owner(&self) -> &str { &self.0.project.owner }
+
+    async fn repo(&self) -> &str { &self.0.project.repo }
+
+    /// Distinguishes projects sharing a repository
+    async fn slug(&self) -> Option<&str> { self.0.project.slug.as_deref() }
+
+    async fn name(&self) -> String { self.0.project.name().into_owned() }
+
+    async fn short_name(&self) -> &str { self.0.project.short_name() }
+
+    async fn platform(&self) -> Option<&str> { self.0.project.platform.as_deref() }
+
+    async fn default_category(&self) -> Option<&str> { self.0.project.default_category.as_deref() }
+
+    async fn default_version(&self) -> Option<VersionObject> {
+        self.0.default_version().map(|v| VersionObject::new(&self.0, v))
+    }
+
+    #[graphql(
+        complexity = "crate::handlers::api::graphql::VERSIONS_ESTIMATE * child_complexity.max(1)"
+    )]
+    async fn versions(&self) -> Vec<VersionObject> {
+        self.0.report_versions.iter().map(|v| VersionObject::new(&self.0, v)).collect()
+    }
+
+    async fn version(&self, id: String) -> Option<VersionObject> {
+        self.0
+            .report_versions
+            .iter()
+            .find(|v| v.eq_ignore_ascii_case(&id))
+            .map(|v| VersionObject::new(&self.0, v))
+    }
+}
+
+pub struct VersionObject {
+    info: Arc<ProjectInfo>,
+    id: String,
+}
+
+impl VersionObject {
+    fn new(info: &Arc<ProjectInfo>, id: &str) -> Self {
+        Self { info: info.clone(), id: id.to_string() }
+    }
+}
+
+#[Object(name = "Version")]
+impl VersionObject {
+    async fn id(&self) -> &str { &self.id }
+
+    async fn is_default(&self) -> bool { self.info.default_version() == Some(self.id.as_str()) }
+
+    /// Report for the latest commit.
+    #[graphql(complexity = "crate::handlers::api::graphql::report_complexity(child_complexity)")]
+    async fn latest(&self, ctx: &Context<'_>) -> Result<Option<ReportObject>> {
+        let Some(commit) = &self.info.commit else {
+            return Ok(None);
+        };
+        self.report_for(ctx, &commit.sha).await
+    }
+
+    /// Report for a specific commit.
+    #[graphql(complexity = "crate::handlers::api::graphql::report_complexity(child_complexity)")]
+    async fn report(&self, ctx: &Context<'_>, commit: String) -> Result<Option<ReportObject>> {
+        self.report_for(ctx, &commit).await
+    }
+
+    /// Reports for the most recent commits, newest first.
+    #[graphql(
+        complexity = "crate::handlers::api::graphql::reports_complexity(first, child_complexity)"
+    )]
+    async fn commits(&self, ctx: &Context<'_>, first: Option<usize>) -> Result<Vec<ReportObject>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let commits =
+            db.get_report_commits(self.info.project.id, &self.id, page_size(first) as u32).await?;
+        let mut reports = Vec::with_capacity(commits.len());
+        for commit in commits {
+            if let Some(report) = self.report_for(ctx, &commit.sha).await? {
+                reports.push(report);
+            }
+        }
+        Ok(reports)
+    }
+}
+
+impl VersionObject {
+    async fn report_for(&self, ctx: &Context<'_>, commit: &str) -> Result<Option<ReportObject>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        Ok(db
+            .get_report(self.info.project.id, commit, &self.id)
+            .await?
+            .map(|file| ReportObject { info: self.info.clone(), file }))
+    }
+}
+
+pub struct ReportObject {
+    info: Arc<ProjectInfo>,
+    file: CachedReportFile,
+}
+
+#[Object(name = "Report")]
+impl ReportObject {
+    async fn version(&self) -> &str { &self.file.version }
+
+    async fn commit(&self) -> ApiCommit { ApiCommit::from(&self.file.commit) }
+
+    /// Measures for a category, or the project's default category if omitted.
+    async fn measures(&self, category: Option<String>) -> ApiMeasures {
+        let category = category.as_deref().or(self.info.project.default_category.as_deref());
+        ApiMeasures::from(self.file.report.measures(category))
+    }
+
+    async fn categories(&self) -> Vec<ApiCategory> {
+        self.file.report.categories.iter().map(ApiCategory::from).collect()
+    }
+
+    /// Units in the report, optionally filtered by category. Loading units is more expensive
+    /// than the summary fields, since the full report must be fetched.
+    #[graphql(
+        complexity = "crate::handlers::api::graphql::units_complexity(first, child_complexity)"
+    )]
+    async fn units(
+        &self,
+        ctx: &Context<'_>,
+        category: Option<String>,
+        first: Option<usize>,
+    ) -> Result<Vec<UnitObject>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let report = db.upgrade_report(&self.file).await?;
+        Ok(report
+            .report
+            .units
+            .into_iter()
+            .filter(|u| {
+                category.as_ref().is_none_or(|c| {
+                    u.metadata.as_ref().is_some_and(|m| m.progress_categories.contains(c))
+                })
+            })
+            .take(page_size(first))
+            .map(UnitObject)
+            .collect())
+    }
+}
+
+pub struct UnitObject(Arc<ReportUnit>);
+
+#[Object(name = "Unit")]
+impl UnitObject {
+    async fn name(&self) -> &str { &self.0.name }
+
+    async fn measures(&self) -> Option<ApiMeasures> {
+        self.0.measures.as_ref().map(ApiMeasures::from)
+    }
+
+    async fn module_name(&self) -> Option<&str> {
+        self.0.metadata.as_ref().and_then(|m| m.module_name.as_deref())
+    }
+
+    async fn source_path(&self) -> Option<&str> {
+        self.0.metadata.as_ref().and_then(|m| m.source_path.as_deref())
+    }
+
+    async fn complete(&self) -> Option<bool> { self.0.metadata.as_ref().and_then(|m| m.complete) }
+
+    async fn categories(&self) -> Vec<String> {
+        self.0.metadata.as_ref().map(|m| m.progress_categories.clone()).unwrap_or_default()
+    }
+
+    #[graphql(
+        complexity = "crate::handlers::api::graphql::list_complexity(first, child_complexity)"
+    )]
+    async fn functions(&self, first: Option<usize>) -> Vec<ApiFunction> {
+        self.0
+            .functions
+            .iter()
+            .take(page_size(first))
+            .map(|f| ApiFunction::new(&self.0, f))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    async fn is_too_complex(query: &str) -> bool {
+        let response = schema().execute(query).await;
+        response.errors.iter().any(|e| e.message.contains("too complex"))
+    }
+
+    #[tokio::test]
+    async fn test_complexity() {
+        // Units of every version of hundreds of projects
+        assert!(
+            is_too_complex(
+                "{ projects(first: 300) { versions { latest { units(first: 1) { name } } } } }"
+            )
+            .await
+        );
+        assert!(
+            is_too_complex("{ projects(first: 100) { versions { latest { version } } } }").await
+        );
+
+        assert!(
+            !is_too_complex("{ projects(first: 100) { name versions { id isDefault } } }").await
+        );
+        assert!(
+            !is_too_complex(
+                "{ projects(first: 50) { defaultVersion { latest { measures { matchedCodePercent } } } } }"
+            )
+            .await
+        );
+        assert!(
+            !is_too_complex(
+                r#"{ project(owner: "a", repo: "b") { version(id: "v") {
+                    latest { units(first: 100) { name measures { matchedCodePercent } } }
+                } } }"#
+            )
+            .await
+        );
+    }
+}
@@ -5,6 +5,9 @@ use maud::{DOCTYPE, html};
 
 use crate::handlers::common::{Load, TemplateContext, nav_links};
 
+pub mod graphql;
+pub mod v1;
+
 pub async fn overview(
     mut ctx: TemplateContext,
     current_user: Option<CurrentUser>,
@@ -35,6 +38,20 @@ pub async fn overview(
                 }
                 main {
                     h1 { "API" }
+                    p {
+                        "A versioned JSON API is available under "
+                        code { "/api/v1" }
+                        ". See the "
+                        a href="/api/v1/openapi.json" { "OpenAPI document" }
+                        " for all endpoints and response types."
+                    }
+                    p {
+                        "For nested queries, a GraphQL endpoint is available at "
+                        code { "/api/graphql" }
+                        ". See the "
+                        a href="/api/graphql/schema" { "schema" }
+                        "."
+                    }
                     #root {}
                 }
             }
@@ -0,0 +1,808 @@
+//! Versioned JSON API. Response types in this module are part of the public contract and must
+//! only change in backwards-compatible ways.
+
+use async_graphql::SimpleObject;
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+};
+use decomp_dev_core::{
+    AppError,
+    models::{Commit, FullReportFile, ProjectInfo},
+};
+use decomp_dev_github::changes::generate_changes;
+use objdiff_core::bindings::report::{
+    ChangeItem, ChangeItemInfo, Measures, ReportCategory, ReportItem, ReportUnit,
+};
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::AppState;
+
+const DEFAULT_PAGE_LIMIT: u32 = 50;
+const MAX_PAGE_LIMIT: u32 = 500;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "decomp.dev API", version = "1"),
+    paths(
+        list_projects,
+        get_project,
+        list_versions,
+        list_commits,
+        get_report,
+        list_units,
+        list_functions,
+        get_history,
+        get_changes
+    )
+)]
+pub struct ApiDoc;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/openapi.json", get(openapi))
+        .route("/projects", get(list_projects))
+        .route("/projects/{owner}/{repo}", get(get_project))
+        .route("/projects/{owner}/{repo}/versions", get(list_versions))
+        .route("/projects/{owner}/{repo}/versions/{version}/commits", get(list_commits))
+        .route("/projects/{owner}/{repo}/versions/{version}/history", get(get_history))
+        .route("/projects/{owner}/{repo}/versions/{version}/changes", get(get_changes))
+        .route("/projects/{owner}/{repo}/versions/{version}/reports/{commit}", get(get_report))
+        .route(
+            "/projects/{owner}/{repo}/versions/{version}/reports/{commit}/units",
+            get(list_units),
+        )
+        .route(
+            "/projects/{owner}/{repo}/versions/{version}/reports/{commit}/functions",
+            get(list_functions),
+        )
+}
+
+async fn openapi() -> Json<utoipa::openapi::OpenApi> { Json(ApiDoc::openapi()) }
+
+/// Cursor pagination parameters.
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// Opaque cursor from the `next_cursor` of the previous page
+    cursor: Option<String>,
+    /// Maximum number of items to return (default 50, maximum 500)
+    limit: Option<u32>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor for the next page, or null if this is the last page
+    pub next_cursor: Option<String>,
+}
+
+impl PageQuery {
+    fn limit(&self) -> u32 { self.limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT) }
+
+    /// Decodes the cursor, if any, into the key of the last item of the previous page.
+    fn after(&self) -> Result<Option<String>, AppError> {
+        self.cursor.as_deref().map(decode_cursor).transpose()
+    }
+}
+
+/// Encodes the key of the last item of a page as an opaque cursor.
+fn encode_cursor(key: &str) -> String { hex::encode(key) }
+
+fn decode_cursor(cursor: &str) -> Result<String, AppError> {
+    hex::decode(cursor)
+        .ok()
+        .and_then(|b| String::from_utf8(b).ok())
+        .ok_or(AppError::Status(StatusCode::BAD_REQUEST))
+}
+
+/// Builds a page from up to `limit + 1` items following the cursor. The extra item only
+/// signals that another page follows.
+fn next_page<T>(mut items: Vec<T>, limit: u32, key: impl Fn(&T) -> String) -> Page<T> {
+    let limit = limit as usize;
+    let next_cursor = if items.len() > limit {
+        items.truncate(limit);
+        items.last().map(|item| encode_cursor(&key(item)))
+    } else {
+        None
+    };
+    Page { items, next_cursor }
+}
+
+/// Returns the page of `items` following the cursor. Cursors encode the key of the last item
+/// returned, so pages stay consistent when items are added before the cursor.
+fn paginate<T>(
+    items: Vec<T>,
+    query: &PageQuery,
+    key: impl Fn(&T) -> String,
+) -> Result<Page<T>, AppError> {
+    let start = match query.after()? {
+        Some(after) => {
+            items
+                .iter()
+                .position(|item| key(item) == after)
+                .ok_or(AppError::Status(StatusCode::BAD_REQUEST))?
+                + 1
+        }
+        None => 0,
+    };
+    let limit = query.limit();
+    let items = items.into_iter().skip(start).take(limit as usize + 1).collect();
+    Ok(next_page(items, limit, key))
+}
+
+#[derive(Serialize, ToSchema, SimpleObject)]
+#[graphql(name = "Measures")]
+pub struct ApiMeasures {
+    pub fuzzy_match_percent: f32,
+    pub total_code: u64,
+    pub matched_code: u64,
+    pub matched_code_percent: f32,
+    pub total_data: u64,
+    pub matched_data: u64,
+    pub matched_data_percent: f32,
+    pub total_functions: u32,
+    pub matched_functions: u32,
+    pub matched_functions_percent: f32,
+    pub complete_code: u64,
+    pub complete_code_percent: f32,
+    pub complete_data: u64,
+    pub complete_data_percent: f32,
+    pub total_units: u32,
+    pub complete_units: u32,
+}
+
+impl From<&Measures> for ApiMeasures {
+    fn from(m: &Measures) -> Self {
+        Self {
+            fuzzy_match_percent: m.fuzzy_match_percent,
+            total_code: m.total_code,
+            matched_code: m.matched_code,
+            matched_code_percent: m.matched_code_percent,
+            total_data: m.total_data,
+            matched_data: m.matched_data,
+            matched_data_percent: m.matched_data_percent,
+            total_functions: m.total_functions,
+            matched_functions: m.matched_functions,
+            matched_functions_percent: m.matched_functions_percent,
+            complete_code: m.complete_code,
+            complete_code_percent: m.complete_code_percent,
+            complete_data: m.complete_data,
+            complete_data_percent: m.complete_data_percent,
+            total_units: m.total_units,
+            complete_units: m.complete_units,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema, SimpleObject)]
+#[graphql(name = "Commit")]
+pub struct ApiCommit {
+    pub sha: String,
+    pub message: Option<String>,
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+}
+
+impl From<&Commit> for ApiCommit {
+    fn from(commit: &Commit) -> Self {
+        Self {
+            sha: commit.sha.clone(),
+            message: commit.message.clone(),
+            timestamp: commit.timestamp.format(&Rfc3339).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiProject {
+    pub id: u64,
+    pub owner: String,
+    pub repo: String,
+    /// Distinguishes projects sharing a repository, appended to the repository in project URLs
+    /// as `{repo}:{slug}`
+    pub slug: Option<String>,
+    pub repo_url: String,
+    pub name: String,
+    pub short_name: String,
+    pub platform: Option<String>,
+    pub default_version: Option<String>,
+    pub default_category: Option<String>,
+    pub versions: Vec<String>,
+    /// Latest commit with a report
+    pub commit: Option<ApiCommit>,
+}
+
+impl From<&ProjectInfo> for ApiProject {
+    fn from(info: &ProjectInfo) -> Self {
+        let project = &info.project;
+        Self {
+            id: project.id,
+            owner: project.owner.clone(),
+            repo: project.repo.clone(),
+            slug: project.slug.clone(),
+            repo_url: project.repo_url(),
+            name: project.name().into_owned(),
+            short_name: project.short_name().to_string(),
+            platform: project.platform.clone(),
+            default_version: info.default_version().map(str::to_string),
+            default_category: project.default_category.clone(),
+            versions: info.report_versions.clone(),
+            commit: info.commit.as_ref().map(ApiCommit::from),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiVersion {
+    pub id: String,
+    pub is_default: bool,
+}
+
+#[derive(Serialize, ToSchema, SimpleObject)]
+#[graphql(name = "Category")]
+pub struct ApiCategory {
+    pub id: String,
+    pub name: String,
+    pub measures: Option<ApiMeasures>,
+}
+
+impl From<&ReportCategory> for ApiCategory {
+    fn from(category: &ReportCategory) -> Self {
+        Self {
+            id: category.id.clone(),
+            name: category.name.clone(),
+            measures: category.measures.as_ref().map(ApiMeasures::from),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiReport {
+    pub project_id: u64,
+    pub version: String,
+    pub commit: ApiCommit,
+    pub measures: ApiMeasures,
+    pub categories: Vec<ApiCategory>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiUnit {
+    pub name: String,
+    pub measures: Option<ApiMeasures>,
+    pub module_name: Option<String>,
+    pub source_path: Option<String>,
+    pub complete: Option<bool>,
+    pub auto_generated: Option<bool>,
+    pub categories: Vec<String>,
+}
+
+impl From<&ReportUnit> for ApiUnit {
+    fn from(unit: &ReportUnit) -> Self {
+        let metadata = unit.metadata.as_ref();
+        Self {
+            name: unit.name.clone(),
+            measures: unit.measures.as_ref().map(ApiMeasures::from),
+            module_name: metadata.and_then(|m| m.module_name.clone()),
+            source_path: metadata.and_then(|m| m.source_path.clone()),
+            complete: metadata.and_then(|m| m.complete),
+            auto_generated: metadata.and_then(|m| m.auto_generated),
+            categories: metadata.map(|m| m.progress_categories.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema, SimpleObject)]
+#[graphql(name = "Function")]
+pub struct ApiFunction {
+    pub unit: String,
+    pub name: String,
+    pub demangled_name: Option<String>,
+    pub size: u64,
+    pub fuzzy_match_percent: f32,
+    pub virtual_address: Option<u64>,
+}
+
+impl ApiFunction {
+    pub fn new(unit: &ReportUnit, function: &ReportItem) -> Self {
+        let metadata = function.metadata.as_ref();
+        Self {
+            unit: unit.name.clone(),
+            name: function.name.clone(),
+            demangled_name: metadata.and_then(|m| m.demangled_name.clone()),
+            size: function.size,
+            fuzzy_match_percent: function.fuzzy_match_percent,
+            virtual_address: metadata.and_then(|m| m.virtual_address),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiHistoryEntry {
+    pub commit: ApiCommit,
+    pub measures: ApiMeasures,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiItemInfo {
+    pub fuzzy_match_percent: f32,
+    pub size: u64,
+}
+
+impl From<&ChangeItemInfo> for ApiItemInfo {
+    fn from(info: &ChangeItemInfo) -> Self {
+        Self { fuzzy_match_percent: info.fuzzy_match_percent, size: info.size }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiItemChange {
+    pub name: String,
+    pub demangled_name: Option<String>,
+    /// Null if the item was added
+    pub from: Option<ApiItemInfo>,
+    /// Null if the item was removed
+    pub to: Option<ApiItemInfo>,
+}
+
+impl From<&ChangeItem> for ApiItemChange {
+    fn from(item: &ChangeItem) -> Self {
+        Self {
+            name: item.name.clone(),
+            demangled_name: item.metadata.as_ref().and_then(|m| m.demangled_name.clone()),
+            from: item.from.as_ref().map(ApiItemInfo::from),
+            to: item.to.as_ref().map(ApiItemInfo::from),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiUnitChange {
+    pub name: String,
+    pub from: Option<ApiMeasures>,
+    pub to: Option<ApiMeasures>,
+    pub functions: Vec<ApiItemChange>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiChanges {
+    pub from_commit: ApiCommit,
+    pub to_commit: ApiCommit,
+    pub from: ApiMeasures,
+    pub to: ApiMeasures,
+    pub units: Vec<ApiUnitChange>,
+}
+
+#[derive(Deserialize)]
+pub struct ProjectPath {
+    owner: String,
+    repo: String,
+}
+
+#[derive(Deserialize)]
+pub struct VersionPath {
+    owner: String,
+    repo: String,
+    version: String,
+}
+
+#[derive(Deserialize)]
+pub struct ReportPath {
+    owner: String,
+    repo: String,
+    version: String,
+    commit: String,
+}
+
+/// Fetches an enabled project, optionally at a specific commit.
+async fn project_info(
+    state: &AppState,
+    owner: &str,
+    repo: &str,
+    commit: Option<&str>,
+) -> Result<ProjectInfo, AppError> {
+    let commit = commit.filter(|c| !c.eq_ignore_ascii_case("latest"));
+    match state.db.get_project_info(owner, repo, commit).await? {
+        Some(info) if info.project.enabled => Ok(info),
+        _ => Err(AppError::Status(StatusCode::NOT_FOUND)),
+    }
+}
+
+/// Resolves the `default` version alias and verifies that the version exists.
+fn resolve_version<'a>(info: &'a ProjectInfo, version: &'a str) -> Result<&'a str, AppError> {
+    if version.eq_ignore_ascii_case("default") {
+        return info.default_version().ok_or(AppError::Status(StatusCode::NOT_FOUND));
+    }
+    info.report_versions
+        .iter()
+        .find(|v| v.eq_ignore_ascii_case(version))
+        .map(String::as_str)
+        .ok_or(AppError::Status(StatusCode::NOT_FOUND))
+}
+
+/// Fetches a full report for a commit, or the latest commit if `commit` is `latest`.
+async fn load_report(
+    state: &AppState,
+    path: &ReportPath,
+) -> Result<(ProjectInfo, FullReportFile), AppError> {
+    let info = project_info(state, &path.owner, &path.repo, Some(&path.commit)).await?;
+    let version = resolve_version(&info, &path.version)?.to_string();
+    let commit = info.commit.as_ref().ok_or(AppError::Status(StatusCode::NOT_FOUND))?;
+    let report = state
+        .db
+        .get_report(info.project.id, &commit.sha, &version)
+        .await?
+        .ok_or(AppError::Status(StatusCode::NOT_FOUND))?;
+    let report = state.db.upgrade_report(&report).await?;
+    Ok((info, report))
+}
+
+/// List projects
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects",
+    params(PageQuery),
+    responses((status = 200, body = Page<ApiProject>))
+)]
+async fn list_projects(
+    State(state): State<AppState>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<ApiProject>>, AppError> {
+    let mut projects = state
+        .db
+        .get_projects()
+        .await?
+        .iter()
+        .filter(|info| info.project.enabled)
+        .map(ApiProject::from)
+        .collect::<Vec<_>>();
+    projects.sort_by_key(|p| p.id);
+    Ok(Json(paginate(projects, &query, |p| p.id.to_string())?))
+}
+
+/// Get a project
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{owner}/{repo}",
+    params(("owner" = String, Path), ("repo" = String, Path)),
+    responses((status = 200, body = ApiProject), (status = 404))
+)]
+async fn get_project(
+    Path(path): Path<ProjectPath>,
+    State(state): State<AppState>,
+) -> Result<Json<ApiProject>, AppError> {
+    let info = project_info(&state, &path.owner, &path.repo, None).await?;
+    Ok(Json(ApiProject::from(&info)))
+}
+
+/// List report versions of a project
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{owner}/{repo}/versions",
+    params(("owner" = String, Path), ("repo" = String, Path)),
+    responses((status = 200, body = Vec<ApiVersion>), (status = 404))
+)]
+async fn list_versions(
+    Path(path): Path<ProjectPath>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ApiVersion>>, AppError> {
+    let info = project_info(&state, &path.owner, &path.repo, None).await?;
+    let default_version = info.default_version();
+    Ok(Json(
+        info.report_versions
+            .iter()
+            .map(|v| ApiVersion { id: v.clone(), is_default: Some(v.as_str()) == default_version })
+            .collect(),
+    ))
+}
+
+/// List commits with a report for a version, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{owner}/{repo}/versions/{version}/commits",
+    params(("owner" = String, Path), ("repo" = String, Path), ("version" = String, Path), PageQuery),
+    responses((status = 200, body = Page<ApiCommit>), (status = 404))
+)]
+async fn list_commits(
+    Path(path): Path<VersionPath>,
+    State(state): State<AppState>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<ApiCommit>>, AppError> {
+    let info = project_info(&state, &path.owner, &path.repo, None).await?;
+    let version = resolve_version(&info, &path.version)?;
+    let limit = query.limit();
+    let commits = state
+        .db
+        .get_report_commits_after(info.project.id, version, query.after()?.as_deref(), limit + 1)
+        .await?
+        .ok_or(AppError::Status(StatusCode::BAD_REQUEST))?
+        .iter()
+        .map(ApiCommit::from)
+        .collect::<Vec<_>>();
+    Ok(Json(next_page(commits, limit, |c| c.sha.clone())))
+}
+
+/// Get a report summary
+///
+/// `commit` may be `latest`, and `version` may be `default`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{owner}/{repo}/versions/{version}/reports/{commit}",
+    params(
+        ("owner" = String, Path),
+        ("repo" = String, Path),
+        ("version" = String, Path),
+        ("commit" = String, Path)
+    ),
+    responses((status = 200, body = ApiReport), (status = 404))
+)]
+async fn get_report(
+    Path(path): Path<ReportPath>,
+    State(state): State<AppState>,
+) -> Result<Json<ApiReport>, AppError> {
+    let (info, report) = load_report(&state, &path).await?;
+    Ok(Json(ApiReport {
+        project_id: info.project.id,
+        version: report.version.clone(),
+        commit: ApiCommit::from(&report.commit),
+        measures: ApiMeasures::from(&report.report.measures),
+        categories: report.report.categories.iter().map(ApiCategory::from).collect(),
+    }))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UnitsQuery {
+    /// Only include units in this category
+    category: Option<String>,
+}
+
+/// List units in a report
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{owner}/{repo}/versions/{version}/reports/{commit}/units",
+    params(
+        ("owner" = String, Path),
+        ("repo" = String, Path),
+        ("version" = String, Path),
+        ("commit" = String, Path),
+        UnitsQuery,
+        PageQuery
+    ),
+    responses((status = 200, body = Page<ApiUnit>), (status = 404))
+)]
+async fn list_units(
+    Path(path): Path<ReportPath>,
+    State(state): State<AppState>,
+    Query(filter): Query<UnitsQuery>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<ApiUnit>>, AppError> {
+    let (_, report) = load_report(&state, &path).await?;
+    let units = report
+        .report
+        .units
+        .iter()
+        .map(|u| ApiUnit::from(u.as_ref()))
+        .filter(|u| filter.category.as_ref().is_none_or(|c| u.categories.contains(c)))
+        .collect::<Vec<_>>();
+    Ok(Json(paginate(units, &query, |u| u.name.clone())?))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FunctionsQuery {
+    /// Only include functions in this unit
+    unit: Option<String>,
+}
+
+/// List functions in a report
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{owner}/{repo}/versions/{version}/reports/{commit}/functions",
+    params(
+        ("owner" = String, Path),
+        ("repo" = String, Path),
+        ("version" = String, Path),
+        ("commit" = String, Path),
+        FunctionsQuery,
+        PageQuery
+    ),
+    responses((status = 200, body = Page<ApiFunction>), (status = 404))
+)]
+async fn list_functions(
+    Path(path): Path<ReportPath>,
+    State(state): State<AppState>,
+    Query(filter): Query<FunctionsQuery>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<ApiFunction>>, AppError> {
+    let (_, report) = load_report(&state, &path).await?;
+    let functions = report
+        .report
+        .units
+        .iter()
+        .filter(|u| filter.unit.as_ref().is_none_or(|name| &u.name == name))
+        .flat_map(|u| u.functions.iter().map(|f| ApiFunction::new(u, f)))
+        .collect::<Vec<_>>();
+    Ok(Json(paginate(functions, &query, |f| format!("{}\0{}", f.unit, f.name))?))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HistoryQuery {
+    /// Measures for this category instead of the whole project
+    category: Option<String>,
+}
+
+/// Get the progress history of a version, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{owner}/{repo}/versions/{version}/history",
+    params(
+        ("owner" = String, Path),
+        ("repo" = String, Path),
+        ("version" = String, Path),
+        HistoryQuery,
+        PageQuery
+    ),
+    responses((status = 200, body = Page<ApiHistoryEntry>), (status = 404))
+)]
+async fn get_history(
+    Path(path): Path<VersionPath>,
+    State(state): State<AppState>,
+    Query(filter): Query<HistoryQuery>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<Page<ApiHistoryEntry>>, AppError> {
+    let info = project_info(&state, &path.owner, &path.repo, None).await?;
+    let version = resolve_version(&info, &path.version)?;
+    let category = filter.category.as_deref().or(info.project.default_category.as_deref());
+    let limit = query.limit();
+    let entries = state
+        .db
+        .get_report_measures_after(
+            info.project.id,
+            version,
+            category,
+            query.after()?.as_deref(),
+            limit + 1,
+        )
+        .await?
+        .ok_or(AppError::Status(StatusCode::BAD_REQUEST))?
+        .iter()
+        .map(|(commit, measures)| ApiHistoryEntry {
+            commit: ApiCommit::from(commit),
+            measures: ApiMeasures::from(measures),
+        })
+        .collect::<Vec<_>>();
+    Ok(Json(next_page(entries, limit, |e| e.commit.sha.clone())))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChangesQuery {
+    /// Base commit (defaults to the commit before `to`)
+    from: Option<String>,
+    /// Target commit (defaults to the latest commit)
+    to: Option<String>,
+}
+
+/// Compare two reports of a version
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{owner}/{repo}/versions/{version}/changes",
+    params(
+        ("owner" = String, Path),
+        ("repo" = String, Path),
+        ("version" = String, Path),
+        ChangesQuery
+    ),
+    responses((status = 200, body = ApiChanges), (status = 404))
+)]
+async fn get_changes(
+    Path(path): Path<VersionPath>,
+    State(state): State<AppState>,
+    Query(query): Query<ChangesQuery>,
+) -> Result<Json<ApiChanges>, AppError> {
+    let VersionPath { owner, repo, version } = path;
+    let to_commit = query.to.unwrap_or_else(|| "latest".to_string());
+    let to_path =
+        ReportPath { owner: owner.clone(), repo: repo.clone(), version, commit: to_commit };
+    let (info, to) = load_report(&state, &to_path).await?;
+    let from_commit = match query.from {
+        Some(from) => from,
+        None => state
+            .db
+            .get_previous_commit(info.project.id, &to.version, &to.commit.sha, 1)
+            .await?
+            .ok_or(AppError::Status(StatusCode::NOT_FOUND))?,
+    };
+    let from_path = ReportPath { owner, repo, version: to.version.clone(), commit: from_commit };
+    let (_, from) = load_report(&state, &from_path).await?;
+
+    let changes = generate_changes(&from.report.flatten(), &to.report.flatten())?;
+    Ok(Json(ApiChanges {
+        from_commit: ApiCommit::from(&from.commit),
+        to_commit: ApiCommit::from(&to.commit),
+        from: ApiMeasures::from(&changes.from.unwrap_or_default()),
+        to: ApiMeasures::from(&changes.to.unwrap_or_default()),
+        units: changes
+            .units
+            .iter()
+            .map(|u| ApiUnitChange {
+                name: u.name.clone(),
+                from: u.from.as_ref().map(ApiMeasures::from),
+                to: u.to.as_ref().map(ApiMeasures::from),
+                functions: u.functions.iter().map(ApiItemChange::from).collect(),
+            })
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn page_query(cursor: Option<&str>, limit: Option<u32>) -> PageQuery {
+        PageQuery { cursor: cursor.map(str::to_string), limit }
+    }
+
+    #[test]
+    fn test_cursor() {
+        for key in ["0123abcd", "unit\0function", "src/ü.c", ""] {
+            assert_eq!(decode_cursor(&encode_cursor(key)).unwrap(), key);
+        }
+        assert!(decode_cursor("not hex").is_err());
+        // Valid hex, but not UTF-8
+        assert!(decode_cursor("ff").is_err());
+    }
+
+    #[test]
+    fn test_paginate() {
+        let items = (1..=5).collect::<Vec<u32>>();
+        let key = |i: &u32| i.to_string();
+
+        let page = paginate(items.clone(), &page_query(None, Some(2)), key).unwrap();
+        assert_eq!(page.items, [1, 2]);
+        let cursor = page.next_cursor.unwrap();
+        assert_eq!(decode_cursor(&cursor).unwrap(), "2");
+
+        let page = paginate(items.clone(), &page_query(Some(&cursor), Some(2)), key).unwrap();
+        assert_eq!(page.items, [3, 4]);
+        let cursor = page.next_cursor.unwrap();
+
+        // The last page is exactly full, so there's no next page
+        let page = paginate(items.clone(), &page_query(Some(&cursor), Some(1)), key).unwrap();
+        assert_eq!(page.items, [5]);
+        assert!(page.next_cursor.is_none());
+        let page = paginate(items.clone(), &page_query(None, Some(5)), key).unwrap();
+        assert_eq!(page.items.len(), 5);
+        assert!(page.next_cursor.is_none());
+
+        // A cursor at the last item yields an empty page
+        let page =
+            paginate(items.clone(), &page_query(Some(&encode_cursor("5")), None), key).unwrap();
+        assert!(page.items.is_empty());
+        assert!(page.next_cursor.is_none());
+
+        // Limits are clamped
+        let page = paginate(items.clone(), &page_query(None, Some(0)), key).unwrap();
+        assert_eq!(page.items, [1]);
+
+        // Unknown cursors are rejected
+        assert!(paginate(items, &page_query(Some(&encode_cursor("6")), None), key).is_err());
+    }
+
+    #[test]
+    fn test_next_page() {
+        let page = next_page(vec!["a", "b", "c"], 2, |s| s.to_string());
+        assert_eq!(page.items, ["a", "b"]);
+        assert_eq!(decode_cursor(&page.next_cursor.unwrap()).unwrap(), "b");
+        let page = next_page(vec!["a", "b"], 2, |s| s.to_string());
+        assert!(page.next_cursor.is_none());
+        let page = next_page(Vec::<&str>::new(), 2, |s| s.to_string());
+        assert!(page.items.is_empty() && page.next_cursor.is_none());
+    }
+}
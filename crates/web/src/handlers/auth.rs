@@ -42,6 +42,7 @@ pub async fn login(
                         repositories: vec![],
                     },
                     super_admin: true,
+                    maintainer_roles: Default::default(),
                 })
                 .await?;
             return Ok(Redirect::to("/").into_response());
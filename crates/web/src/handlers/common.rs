@@ -15,6 +15,7 @@ use axum::{
 use decomp_dev_auth::CurrentUser;
 use decomp_dev_core::{
     AppError,
+    models::Platform,
     util::{format_percent, size},
 };
 use maud::{Markup, PreEscaped, Render, html};
@@ -417,6 +418,9 @@ impl Render for ProgressSections {
 pub fn nav_links() -> Markup {
     html! {
         ul {
+            li {
+                a href="/search" { "Search" }
+            }
             li {
                 a href="https://ghidra.decomp.dev" { "Ghidra" }
             }
@@ -430,6 +434,18 @@ pub fn nav_links() -> Markup {
     }
 }
 
+/// Renders a platform's uploaded icon, or its icon font glyph.
+pub fn platform_icon(platform: &Platform) -> Markup {
+    html! {
+        @if let Some(icon_image_id) = platform.icon_image_id {
+            img.platform-icon src=(format!("/images/{}.png?w=64&h=64", hex::encode(icon_image_id)))
+                alt="" title=(platform.name);
+        } @else {
+            span.platform-icon.(format!("icon-{}", platform.id)) title=(platform.name) {}
+        }
+    }
+}
+
 pub async fn get_robots() -> Result<String, AppError> {
     static ROBOTS_CACHE: LazyLock<std::sync::RwLock<Option<String>>> =
         LazyLock::new(|| std::sync::RwLock::new(None));
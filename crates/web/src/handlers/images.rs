@@ -7,7 +7,7 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use decomp_dev_core::{AppError, models::ImageId};
-use decomp_dev_images::encode_image;
+use decomp_dev_images::{encode_image, upload::fit_dimensions};
 use image::{ImageFormat, ImageReader};
 
 use crate::{AppState, handlers::parse_accept};
@@ -47,29 +47,16 @@ pub async fn get_image(
     let id = hex::decode(&params.id).map_err(|_| AppError::Status(StatusCode::NOT_FOUND))?;
     let id: ImageId =
         id.as_slice().try_into().map_err(|_| AppError::Status(StatusCode::NOT_FOUND))?;
-    let (mime_str, image_width, image_height, data) =
-        state.db.get_image(id).await?.ok_or_else(|| AppError::Status(StatusCode::NOT_FOUND))?;
+    let (mime_str, image_width, image_height) = state
+        .db
+        .get_image_info(id)
+        .await?
+        .ok_or_else(|| AppError::Status(StatusCode::NOT_FOUND))?;
     let mime_type = mime::Mime::from_str(&mime_str)?;
 
     let mut transforms = Vec::<Transform>::new();
-    let mut current_width = image_width;
-    let mut current_height = image_height;
-    if let Some(width) = query.width
-        && current_width > width
-    {
-        // Maintaining aspect ratio
-        let height = (current_height as f32 * (width as f32 / current_width as f32)) as u32;
-        current_width = width;
-        current_height = height;
-    }
-    if let Some(height) = query.height
-        && current_height > height
-    {
-        // Maintaining aspect ratio
-        let width = (current_width as f32 * (height as f32 / current_height as f32)) as u32;
-        current_width = width;
-        current_height = height;
-    }
+    let (current_width, current_height) =
+        fit_dimensions(image_width, image_height, query.width, query.height);
     if current_width != image_width || current_height != image_height {
         transforms.push(Transform::Resize(current_width, current_height));
     }
@@ -92,24 +79,11 @@ pub async fn get_image(
         essence == mime_type || essence == mime::IMAGE_STAR || essence == mime::STAR_STAR
     });
     if transforms.is_empty() && orig_acceptable {
+        let data = load_original(&state, id).await?;
         out_headers.insert(header::CONTENT_TYPE, mime_str.parse()?);
         return Ok((out_headers, data).into_response());
     }
 
-    let format = ImageFormat::from_mime_type(&mime_str)
-        .ok_or_else(|| anyhow!("Invalid image mime type: {}", mime_str))?;
-    let mut image = ImageReader::with_format(Cursor::new(&data[..]), format).decode()?;
-    for transform in transforms {
-        match transform {
-            Transform::Resize(width, height) => {
-                image = image.resize_exact(width, height, image::imageops::FilterType::Lanczos3);
-            }
-            Transform::Blur(blur) => {
-                image = image.blur(blur);
-            }
-        }
-    }
-
     let mut out_format = None;
     for mime in acceptable {
         if mime.type_() != mime::IMAGE {
@@ -123,12 +97,51 @@ pub async fn get_image(
     }
     // Otherwise, use WebP as the default
     let out_format = out_format.unwrap_or(ImageFormat::WebP);
-    let encoded = encode_image(&image, out_format)?;
-
     out_headers.insert(header::CONTENT_TYPE, out_format.to_mime_type().parse()?);
+
+    // Serve the closest pre-generated variant, if any
+    let variant = state
+        .db
+        .get_image_variant(id, current_width, current_height, query.blur, out_format.to_mime_type())
+        .await?;
+    let mut image = match variant {
+        Some((width, height, variant)) if width == current_width && height == current_height => {
+            return Ok((out_headers, variant).into_response());
+        }
+        // Larger unblurred variants are cheaper to resize than the original
+        // (AVIF decoding is unsupported)
+        Some((_, _, variant)) if query.blur.is_none() && out_format != ImageFormat::Avif => {
+            ImageReader::with_format(Cursor::new(&variant[..]), out_format).decode()?
+        }
+        _ => {
+            let format = ImageFormat::from_mime_type(&mime_str)
+                .ok_or_else(|| anyhow!("Invalid image mime type: {}", mime_str))?;
+            let data = load_original(&state, id).await?;
+            ImageReader::with_format(Cursor::new(&data[..]), format).decode()?
+        }
+    };
+    for transform in transforms {
+        match transform {
+            Transform::Resize(width, height) => {
+                image = image.resize_exact(width, height, image::imageops::FilterType::Lanczos3);
+            }
+            Transform::Blur(blur) => {
+                image = image.blur(blur);
+            }
+        }
+    }
+
+    let encoded = encode_image(&image, out_format)?;
     Ok((out_headers, encoded).into_response())
 }
 
+/// Loads the original image data, which is only needed when no variant can be served.
+async fn load_original(state: &AppState, id: ImageId) -> Result<Vec<u8>, AppError> {
+    let (_, _, _, data) =
+        state.db.get_image(id).await?.ok_or_else(|| AppError::Status(StatusCode::NOT_FOUND))?;
+    Ok(data)
+}
+
 fn extract_extension(params: ImageParams) -> (ImageParams, Option<String>) {
     if let Some((id, ext)) = params.id.rsplit_once('.') {
         return (ImageParams { id: id.to_string() }, Some(ext.to_string()));
@@ -1,4 +1,4 @@
-use std::io::Cursor;
+use std::collections::BTreeSet;
 
 use anyhow::{Context, Result};
 use apalis::prelude::TaskSink;
@@ -14,22 +14,34 @@ use decomp_dev_auth::CurrentUser;
 use decomp_dev_core::{
     AppError,
     models::{
-        ALL_PLATFORMS, CachedReportFile, Project, ProjectInfo, ProjectVisibility, PullReportStyle,
-        project_visibility,
+        AuditAction, AuditLogEntry, CachedReportFile, CustomCategory, FieldChange, ImageId, JobRun,
+        MaintainerRole, Milestone, MilestoneMeasure, Platform, Project, ProjectInfo,
+        ProjectVisibility, PullReportStyle, SITE_AUDIT_PROJECT_ID, project_visibility,
+        version_regex,
     },
+    util::{format_percent, size},
 };
 use decomp_dev_github::{
-    check_for_reports, extract_github_url, graphql::RepositoryPermission, refresh_project,
+    check_for_reports,
+    diagnostics::{ArtifactDiagnostics, SetupDiagnostics},
+    extract_github_url,
+    graphql::RepositoryPermission,
+    refresh_project,
+};
+use decomp_dev_images::upload::{
+    HEADER_VARIANTS, ICON_VARIANTS, MAX_UPLOAD_BYTES, MAX_UPLOAD_DIMENSION, VariantSpec,
+    generate_variants, process_upload,
 };
 use decomp_dev_jobs::RefreshProjectJob;
 use itertools::Itertools;
 use maud::{DOCTYPE, Markup, html};
 use serde::{Deserialize, Serialize};
+use time::{Date, UtcDateTime, macros::format_description};
 use tower_sessions::Session;
 
 use crate::{
     AppState,
-    handlers::common::{Load, TemplateContext, nav_links},
+    handlers::common::{Load, TemplateContext, date, nav_links, timeago},
 };
 
 pub async fn manage(
@@ -42,7 +54,7 @@ pub async fn manage(
         .get_projects()
         .await?
         .into_iter()
-        .filter(|p| current_user.can_manage_repo(p.project.id))
+        .filter(|p| current_user.can_manage_project(&p.project))
         .sorted_by(|a, b| lexicmp::natural_lexical_cmp(&a.project.name(), &b.project.name()))
         .collect::<Vec<_>>();
 
@@ -72,7 +84,13 @@ pub async fn manage(
                 }
                 main {
                     h3 { "Projects" }
-                    p { a href="/manage/new" role="button" { "Add New" } }
+                    p {
+                        a href="/manage/new" role="button" { "Add New" }
+                        @if current_user.super_admin {
+                            " "
+                            a href="/admin" role="button" .secondary { "Admin" }
+                        }
+                    }
                     @if projects.is_empty() {
                         article { "No projects found." }
                     }
@@ -88,7 +106,7 @@ pub async fn manage(
 }
 
 fn project_fragment(info: &ProjectInfo) -> Markup {
-    let project_path = format!("/manage/{}/{}", info.project.owner, info.project.repo);
+    let project_path = format!("/manage/{}/{}", info.project.owner, info.project.repo_path());
     html! {
         article.project {
             .project-header {
@@ -108,7 +126,7 @@ pub async fn new(
     State(state): State<AppState>,
     current_user: CurrentUser,
 ) -> Result<Response, AppError> {
-    render_new(ctx, &state, &current_user, None, None).await
+    render_new(ctx, &state, &current_user, None, None, None).await
 }
 
 async fn render_new(
@@ -117,6 +135,7 @@ async fn render_new(
     current_user: &CurrentUser,
     message: Option<&str>,
     prefill: Option<&Project>,
+    diagnostics: Option<&SetupDiagnostics>,
 ) -> Result<Response, AppError> {
     let projects = state.db.get_projects().await?;
 
@@ -126,7 +145,8 @@ async fn render_new(
         .iter()
         .filter(|r| r.permission == RepositoryPermission::Admin)
         .map(|r| {
-            (r.id, format!("{}/{}", r.owner, r.name), projects.iter().any(|p| p.project.id == r.id))
+            let exists = projects.iter().any(|p| p.project.repository_id == r.id);
+            (r.id, format!("{}/{}", r.owner, r.name), exists)
         })
         .sorted_by(|a, b| lexicmp::lexical_cmp(&a.1, &b.1))
         .collect::<Vec<_>>();
@@ -135,15 +155,19 @@ async fn render_new(
     let current_name = prefill.as_ref().and_then(|p| p.name.as_deref()).unwrap_or("");
     let current_short_name = prefill.as_ref().and_then(|p| p.short_name.as_deref()).unwrap_or("");
     let current_platform = prefill.as_ref().and_then(|p| p.platform.as_deref());
+    let current_slug = prefill.as_ref().and_then(|p| p.slug.as_deref()).unwrap_or("");
+    let current_artifact_prefix =
+        prefill.as_ref().and_then(|p| p.artifact_prefix.as_deref()).unwrap_or("");
+    let current_version_pattern =
+        prefill.as_ref().and_then(|p| p.version_pattern.as_deref()).unwrap_or("");
+    let platforms = state.db.get_platforms().await?;
 
     let repo_options = html! {
         @for (id, repo, exists) in repos {
-            @if exists {
-                option value=(id) disabled { (repo) }
-            } @else if prefill.is_some_and(|p| p.id == id) {
-                option value=(id) selected { (repo) }
-            } @else {
-                option value=(id) { (repo) }
+            @let selected = prefill.is_some_and(|p| p.repository_id == id);
+            option value=(id) selected[selected] {
+                (repo)
+                @if exists { " (has projects)" }
             }
         }
     };
@@ -207,12 +231,24 @@ async fn render_new(
                             }
                             label {
                                 "Platform"
-                                select name="platform" required { (platform_options(current_platform)) }
+                                select name="platform" required { (platform_options(&platforms, current_platform)) }
                                 small { "Platform not listed? Please open an issue on GitHub." }
                             }
+                            details open[!current_slug.is_empty()] {
+                                summary { "Multiple projects in one repository" }
+                                label {
+                                    "Slug"
+                                    input name="slug" value=(current_slug) pattern="[a-z0-9\\-]+" placeholder="e.g. engine";
+                                    small { "Required for additional projects in a repository that already has one. The project will be available at " code { "/{owner}/{repo}:{slug}" } "." }
+                                }
+                                (artifact_rule_fields(current_artifact_prefix, current_version_pattern))
+                            }
                         }
                         button type="submit" { "Add" }
                     }
+                    @if let Some(diagnostics) = diagnostics {
+                        (setup_diagnostics(diagnostics))
+                    }
                 }
             }
             (ctx.footer(Some(current_user)))
@@ -221,17 +257,117 @@ async fn render_new(
     Ok((ctx, rendered).into_response())
 }
 
-fn platform_options(current_platform: Option<&str>) -> Markup {
+fn setup_diagnostics(diagnostics: &SetupDiagnostics) -> Markup {
+    html! {
+        h4 { "Diagnostics" }
+        p {
+            "Checked the latest completed push run of each workflow on branch "
+            code { (diagnostics.branch) }
+            ". Artifacts must be named "
+            code { "{version}_report" }
+            " and contain a "
+            code { "report.json" }
+            " or "
+            code { "{version}_report.json" }
+            " file."
+        }
+        @if diagnostics.workflows.is_empty() {
+            p.muted { "No workflows were checked." }
+        }
+        @for workflow in &diagnostics.workflows {
+            details open {
+                summary { code { (workflow.workflow_id) } }
+                @if let Some(note) = &workflow.note {
+                    p.muted { (note) }
+                }
+                @if let Some(run) = &workflow.run {
+                    p {
+                        "Run "
+                        a href=(run.html_url) target="_blank" { (run.run_id) }
+                        " at commit "
+                        code { (run.head_sha.get(..7).unwrap_or(&run.head_sha)) }
+                    }
+                    @if let Some(error) = &run.error {
+                        p { small { "Error: " (error) } }
+                    } @else if run.artifacts.is_empty() {
+                        p.muted { "Run has no artifacts." }
+                    } @else {
+                        ul {
+                            @for artifact in &run.artifacts {
+                                li { (artifact_diagnostics(artifact)) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn artifact_diagnostics(artifact: &ArtifactDiagnostics) -> Markup {
+    html! {
+        code { (artifact.name) }
+        " — "
+        @match &artifact.version {
+            Ok(version) => { "version " code { (version) } }
+            Err(reason) => { span.muted { "skipped: " (reason) } }
+        }
+        @if !artifact.reports.is_empty() {
+            " — found reports: " (artifact.reports.join(", "))
+        }
+        @if let Some(error) = &artifact.error {
+            br;
+            small { "Error: " (error) }
+        }
+        @if !artifact.files.is_empty() {
+            ul {
+                @for file in &artifact.files {
+                    li {
+                        code { (file.path) }
+                        " — "
+                        @if file.accepted { "accepted" } @else { "rejected" }
+                        ": " (file.reason)
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn artifact_rule_fields(artifact_prefix: &str, version_pattern: &str) -> Markup {
+    html! {
+        label {
+            "Artifact prefix "
+            small { "(optional)" }
+            input name="artifact_prefix" value=(artifact_prefix) placeholder="e.g. engine-";
+            small {
+                "Only artifacts with versions starting with this prefix are imported, e.g. "
+                code { "engine-GALE01_report" } ". The prefix is removed from the version."
+            }
+        }
+        label {
+            "Version pattern "
+            small { "(optional)" }
+            input name="version_pattern" value=(version_pattern) placeholder="e.g. GALE01*";
+            small {
+                "Only versions matching this glob are imported. Prefix with "
+                code { "re:" } " for a regular expression. When several projects share a "
+                "repository, each needs rules that only select its own artifacts."
+            }
+        }
+    }
+}
+
+fn platform_options(platforms: &[Platform], current_platform: Option<&str>) -> Markup {
     html! {
         @if current_platform.is_none() {
             option value="" disabled selected { "Select one" }
         }
-        @for platform in ALL_PLATFORMS {
-            @let platform_str = platform.to_str();
-            @if current_platform == Some(platform_str) {
-                option value=(platform_str) selected { (platform.name()) }
+        @for platform in platforms {
+            @if current_platform.is_some_and(|p| platform.id.eq_ignore_ascii_case(p)) {
+                option value=(platform.id) selected { (platform.name) }
             } @else {
-                option value=(platform_str) { (platform.name()) }
+                option value=(platform.id) { (platform.name) }
             }
         }
     }
@@ -244,6 +380,9 @@ pub struct NewForm {
     name: String,
     short_name: String,
     platform: String,
+    slug: Option<String>,
+    artifact_prefix: Option<String>,
+    version_pattern: Option<String>,
 }
 
 pub async fn new_save(
@@ -263,25 +402,48 @@ pub async fn new_save(
                     &current_user,
                     Some("Invalid repository URL."),
                     None,
+                    None,
                 )
                 .await;
             };
             let Ok(repo) = client.repos(owner, repo).get().await else {
-                return render_new(ctx, &state, &current_user, Some("Repository not found."), None)
-                    .await;
+                return render_new(
+                    ctx,
+                    &state,
+                    &current_user,
+                    Some("Repository not found."),
+                    None,
+                    None,
+                )
+                .await;
             };
             (repo.id.into_inner(), Some(repo))
         }
         (None, None) => {
-            return render_new(ctx, &state, &current_user, Some("Repository is required."), None)
-                .await;
+            return render_new(
+                ctx,
+                &state,
+                &current_user,
+                Some("Repository is required."),
+                None,
+                None,
+            )
+            .await;
         }
     };
-    if let Some(existing) = state.db.get_project_info_by_id(repository_id, None).await? {
-        return Ok(Redirect::to(&format!("/{}/{}", existing.project.owner, existing.project.repo))
-            .into_response());
+    let slug = optional_field(form.slug.as_deref()).map(|s| s.to_ascii_lowercase());
+    let existing = state.db.get_repository_projects(repository_id).await?;
+    if let Some(existing) = existing.iter().find(|p| p.project.slug == slug) {
+        return Ok(Redirect::to(&format!(
+            "/{}/{}",
+            existing.project.owner,
+            existing.project.repo_path()
+        ))
+        .into_response());
     }
-    let Some(platform) = ALL_PLATFORMS.iter().find(|p| p.to_str() == form.platform) else {
+    let platforms = state.db.get_platforms().await?;
+    let Some(platform) = platforms.iter().find(|p| p.id.eq_ignore_ascii_case(&form.platform))
+    else {
         return Err(AppError::Status(StatusCode::BAD_REQUEST));
     };
     let repo = match repo {
@@ -296,6 +458,7 @@ pub async fn new_save(
                     &current_user,
                     Some("Failed to fetch repository information."),
                     None,
+                    None,
                 )
                 .await;
             }
@@ -305,12 +468,15 @@ pub async fn new_save(
     let name = form.name.trim();
     let short_name = form.short_name.trim();
     let mut project = Project {
-        id: repo.id.into_inner(),
+        repository_id: repo.id.into_inner(),
         owner: repo.owner.as_ref().map(|o| o.login.clone()).unwrap_or_default(),
         repo: repo.name.clone(),
+        slug,
+        artifact_prefix: optional_field(form.artifact_prefix.as_deref()),
+        version_pattern: optional_field(form.version_pattern.as_deref()),
         name: (!name.is_empty()).then_some(name.to_string()),
         short_name: (!short_name.is_empty()).then_some(short_name.to_string()),
-        platform: Some(platform.to_str().to_string()),
+        platform: Some(platform.id.clone()),
         ..Default::default()
     };
     if !current_user.super_admin && repo.permissions.as_ref().is_none_or(|p| !p.admin) {
@@ -320,21 +486,50 @@ pub async fn new_save(
             &current_user,
             Some("You do not have admin permissions on this repository."),
             Some(&project),
+            None,
         )
         .await;
     }
+    let invalid = if let Some(slug) = project.slug.as_deref() {
+        if existing.is_empty() {
+            Some("The first project of a repository can't have a slug.".to_string())
+        } else if !is_valid_slug(slug) {
+            Some("Slug may only contain lowercase letters, numbers and dashes.".to_string())
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+    .or_else(|| {
+        let pattern = project.version_pattern.as_deref()?;
+        version_regex(pattern).err().map(|e| format!("Invalid version pattern: {e}"))
+    });
+    if let Some(message) = invalid {
+        return render_new(ctx, &state, &current_user, Some(&message), Some(&project), None).await;
+    }
 
-    let workflow_id = match check_for_reports(&client, &project, &repo).await {
+    let (result, diagnostics) = check_for_reports(&client, &project, &repo).await;
+    let workflow_id = match result {
         Ok(workflow_id) => workflow_id,
         Err(e) => {
             let message = e.to_string();
-            return render_new(ctx, &state, &current_user, Some(&message), Some(&project)).await;
+            return render_new(
+                ctx,
+                &state,
+                &current_user,
+                Some(&message),
+                Some(&project),
+                Some(&diagnostics),
+            )
+            .await;
         }
     };
     project.workflow_id = Some(workflow_id);
-    state.db.create_project(&project).await?;
+    project.id =
+        state.db.create_project(&project, current_user.data.id, &current_user.data.login).await?;
     refresh_project(&state.github, &state.db, project.id, Some(&client), true).await?;
-    Ok(Redirect::to(&format!("/{}/{}", project.owner, project.repo)).into_response())
+    Ok(Redirect::to(&format!("/{}/{}", project.owner, project.repo_path())).into_response())
 }
 
 pub async fn manage_project(
@@ -347,7 +542,7 @@ pub async fn manage_project(
     let Some(info) = state.db.get_project_info(&params.owner, &params.repo, None).await? else {
         return Err(AppError::Status(StatusCode::NOT_FOUND));
     };
-    if !current_user.can_manage_repo(info.project.id) {
+    if !current_user.can_manage_project(&info.project) {
         return Err(AppError::Status(StatusCode::FORBIDDEN));
     }
 
@@ -365,14 +560,14 @@ pub async fn manage_project(
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
-enum Message {
+pub(crate) enum Message {
     #[default]
     None,
     Info(String),
     Error(String),
 }
 
-fn render_message(message: &Message) -> Markup {
+pub(crate) fn render_message(message: &Message) -> Markup {
     match message {
         Message::None => Markup::default(),
         Message::Info(msg) => html! {
@@ -384,6 +579,229 @@ fn render_message(message: &Message) -> Markup {
     }
 }
 
+const AUDIT_LOG_LIMIT: u32 = 100;
+const JOB_RUNS_LIMIT: u32 = 20;
+const MAX_TAGS: usize = 10;
+const MAX_TAG_LEN: usize = 32;
+const MAX_SLUG_LEN: usize = 32;
+/// Requests the pre-generated `thumbnail` variant of a hero image.
+const THUMBNAIL_QUERY: &str = "?w=256&h=256";
+
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug.len() <= MAX_SLUG_LEN
+        && slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Parses a comma-separated tag list, dropping empty, overlong and duplicate (ignoring case)
+/// tags. Tags are sorted case-insensitively and limited to [`MAX_TAGS`].
+fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty() && tag.len() <= MAX_TAG_LEN)
+        .unique_by(|tag| tag.to_lowercase())
+        .sorted_by_key(|tag| tag.to_lowercase())
+        .take(MAX_TAGS)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Trims an optional form field, treating an empty value as unset.
+fn optional_field(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+fn job_runs_table(project: &Project, runs: &[JobRun]) -> Markup {
+    html! {
+        @if runs.is_empty() {
+            p.muted { "No ingest attempts recorded." }
+        } @else {
+            .overflow-auto {
+                table.striped {
+                    thead {
+                        tr {
+                            th { "Time" }
+                            th { "Job" }
+                            th { "Commit" }
+                            th { "Artifacts" }
+                            th { "Inserted" }
+                            th { "Result" }
+                        }
+                    }
+                    tbody {
+                        @for run in runs {
+                            tr {
+                                td {
+                                    span title=(date(run.finished_at)) { (timeago(run.finished_at)) }
+                                }
+                                td {
+                                    @if let Some(run_id) = run.run_id {
+                                        a href=(format!("{}/actions/runs/{run_id}", project.repo_url())) target="_blank" {
+                                            (run.kind)
+                                        }
+                                    } @else {
+                                        (run.kind)
+                                    }
+                                    @if let Some(event) = &run.event {
+                                        br;
+                                        small { (event) }
+                                    }
+                                }
+                                td {
+                                    @if let Some(sha) = &run.commit_sha {
+                                        a href=(format!("{}/commit/{sha}", project.repo_url())) target="_blank" {
+                                            code { (sha.get(..7).unwrap_or(sha)) }
+                                        }
+                                    }
+                                }
+                                td {
+                                    @if let Some(artifacts) = run.artifacts {
+                                        (artifacts)
+                                    }
+                                }
+                                td {
+                                    (run.reports_inserted)
+                                    @if !run.versions.is_empty() {
+                                        br;
+                                        small { (run.versions.join(", ")) }
+                                    }
+                                }
+                                td {
+                                    @if let Some(error) = &run.error {
+                                        details {
+                                            summary { "Failed" }
+                                            pre { code { (error) } }
+                                        }
+                                    } @else if let Some(reason) = &run.skip_reason {
+                                        span.muted { "Skipped: " (reason) }
+                                    } @else {
+                                        "Success"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn audit_log_table(entries: &[AuditLogEntry], show_project: bool) -> Markup {
+    html! {
+        @if entries.is_empty() {
+            p.muted { "No actions recorded." }
+        } @else {
+            .overflow-auto {
+                table.striped {
+                    thead {
+                        tr {
+                            th { "Time" }
+                            th { "User" }
+                            @if show_project {
+                                th { "Project" }
+                            }
+                            th { "Action" }
+                            th { "Changes" }
+                        }
+                    }
+                    tbody {
+                        @for entry in entries {
+                            tr {
+                                td {
+                                    span title=(date(entry.created_at)) { (timeago(entry.created_at)) }
+                                }
+                                td {
+                                    a href=(format!("https://github.com/{}", entry.user_login)) target="_blank" {
+                                        (entry.user_login)
+                                    }
+                                }
+                                @if show_project {
+                                    td {
+                                        @if let (Some(owner), Some(repo)) = (&entry.project_owner, &entry.project_repo) {
+                                            a href=(format!("/manage/{owner}/{repo}")) { (owner) "/" (repo) }
+                                        } @else if entry.project_id == SITE_AUDIT_PROJECT_ID {
+                                            span.muted { "Site" }
+                                        } @else {
+                                            (entry.project_id)
+                                        }
+                                    }
+                                }
+                                td {
+                                    (entry.action)
+                                    @if let Some(details) = &entry.details {
+                                        br;
+                                        small { (details) }
+                                    }
+                                }
+                                td {
+                                    @for change in &entry.changes {
+                                        small {
+                                            code { (change.field) } ": "
+                                            (change.before.as_deref().unwrap_or("(none)"))
+                                            " → "
+                                            (change.after.as_deref().unwrap_or("(none)"))
+                                        }
+                                        br;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub async fn audit_log(
+    mut ctx: TemplateContext,
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Response, AppError> {
+    if !current_user.super_admin {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let entries = state.db.get_audit_log(None, AUDIT_LOG_LIMIT).await?;
+    let rendered = html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                title { "Audit Log • decomp.dev" }
+                (ctx.header().await)
+                (ctx.chunks("main", Load::Deferred).await)
+                (ctx.chunks("manage", Load::Deferred).await)
+            }
+            body {
+                header {
+                    nav {
+                        ul {
+                            li {
+                                a href="/" { strong { "decomp.dev" } }
+                            }
+                            li {
+                                a href="/manage" { "Manage" }
+                            }
+                            li {
+                                a href="/manage/audit" { "Audit log" }
+                            }
+                        }
+                        (nav_links())
+                    }
+                }
+                main {
+                    h3 { "Audit log" }
+                    (audit_log_table(&entries, true))
+                }
+            }
+            (ctx.footer(Some(&current_user)))
+        }
+    };
+    Ok((ctx, rendered).into_response())
+}
+
 async fn render_manage_project(
     mut ctx: TemplateContext,
     state: &AppState,
@@ -394,19 +812,24 @@ async fn render_manage_project(
 ) -> Result<Response, AppError> {
     let project_short_name = project_info.project.short_name();
     let project_manage_path =
-        format!("/manage/{}/{}", project_info.project.owner, project_info.project.repo);
-    let refresh_path =
-        format!("/manage/{}/{}/refresh", project_info.project.owner, project_info.project.repo);
+        format!("/manage/{}/{}", project_info.project.owner, project_info.project.repo_path());
+    let refresh_path = format!(
+        "/manage/{}/{}/refresh",
+        project_info.project.owner,
+        project_info.project.repo_path()
+    );
     let default_version = project_info.default_version();
 
     let current_name = project_info.project.name.as_deref().unwrap_or("");
     let current_short_name = project_info.project.short_name.as_deref().unwrap_or("");
     let current_platform = project_info.project.platform.as_deref();
+    let platforms = state.db.get_platforms().await?;
+    let groups = state.db.get_project_groups().await?;
     let current_workflow_id = project_info.project.workflow_id.as_deref().unwrap_or("");
 
     let installation_id = if let Some(installations) = &state.github.installations {
         let installations = installations.lock().await;
-        installations.repo_to_installation.get(&project_info.project.id).cloned()
+        installations.repo_to_installation.get(&project_info.project.repository_id).cloned()
     } else {
         None
     };
@@ -416,6 +839,28 @@ async fn render_manage_project(
         .await?
         .unwrap_or_default();
 
+    let audit_log = state.db.get_audit_log(Some(project_info.project.id), AUDIT_LOG_LIMIT).await?;
+    let maintainers = state.db.get_project_maintainers(project_info.project.id).await?;
+    let job_runs = state.db.get_job_runs(project_info.project.id, JOB_RUNS_LIMIT).await?;
+    let can_manage_maintainers = current_user.can_manage_maintainers(&project_info.project);
+    let maintainers_path = format!(
+        "/manage/{}/{}/maintainers",
+        project_info.project.owner,
+        project_info.project.repo_path()
+    );
+    let categories = state.db.get_project_categories(project_info.project.id).await?;
+    let categories_path = format!(
+        "/manage/{}/{}/categories",
+        project_info.project.owner,
+        project_info.project.repo_path()
+    );
+    let milestones = state.db.get_milestones(project_info.project.id, None).await?;
+    let milestones_path = format!(
+        "/manage/{}/{}/milestones",
+        project_info.project.owner,
+        project_info.project.repo_path()
+    );
+
     // Check if the project is hidden based on matched code percentage
     let visibility =
         project_visibility(&project_info.project, latest_report.map(|r| &r.report.measures));
@@ -485,9 +930,28 @@ async fn render_manage_project(
                             }
                             label {
                                 "Platform"
-                                select name="platform" { (platform_options(current_platform)) }
+                                select name="platform" { (platform_options(&platforms, current_platform)) }
                                 small { "Platform not listed? Please open an issue on GitHub." }
                             }
+                            label {
+                                "Tags "
+                                small { "(optional)" }
+                                input name="tags" value=(project_info.tags.join(", ")) placeholder="e.g. Zelda, matching";
+                                small { "Comma-separated. Tags can be used to filter the projects list." }
+                            }
+                            @if !groups.is_empty() {
+                                label {
+                                    "Groups "
+                                    small { "(optional)" }
+                                    select name="groups" multiple size=(groups.len().min(5)) {
+                                        @for group in &groups {
+                                            @let selected = group.project_ids.contains(&project_info.project.id);
+                                            option value=(group.id) selected[selected] { (group.name) }
+                                        }
+                                    }
+                                    small { "Related projects, e.g. a game series. Groups are created by site admins." }
+                                }
+                            }
                             label {
                                 "Default version"
                                 select name="default_version" {
@@ -502,6 +966,16 @@ async fn render_manage_project(
                                 input name="workflow_id" type="text" value=(current_workflow_id);
                                 small { "The GitHub Actions workflow that contains report artifacts." }
                             }
+                            @if let Some(slug) = &project_info.project.slug {
+                                label {
+                                    "Slug"
+                                    input type="text" readonly disabled value=(slug);
+                                }
+                            }
+                            (artifact_rule_fields(
+                                project_info.project.artifact_prefix.as_deref().unwrap_or(""),
+                                project_info.project.version_pattern.as_deref().unwrap_or(""),
+                            ))
                             label {
                                 input name="enable_pr_comments" type="checkbox" role="switch"
                                     disabled[installation_id.is_none()]
@@ -523,13 +997,24 @@ async fn render_manage_project(
                                 }
                             }
                             hr;
+                            @if let Some(header_image_id) = project_info.project.header_image_id.map(hex::encode) {
+                                picture {
+                                    source srcset=(format!("/images/{header_image_id}.webp{THUMBNAIL_QUERY}")) type="image/webp";
+                                    img.header-thumbnail src=(format!("/images/{header_image_id}.jpg{THUMBNAIL_QUERY}")) alt="Current hero image";
+                                }
+                            }
                             label {
                                 "Hero image "
                                 small { "(optional)" }
-                                input name="header_image" type="file" accept="image/*";
+                                input name="header_image" type="file" accept="image/png,image/jpeg,image/webp,image/gif";
                                 small {
                                     "Image should be at least 1024×256. A common size is 1920×620."
                                     br;
+                                    (format!(
+                                        "PNG, JPEG, WebP or GIF, up to {} and {MAX_UPLOAD_DIMENSION}×{MAX_UPLOAD_DIMENSION}.",
+                                        size(MAX_UPLOAD_BYTES as u64)
+                                    ))
+                                    br;
                                     "Upload the best quality/resolution available; the image will be resized to fit."
                                     br;
                                     a href="https://www.steamgriddb.com/heroes" target="_blank" { "SteamGridDB" }
@@ -544,6 +1029,260 @@ async fn render_manage_project(
                         }
                         button type="submit" { "Save" }
                     }
+                    h4 { "Maintainers" }
+                    p {
+                        small.muted {
+                            "Users with admin permissions on the GitHub repository can always manage the project. "
+                            "Maintainers can manage the project without repository permissions; "
+                            "admins can also add and remove maintainers."
+                        }
+                    }
+                    @if maintainers.is_empty() {
+                        p.muted { "No additional maintainers." }
+                    } @else {
+                        .overflow-auto {
+                            table.striped {
+                                thead {
+                                    tr {
+                                        th { "User" }
+                                        th { "Role" }
+                                        th { "Added" }
+                                        @if can_manage_maintainers {
+                                            th {}
+                                        }
+                                    }
+                                }
+                                tbody {
+                                    @for maintainer in &maintainers {
+                                        tr {
+                                            td {
+                                                a href=(format!("https://github.com/{}", maintainer.user_login)) target="_blank" {
+                                                    (maintainer.user_login)
+                                                }
+                                            }
+                                            td { (maintainer.role) }
+                                            td {
+                                                span title=(date(maintainer.created_at)) { (timeago(maintainer.created_at)) }
+                                            }
+                                            @if can_manage_maintainers {
+                                                td {
+                                                    form action=(format!("{maintainers_path}/remove")) method="post" {
+                                                        input type="hidden" name="user_id" value=(maintainer.user_id);
+                                                        button.outline.secondary type="submit" { "Remove" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    @if can_manage_maintainers {
+                        form action=(maintainers_path) method="post" data-loading="Adding..." {
+                            label {
+                                "Add maintainer"
+                                fieldset role="group" {
+                                    input name="login" type="text" placeholder="GitHub username" required;
+                                    select name="role" {
+                                        @for &role in MaintainerRole::variants() {
+                                            option value=(role.as_str()) { (role) }
+                                        }
+                                    }
+                                    button.outline type="submit" { "Add" }
+                                }
+                                small { "Adding an existing maintainer updates their role." }
+                            }
+                        }
+                    }
+                    h4 { "Categories" }
+                    p {
+                        small.muted {
+                            "Categories defined here group units by name, for reports that don't "
+                            "include them. Rules are glob patterns over unit names, one per line "
+                            "("
+                            code { "*" }
+                            " matches within a path segment, "
+                            code { "**" }
+                            " across segments), or regular expressions prefixed with "
+                            code { "re:" }
+                            ". Categories in the report take precedence over categories with the same ID."
+                        }
+                    }
+                    @if categories.is_empty() {
+                        p.muted { "No categories." }
+                    } @else {
+                        .overflow-auto {
+                            table.striped {
+                                thead {
+                                    tr {
+                                        th { "ID" }
+                                        th { "Name" }
+                                        th { "Rules" }
+                                        th {}
+                                    }
+                                }
+                                tbody {
+                                    @for category in &categories {
+                                        tr {
+                                            td { code { (category.id) } }
+                                            td { (category.name) }
+                                            td {
+                                                @for rule in &category.rules {
+                                                    code { (rule) }
+                                                    br;
+                                                }
+                                            }
+                                            td {
+                                                form action=(format!("{categories_path}/remove")) method="post" {
+                                                    input type="hidden" name="id" value=(category.id);
+                                                    button.outline.secondary type="submit" { "Remove" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    form action=(categories_path) method="post" data-loading="Saving..." {
+                        fieldset {
+                            .grid {
+                                label {
+                                    "ID"
+                                    input name="id" type="text" placeholder="e.g. game" pattern="[A-Za-z0-9_\\-]+" required;
+                                }
+                                label {
+                                    "Name"
+                                    input name="name" type="text" placeholder="e.g. Game" required;
+                                }
+                            }
+                            label {
+                                "Rules"
+                                textarea name="rules" rows="3" placeholder="main/Game/**\nre:^main/(Map|Obj)/" required {}
+                            }
+                        }
+                        button.outline type="submit" { "Save category" }
+                        small { "Saving an existing ID replaces its name and rules." }
+                    }
+                    h4 { "Milestones" }
+                    p {
+                        small.muted {
+                            "Milestones track a target for a measure, optionally limited to a category "
+                            "or to units with a name prefix. A milestone is achieved when a new report "
+                            "reaches its target."
+                        }
+                    }
+                    @if milestones.is_empty() {
+                        p.muted { "No milestones." }
+                    } @else {
+                        .overflow-auto {
+                            table.striped {
+                                thead {
+                                    tr {
+                                        th { "Name" }
+                                        th { "Version" }
+                                        th { "Target" }
+                                        th { "Scope" }
+                                        th { "Due" }
+                                        th { "Status" }
+                                        th {}
+                                    }
+                                }
+                                tbody {
+                                    @for milestone in &milestones {
+                                        tr {
+                                            td { (milestone.name) }
+                                            td { (milestone.version) }
+                                            td { (format_percent(milestone.target)) " " (milestone.measure) }
+                                            td {
+                                                @if let Some(scope) = milestone.scope() {
+                                                    code { (scope) }
+                                                } @else {
+                                                    span.muted { "All" }
+                                                }
+                                            }
+                                            td {
+                                                @if let Some(due_date) = milestone.due_date {
+                                                    (due_date.date())
+                                                }
+                                            }
+                                            td {
+                                                @if let Some(achieved_at) = milestone.achieved_at {
+                                                    "Achieved "
+                                                    span title=(date(achieved_at)) { (timeago(achieved_at)) }
+                                                } @else {
+                                                    "In progress"
+                                                }
+                                            }
+                                            td {
+                                                form action=(format!("{milestones_path}/remove")) method="post" {
+                                                    input type="hidden" name="id" value=(milestone.id);
+                                                    button.outline.secondary type="submit" { "Remove" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    form action=(milestones_path) method="post" data-loading="Adding..." {
+                        fieldset {
+                            label {
+                                "Name"
+                                input name="name" type="text" placeholder="e.g. Link main/Game" required;
+                            }
+                            .grid {
+                                label {
+                                    "Version"
+                                    select name="version" {
+                                        @for version in &project_info.report_versions {
+                                            @let selected = default_version == Some(version.as_str());
+                                            option value=(version) selected[selected] { (version) }
+                                        }
+                                    }
+                                }
+                                label {
+                                    "Measure"
+                                    select name="measure" {
+                                        @for &measure in MilestoneMeasure::variants() {
+                                            option value=(measure.as_str()) { (measure) }
+                                        }
+                                    }
+                                }
+                                label {
+                                    "Target (%)"
+                                    input name="target" type="number" min="0.01" max="100" step="any" value="100" required;
+                                }
+                            }
+                            .grid {
+                                label {
+                                    "Category "
+                                    small { "(optional)" }
+                                    select name="category" {
+                                        option value="" { "All" }
+                                        @if let Some(report) = latest_report {
+                                            @for category in &report.report.categories {
+                                                option value=(category.id) { (category.name) }
+                                            }
+                                        }
+                                    }
+                                }
+                                label {
+                                    "Unit prefix "
+                                    small { "(optional)" }
+                                    input name="unit_prefix" type="text" placeholder="e.g. main/Game/";
+                                }
+                                label {
+                                    "Due date "
+                                    small { "(optional)" }
+                                    input name="due_date" type="date";
+                                }
+                            }
+                        }
+                        button.outline type="submit" { "Add milestone" }
+                    }
                     h4 { "Debug" }
                     @if let Some(installation_id) = installation_id {
                         p {
@@ -562,7 +1301,7 @@ async fn render_manage_project(
                             small { "Fetches any missing report artifacts." }
                         }
                     }
-                    form.mt-spacing action=(format!("/manage/{}/{}/delete-commit", project_info.project.owner, project_info.project.repo)) method="post" data-loading="Deleting..." {
+                    form.mt-spacing action=(format!("/manage/{}/{}/delete-commit", project_info.project.owner, project_info.project.repo_path())) method="post" data-loading="Deleting..." {
                         label {
                             "Delete reports"
                             fieldset role="group" {
@@ -572,6 +1311,10 @@ async fn render_manage_project(
                             small { "Delete all reports for a specific commit. Must be the full 40-character SHA." }
                         }
                     }
+                    h4 { "Ingest history" }
+                    (job_runs_table(&project_info.project, &job_runs))
+                    h4 { "Audit log" }
+                    (audit_log_table(&audit_log, false))
                 }
             }
             (ctx.footer(Some(current_user)))
@@ -580,6 +1323,80 @@ async fn render_manage_project(
     Ok((ctx, rendered).into_response())
 }
 
+/// Processes an uploaded image and stores it with its variants. The inner error is suitable for
+/// displaying to the user.
+pub(crate) async fn upload_image(
+    state: &AppState,
+    data: Bytes,
+    specs: &'static [VariantSpec],
+) -> Result<Result<ImageId, anyhow::Error>> {
+    let processed = match tokio::task::spawn_blocking(move || process_upload(&data, specs))
+        .await
+        .context("Failed to join image processing task")?
+    {
+        Ok(processed) => processed,
+        Err(e) => return Ok(Err(e)),
+    };
+    let original = &processed.original;
+    let id = state
+        .db
+        .create_image(
+            original.format.to_mime_type(),
+            original.width,
+            original.height,
+            &original.data,
+        )
+        .await?;
+    state.db.create_image_variants(id, &processed.variants).await?;
+    Ok(Ok(id))
+}
+
+/// Generates variants for images stored before they were pre-generated. Images that fail to
+/// process are skipped, so that they don't block the rest.
+pub(crate) async fn backfill_image_variants(state: &AppState) -> Result<()> {
+    let mut header_ids = BTreeSet::new();
+    for spec in HEADER_VARIANTS {
+        header_ids.extend(state.db.get_header_images_missing_variant(spec.name).await?);
+    }
+    let mut icon_ids = BTreeSet::new();
+    for spec in ICON_VARIANTS {
+        icon_ids.extend(state.db.get_platform_icons_missing_variant(spec.name).await?);
+    }
+    let mut generated = 0;
+    for (id, specs) in header_ids
+        .into_iter()
+        .map(|id| (id, HEADER_VARIANTS))
+        .chain(icon_ids.into_iter().map(|id| (id, ICON_VARIANTS)))
+    {
+        let Some((_, _, _, data)) = state.db.get_image(id).await? else {
+            continue;
+        };
+        let variants = match tokio::task::spawn_blocking(move || generate_variants(&data, specs))
+            .await
+            .context("Failed to join image processing task")?
+        {
+            Ok(variants) => variants,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to generate variants for image {}: {:?}",
+                    hex::encode(id),
+                    e
+                );
+                continue;
+            }
+        };
+        if let Err(e) = state.db.create_image_variants(id, &variants).await {
+            tracing::warn!("Failed to store variants for image {}: {:?}", hex::encode(id), e);
+            continue;
+        }
+        generated += 1;
+    }
+    if generated > 0 {
+        tracing::info!("Generated variants for {} images", generated);
+    }
+    Ok(())
+}
+
 #[derive(Debug, TryFromMultipart)]
 pub struct ProjectForm {
     pub name: String,
@@ -587,11 +1404,15 @@ pub struct ProjectForm {
     pub platform: String,
     pub default_version: Option<String>,
     pub workflow_id: String,
+    pub artifact_prefix: Option<String>,
+    pub version_pattern: Option<String>,
     pub enable_pr_comments: Option<String>,
     pub pr_report_style: Option<String>,
     pub header_image: Option<Bytes>,
     pub clear_header_image: Option<String>,
     pub enabled: Option<String>,
+    pub tags: Option<String>,
+    pub groups: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -604,50 +1425,69 @@ pub async fn manage_project_save(
     Path(params): Path<ProjectParams>,
     State(state): State<AppState>,
     current_user: CurrentUser,
+    session: Session,
     TypedMultipart(form): TypedMultipart<ProjectForm>,
 ) -> Result<Response, AppError> {
     let Some(project_info) = state.db.get_project_info(&params.owner, &params.repo, None).await?
     else {
         return Err(AppError::Status(StatusCode::NOT_FOUND));
     };
-    if !current_user.can_manage_repo(project_info.project.id) {
+    if !current_user.can_manage_project(&project_info.project) {
         return Err(AppError::Status(StatusCode::FORBIDDEN));
     }
-    if !ALL_PLATFORMS.iter().any(|p| p.to_str() == form.platform) {
+    let platforms = state.db.get_platforms().await?;
+    let Some(platform) = platforms.iter().find(|p| p.id.eq_ignore_ascii_case(form.platform.trim()))
+    else {
         return Err(AppError::Status(StatusCode::BAD_REQUEST));
     };
 
+    let before = project_info.project.clone();
     let mut header_image_id = project_info.project.header_image_id;
     if let Some(header_image) = form.header_image.filter(|b| !b.is_empty()) {
-        let format = image::guess_format(&header_image)?;
-        let (width, height) =
-            image::ImageReader::with_format(Cursor::new(&header_image[..]), format)
-                .into_dimensions()?;
-        let id = state.db.create_image(format.to_mime_type(), width, height, &header_image).await?;
-        header_image_id = Some(id);
+        match upload_image(&state, header_image, HEADER_VARIANTS).await? {
+            Ok(id) => header_image_id = Some(id),
+            Err(e) => {
+                let message = Message::Error(format!("Failed to process hero image: {e}"));
+                session
+                    .insert(&format!("manage_{}_message", project_info.project.id), message)
+                    .await?;
+                let redirect_url = format!("/manage/{}/{}", params.owner, params.repo);
+                return Ok(Redirect::to(&redirect_url).into_response());
+            }
+        }
     } else if form.clear_header_image.is_some_and(|v| v == "on") {
         header_image_id = None;
     }
+    let version_pattern = optional_field(form.version_pattern.as_deref());
+    if let Some(Err(e)) = version_pattern.as_deref().map(version_regex) {
+        let message = Message::Error(format!("Invalid version pattern: {e}"));
+        session.insert(&format!("manage_{}_message", project_info.project.id), message).await?;
+        let redirect_url = format!("/manage/{}/{}", params.owner, params.repo);
+        return Ok(Redirect::to(&redirect_url).into_response());
+    }
 
     let installation_id = if let Some(installations) = &state.github.installations {
         let installations = installations.lock().await;
-        installations.repo_to_installation.get(&project_info.project.id).cloned()
+        installations.repo_to_installation.get(&project_info.project.repository_id).cloned()
     } else {
         None
     };
     let name = form.name.trim();
     let short_name = form.short_name.trim();
-    let platform = form.platform.trim();
     let workflow_id = form.workflow_id.trim();
     let project = Project {
         id: project_info.project.id,
+        repository_id: project_info.project.repository_id,
         owner: project_info.project.owner,
         repo: project_info.project.repo,
+        slug: project_info.project.slug,
+        artifact_prefix: optional_field(form.artifact_prefix.as_deref()),
+        version_pattern,
         name: (!name.is_empty()).then_some(name.to_string()),
         short_name: (!short_name.is_empty()).then_some(short_name.to_string()),
         default_category: project_info.project.default_category,
         default_version: form.default_version,
-        platform: (!platform.is_empty()).then_some(platform.to_string()),
+        platform: Some(platform.id.clone()),
         workflow_id: (!workflow_id.is_empty()).then_some(workflow_id.to_string()),
         // If there's no installation ID, use the existing value
         enable_pr_comments: if installation_id.is_some() {
@@ -663,7 +1503,47 @@ pub async fn manage_project_save(
         header_image_id,
         enabled: form.enabled.is_some_and(|v| v == "on"),
     };
-    state.db.update_project(&project).await?;
+    let mut changes = Project::diff(Some(&before), &project);
+
+    let tags = parse_tags(form.tags.as_deref().unwrap_or_default());
+    let tags_changed = tags != project_info.tags;
+    if tags_changed {
+        changes.push(FieldChange {
+            field: "tags".to_string(),
+            before: Some(project_info.tags.join(", ")).filter(|s| !s.is_empty()),
+            after: Some(tags.join(", ")).filter(|s| !s.is_empty()),
+        });
+    }
+    let groups = state.db.get_project_groups().await?;
+    let before_groups = groups
+        .iter()
+        .filter(|g| g.project_ids.contains(&project.id))
+        .map(|g| g.id.clone())
+        .collect::<Vec<_>>();
+    let after_groups = groups
+        .iter()
+        .filter(|g| form.groups.iter().any(|id| g.id.eq_ignore_ascii_case(id)))
+        .map(|g| g.id.clone())
+        .collect::<Vec<_>>();
+    let groups_changed = before_groups != after_groups;
+    if groups_changed {
+        changes.push(FieldChange {
+            field: "groups".to_string(),
+            before: Some(before_groups.join(", ")).filter(|s| !s.is_empty()),
+            after: Some(after_groups.join(", ")).filter(|s| !s.is_empty()),
+        });
+    }
+    state
+        .db
+        .update_project(
+            &project,
+            tags_changed.then_some(tags.as_slice()),
+            groups_changed.then_some(after_groups.as_slice()),
+            current_user.data.id,
+            &current_user.data.login,
+            &changes,
+        )
+        .await?;
     let redirect_url = format!("/{}/{}", params.owner, params.repo);
     Ok(Redirect::to(&redirect_url).into_response())
 }
@@ -677,16 +1557,29 @@ pub async fn manage_project_refresh(
     let Some(info) = state.db.get_project_info(&params.owner, &params.repo, None).await? else {
         return Err(AppError::Status(StatusCode::NOT_FOUND));
     };
-    if !current_user.can_manage_repo(info.project.id) {
+    if !current_user.can_manage_project(&info.project) {
         return Err(AppError::Status(StatusCode::FORBIDDEN));
     }
 
     // Enqueue a refresh job instead of processing synchronously
-    let job = RefreshProjectJob { repository_id: info.project.id, full_refresh: true };
+    let job = RefreshProjectJob::new(info.project.id, true);
 
     let mut storage = state.jobs.refresh_project();
     let message = match storage.push(job).await {
-        Ok(()) => Message::Info("Refresh job queued. Reports will be updated shortly.".to_string()),
+        Ok(()) => {
+            state
+                .db
+                .create_audit_log(
+                    current_user.data.id,
+                    &current_user.data.login,
+                    info.project.id,
+                    AuditAction::RefreshProject,
+                    &[],
+                    None,
+                )
+                .await?;
+            Message::Info("Refresh job queued. Reports will be updated shortly.".to_string())
+        }
         Err(e) => {
             tracing::error!("Failed to enqueue refresh job: {:?}", e);
             Message::Error(format!("Failed to queue refresh: {e}"))
@@ -713,12 +1606,24 @@ pub async fn delete_commit(
     let Some(info) = state.db.get_project_info(&params.owner, &params.repo, None).await? else {
         return Err(AppError::Status(StatusCode::NOT_FOUND));
     };
-    if !current_user.can_manage_repo(info.project.id) {
+    if !current_user.can_manage_project(&info.project) {
         return Err(AppError::Status(StatusCode::FORBIDDEN));
     }
     let num_reports_deleted =
         state.db.delete_reports_by_commit(info.project.id, &form.commit_sha).await?;
     let message = if num_reports_deleted > 0 {
+        let details = format!("{} ({num_reports_deleted} reports)", form.commit_sha);
+        state
+            .db
+            .create_audit_log(
+                current_user.data.id,
+                &current_user.data.login,
+                info.project.id,
+                AuditAction::DeleteCommit,
+                &[],
+                Some(&details),
+            )
+            .await?;
         Message::Info(format!("Deleted {num_reports_deleted} reports"))
     } else {
         Message::Error("No reports found. Is the commit SHA correct?".to_string())
@@ -727,3 +1632,393 @@ pub async fn delete_commit(
     let redirect_url = format!("/manage/{}/{}", params.owner, params.repo);
     Ok(Redirect::to(&redirect_url).into_response())
 }
+
+#[derive(Deserialize)]
+pub struct AddMaintainerForm {
+    login: String,
+    role: String,
+}
+
+pub async fn add_maintainer(
+    Path(params): Path<ProjectParams>,
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<AddMaintainerForm>,
+) -> Result<Response, AppError> {
+    let Some(info) = state.db.get_project_info(&params.owner, &params.repo, None).await? else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    if !current_user.can_manage_maintainers(&info.project) {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let Ok(role) = form.role.parse::<MaintainerRole>() else {
+        return Err(AppError::Status(StatusCode::BAD_REQUEST));
+    };
+    let login = form.login.trim().trim_start_matches('@');
+    let client = current_user.client(&state.config.github)?;
+    let message = match client.users(login).profile().await {
+        Ok(profile) => {
+            let user_id = profile.id.into_inner();
+            let previous_role = state
+                .db
+                .get_project_maintainers(info.project.id)
+                .await?
+                .into_iter()
+                .find(|m| m.user_id == user_id)
+                .map(|m| m.role);
+            state
+                .db
+                .upsert_project_maintainer(info.project.id, user_id, &profile.login, role)
+                .await?;
+            state
+                .db
+                .create_audit_log(
+                    current_user.data.id,
+                    &current_user.data.login,
+                    info.project.id,
+                    AuditAction::AddMaintainer,
+                    &[FieldChange {
+                        field: "role".to_string(),
+                        before: previous_role.map(|r| r.as_str().to_string()),
+                        after: Some(role.as_str().to_string()),
+                    }],
+                    Some(&profile.login),
+                )
+                .await?;
+            Message::Info(format!("Added {} as {}", profile.login, role))
+        }
+        Err(e) => {
+            tracing::warn!("Failed to fetch GitHub user {}: {:?}", login, e);
+            Message::Error(format!("GitHub user \"{login}\" not found"))
+        }
+    };
+    session.insert(&format!("manage_{}_message", info.project.id), message).await?;
+    let redirect_url = format!("/manage/{}/{}", params.owner, params.repo);
+    Ok(Redirect::to(&redirect_url).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct RemoveMaintainerForm {
+    user_id: u64,
+}
+
+pub async fn remove_maintainer(
+    Path(params): Path<ProjectParams>,
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<RemoveMaintainerForm>,
+) -> Result<Response, AppError> {
+    let Some(info) = state.db.get_project_info(&params.owner, &params.repo, None).await? else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    if !current_user.can_manage_maintainers(&info.project) {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let maintainer = state
+        .db
+        .get_project_maintainers(info.project.id)
+        .await?
+        .into_iter()
+        .find(|m| m.user_id == form.user_id);
+    let message = match maintainer {
+        Some(maintainer)
+            if state.db.delete_project_maintainer(info.project.id, form.user_id).await? =>
+        {
+            state
+                .db
+                .create_audit_log(
+                    current_user.data.id,
+                    &current_user.data.login,
+                    info.project.id,
+                    AuditAction::RemoveMaintainer,
+                    &[FieldChange {
+                        field: "role".to_string(),
+                        before: Some(maintainer.role.as_str().to_string()),
+                        after: None,
+                    }],
+                    Some(&maintainer.user_login),
+                )
+                .await?;
+            Message::Info(format!("Removed {}", maintainer.user_login))
+        }
+        _ => Message::Error("Maintainer not found".to_string()),
+    };
+    session.insert(&format!("manage_{}_message", info.project.id), message).await?;
+    let redirect_url = format!("/manage/{}/{}", params.owner, params.repo);
+    Ok(Redirect::to(&redirect_url).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct AddMilestoneForm {
+    name: String,
+    version: String,
+    measure: String,
+    target: f32,
+    category: String,
+    unit_prefix: String,
+    due_date: String,
+}
+
+pub async fn add_milestone(
+    Path(params): Path<ProjectParams>,
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<AddMilestoneForm>,
+) -> Result<Response, AppError> {
+    let Some(info) = state.db.get_project_info(&params.owner, &params.repo, None).await? else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    if !current_user.can_manage_project(&info.project) {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let Ok(measure) = form.measure.parse::<MilestoneMeasure>() else {
+        return Err(AppError::Status(StatusCode::BAD_REQUEST));
+    };
+    let Some(version) = info.report_versions.iter().find(|v| v.eq_ignore_ascii_case(&form.version))
+    else {
+        return Err(AppError::Status(StatusCode::BAD_REQUEST));
+    };
+    let name = form.name.trim();
+    // A target of 0% would be reached immediately and can't be shown as progress
+    if name.is_empty() || !(form.target > 0.0 && form.target <= 100.0) {
+        return Err(AppError::Status(StatusCode::BAD_REQUEST));
+    }
+    let due_date = match form.due_date.trim() {
+        "" => None,
+        s => match Date::parse(s, format_description!("[year]-[month]-[day]")) {
+            Ok(date) => Some(date.midnight().as_utc()),
+            Err(_) => return Err(AppError::Status(StatusCode::BAD_REQUEST)),
+        },
+    };
+    let category = form.category.trim();
+    let unit_prefix = form.unit_prefix.trim();
+    let mut milestone = Milestone {
+        id: 0,
+        project_id: info.project.id,
+        name: name.to_string(),
+        version: version.clone(),
+        measure,
+        target: form.target,
+        category: (!category.is_empty()).then(|| category.to_string()),
+        unit_prefix: (!unit_prefix.is_empty()).then(|| unit_prefix.to_string()),
+        due_date,
+        achieved_at: None,
+        achieved_commit: None,
+        created_at: UtcDateTime::now(),
+    };
+    milestone.id = state.db.create_milestone(&milestone).await?;
+    state
+        .db
+        .create_audit_log(
+            current_user.data.id,
+            &current_user.data.login,
+            info.project.id,
+            AuditAction::AddMilestone,
+            &[FieldChange {
+                field: "target".to_string(),
+                before: None,
+                after: Some(format!("{} {}", format_percent(milestone.target), milestone.measure)),
+            }],
+            Some(&milestone.name),
+        )
+        .await?;
+    // The latest report may already meet the target
+    let achieved = if let Some(commit) = &info.commit {
+        state.db.check_milestones(info.project.id, commit, &milestone.version).await?
+    } else {
+        vec![]
+    };
+    let message = if achieved.iter().any(|m| m.id == milestone.id) {
+        Message::Info(format!(
+            "Added {}, which the latest report has already achieved",
+            milestone.name
+        ))
+    } else {
+        Message::Info(format!("Added {}", milestone.name))
+    };
+    session.insert(&format!("manage_{}_message", info.project.id), message).await?;
+    let redirect_url = format!("/manage/{}/{}", params.owner, params.repo);
+    Ok(Redirect::to(&redirect_url).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct RemoveMilestoneForm {
+    id: u64,
+}
+
+pub async fn remove_milestone(
+    Path(params): Path<ProjectParams>,
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<RemoveMilestoneForm>,
+) -> Result<Response, AppError> {
+    let Some(info) = state.db.get_project_info(&params.owner, &params.repo, None).await? else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    if !current_user.can_manage_project(&info.project) {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let milestone =
+        state.db.get_milestones(info.project.id, None).await?.into_iter().find(|m| m.id == form.id);
+    let message = match milestone {
+        Some(milestone) if state.db.delete_milestone(info.project.id, form.id).await? => {
+            state
+                .db
+                .create_audit_log(
+                    current_user.data.id,
+                    &current_user.data.login,
+                    info.project.id,
+                    AuditAction::RemoveMilestone,
+                    &[],
+                    Some(&milestone.name),
+                )
+                .await?;
+            Message::Info(format!("Removed {}", milestone.name))
+        }
+        _ => Message::Error("Milestone not found".to_string()),
+    };
+    session.insert(&format!("manage_{}_message", info.project.id), message).await?;
+    let redirect_url = format!("/manage/{}/{}", params.owner, params.repo);
+    Ok(Redirect::to(&redirect_url).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct SaveCategoryForm {
+    id: String,
+    name: String,
+    rules: String,
+}
+
+pub async fn save_category(
+    Path(params): Path<ProjectParams>,
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<SaveCategoryForm>,
+) -> Result<Response, AppError> {
+    let Some(info) = state.db.get_project_info(&params.owner, &params.repo, None).await? else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    if !current_user.can_manage_project(&info.project) {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let id = form.id.trim();
+    let name = form.name.trim();
+    if id.is_empty()
+        || name.is_empty()
+        || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(AppError::Status(StatusCode::BAD_REQUEST));
+    }
+    let category = CustomCategory {
+        project_id: info.project.id,
+        id: id.to_string(),
+        name: name.to_string(),
+        rules: form
+            .rules
+            .lines()
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty())
+            .map(str::to_string)
+            .collect(),
+        created_at: UtcDateTime::now(),
+    };
+    let message = if category.rules.is_empty() {
+        Message::Error("At least one rule is required".to_string())
+    } else if let Err(e) = category.matcher() {
+        Message::Error(format!("Invalid rule: {e}"))
+    } else {
+        state.db.upsert_project_category(&category).await?;
+        state
+            .db
+            .create_audit_log(
+                current_user.data.id,
+                &current_user.data.login,
+                info.project.id,
+                AuditAction::AddCategory,
+                &[FieldChange {
+                    field: "rules".to_string(),
+                    before: None,
+                    after: Some(category.rules.join(", ")),
+                }],
+                Some(&category.id),
+            )
+            .await?;
+        let db = state.db.clone();
+        let project_id = info.project.id;
+        tokio::spawn(async move {
+            if let Err(e) = db.rebuild_custom_categories(project_id).await {
+                tracing::error!("Failed to compute categories for {}: {:?}", project_id, e);
+            }
+        });
+        Message::Info(format!(
+            "Saved {}. Measures for existing reports are being computed.",
+            category.name
+        ))
+    };
+    session.insert(&format!("manage_{}_message", info.project.id), message).await?;
+    let redirect_url = format!("/manage/{}/{}", params.owner, params.repo);
+    Ok(Redirect::to(&redirect_url).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct RemoveCategoryForm {
+    id: String,
+}
+
+pub async fn remove_category(
+    Path(params): Path<ProjectParams>,
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    session: Session,
+    Form(form): Form<RemoveCategoryForm>,
+) -> Result<Response, AppError> {
+    let Some(info) = state.db.get_project_info(&params.owner, &params.repo, None).await? else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    if !current_user.can_manage_project(&info.project) {
+        return Err(AppError::Status(StatusCode::FORBIDDEN));
+    }
+    let message = if state.db.delete_project_category(info.project.id, &form.id).await? {
+        state
+            .db
+            .create_audit_log(
+                current_user.data.id,
+                &current_user.data.login,
+                info.project.id,
+                AuditAction::RemoveCategory,
+                &[],
+                Some(&form.id),
+            )
+            .await?;
+        Message::Info(format!("Removed {}", form.id))
+    } else {
+        Message::Error("Category not found".to_string())
+    };
+    session.insert(&format!("manage_{}_message", info.project.id), message).await?;
+    let redirect_url = format!("/manage/{}/{}", params.owner, params.repo);
+    Ok(Redirect::to(&redirect_url).into_response())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags() {
+        assert!(parse_tags("").is_empty());
+        assert!(parse_tags(" , ,").is_empty());
+        assert_eq!(parse_tags("zelda, PC port,matching"), ["matching", "PC port", "zelda"]);
+        // The first spelling of a duplicate is kept
+        assert_eq!(parse_tags("Zelda, zelda, ZELDA"), ["Zelda"]);
+        assert_eq!(parse_tags(&format!("ok, {}", "x".repeat(MAX_TAG_LEN + 1))), ["ok"]);
+        let many = (0..MAX_TAGS + 5).map(|i| format!("tag{i:02}")).collect::<Vec<_>>().join(",");
+        let tags = parse_tags(&many);
+        assert_eq!(tags.len(), MAX_TAGS);
+        assert_eq!(tags.first().map(String::as_str), Some("tag00"));
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use decomp_dev_db::CacheStats;
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+
+use crate::AppState;
+
+const DURATION_BUCKETS: &[f64] =
+    &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0];
+
+/// Installs the global metrics recorder. The returned handle renders the Prometheus output.
+pub fn install_recorder() -> Result<PrometheusHandle, BuildError> {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), DURATION_BUCKETS)?
+        .install_recorder()
+}
+
+/// Records the count and latency of requests for each route.
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let method = request.method().to_string();
+    let response = next.run(request).await;
+    let labels =
+        [("method", method), ("route", route), ("status", response.status().as_u16().to_string())];
+    metrics::counter!("http_requests_total", &labels).increment(1);
+    metrics::histogram!("http_request_duration_seconds", &labels)
+        .record(start.elapsed().as_secs_f64());
+    response
+}
+
+/// Renders the Prometheus metrics on the public server, for requests bearing the configured
+/// `metrics_token`. Responds as if the route doesn't exist when no token is configured.
+pub async fn get_public_metrics(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let Some(token) = state.config.server.metrics_token.as_deref() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let authorized = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|v| v == token);
+    if !authorized {
+        return (StatusCode::UNAUTHORIZED, [(header::WWW_AUTHENTICATE, "Bearer")]).into_response();
+    }
+    get_metrics(State(state)).await
+}
+
+/// Renders the Prometheus metrics. The jobs server, which is bound to localhost, serves this
+/// without authentication.
+pub async fn get_metrics(State(state): State<AppState>) -> Response {
+    let (report_cache, report_unit_cache) = state.db.cache_stats();
+    record_cache("report", &report_cache);
+    record_cache("report_unit", &report_unit_cache);
+
+    metrics::gauge!("db_pool_connections").set(state.db.pool.size() as f64);
+    metrics::gauge!("db_pool_idle_connections").set(state.db.pool.num_idle() as f64);
+
+    for (key, budget) in state.github.rate_limits.snapshot() {
+        let key = key.to_string();
+        metrics::gauge!("github_rate_limit", "key" => key.clone()).set(budget.limit as f64);
+        metrics::gauge!("github_rate_limit_remaining", "key" => key.clone())
+            .set(budget.available() as f64);
+        metrics::gauge!("github_rate_limit_used", "key" => key).set(budget.used as f64);
+    }
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")], state.metrics.render())
+        .into_response()
+}
+
+fn record_cache(cache: &'static str, stats: &CacheStats) {
+    metrics::gauge!("db_cache_entries", "cache" => cache).set(stats.entries as f64);
+    metrics::gauge!("db_cache_weighted_size", "cache" => cache).set(stats.weighted_size as f64);
+    metrics::counter!("db_cache_hits_total", "cache" => cache).absolute(stats.hits);
+    metrics::counter!("db_cache_misses_total", "cache" => cache).absolute(stats.misses);
+    if let Some(hit_rate) = stats.hit_rate() {
+        metrics::gauge!("db_cache_hit_ratio", "cache" => cache).set(hit_rate);
+    }
+}
@@ -4,6 +4,7 @@ use axum::{
     Router,
     extract::{DefaultBodyLimit, Request},
     http::{HeaderMap, HeaderValue, header, header::Entry},
+    middleware,
     response::Response,
     routing::{get, post},
 };
@@ -14,17 +15,22 @@ use tower_http::services::ServeDir;
 
 use crate::AppState;
 
+mod admin;
 mod api;
 mod auth;
 mod common;
 pub mod csp;
 mod images;
 mod manage;
+pub mod metrics;
 mod project;
 mod report;
+mod search;
 mod treemap;
 mod webhook;
 
+pub(crate) use manage::backfill_image_variants;
+
 pub fn build_router() -> Router<AppState> {
     Router::<AppState>::new()
         .nest_service(
@@ -55,12 +61,28 @@ pub fn build_router() -> Router<AppState> {
             },
         ))
         .route("/robots.txt", get(common::get_robots))
+        .route("/metrics", get(metrics::get_public_metrics))
         .route("/api", get(api::overview))
+        .nest("/api/v1", api::v1::router())
+        .route("/api/graphql", get(api::graphql::graphql).post(api::graphql::graphql))
+        .route("/api/graphql/schema", get(api::graphql::graphql_schema))
         .route("/api/github/webhook", post(webhook::webhook))
         .route("/api/github/oauth", get(decomp_dev_auth::oauth))
         .route("/login", get(auth::login))
         .route("/logout", post(auth::logout))
+        .route("/admin", get(admin::admin))
+        .route("/admin/cleanup", post(admin::admin_cleanup))
+        .route("/admin/refresh", post(admin::admin_refresh))
+        .route(
+            "/admin/platforms",
+            post(admin::admin_save_platform)
+                .layer(DefaultBodyLimit::max(50 * 1000 * 1000 /* 50MB */)),
+        )
+        .route("/admin/platforms/remove", post(admin::admin_remove_platform))
+        .route("/admin/groups", post(admin::admin_save_group))
+        .route("/admin/groups/remove", post(admin::admin_remove_group))
         .route("/manage", get(manage::manage))
+        .route("/manage/audit", get(manage::audit_log))
         .route("/manage/new", get(manage::new))
         .route("/manage/new", post(manage::new_save))
         .route("/manage/{owner}/{repo}", get(manage::manage_project))
@@ -71,15 +93,25 @@ pub fn build_router() -> Router<AppState> {
         )
         .route("/manage/{owner}/{repo}/refresh", post(manage::manage_project_refresh))
         .route("/manage/{owner}/{repo}/delete-commit", post(manage::delete_commit))
+        .route("/manage/{owner}/{repo}/maintainers", post(manage::add_maintainer))
+        .route("/manage/{owner}/{repo}/maintainers/remove", post(manage::remove_maintainer))
+        .route("/manage/{owner}/{repo}/categories", post(manage::save_category))
+        .route("/manage/{owner}/{repo}/categories/remove", post(manage::remove_category))
+        .route("/manage/{owner}/{repo}/milestones", post(manage::add_milestone))
+        .route("/manage/{owner}/{repo}/milestones/remove", post(manage::remove_milestone))
         .route("/og.png", get(decomp_dev_images::get_og))
         .route("/", get(project::get_projects))
         .route("/projects", get(project::get_projects))
         .route("/projects.json", get(project::get_projects))
         .route("/projects/{id}", get(report::get_report))
+        .route("/groups/{id}", get(project::get_group))
+        .route("/search", get(search::search))
+        .route("/search.json", get(search::search))
         .route("/images/{id}", get(images::get_image))
         .route("/{owner}/{repo}", get(report::get_report))
         .route("/{owner}/{repo}/{version}", get(report::get_report))
         .route("/{owner}/{repo}/{version}/{commit}", get(report::get_report))
+        .route_layer(middleware::from_fn(metrics::track_metrics))
 }
 
 /// Adds a charset to the Content-Type header if it is missing and the type is text/*.
@@ -105,6 +137,7 @@ pub fn parse_accept(headers: &HeaderMap, ext: Option<&str>) -> Vec<Mime> {
     if let Some(ext) = ext {
         return match ext.to_ascii_lowercase().as_str() {
             "json" => vec![mime::APPLICATION_JSON],
+            "csv" => vec![mime::TEXT_CSV],
             "binpb" | "proto" => vec![Mime::from_str("application/x-protobuf").unwrap()],
             "svg" => vec![mime::IMAGE_SVG],
             _ => {
@@ -1,17 +1,18 @@
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
 use axum::{
     Json,
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::{HeaderMap, StatusCode, Uri},
     response::{IntoResponse, Response},
 };
 use decomp_dev_auth::CurrentUser;
 use decomp_dev_core::{
     AppError, FullUri,
+    forecast::{Forecast, ForecastWindow},
     models::{
-        ALL_PLATFORMS, CachedReportFile, Commit, Platform, ProjectInfo, ProjectVisibility,
+        CachedReportFile, Commit, Platform, Project, ProjectGroup, ProjectInfo, ProjectVisibility,
         ReportInner, project_visibility,
     },
     util::{UrlExt, format_percent, size},
@@ -27,7 +28,9 @@ use url::Url;
 use crate::{
     AppState,
     handlers::{
-        common::{Load, ProgressSections, TemplateContext, date, nav_links, timeago},
+        common::{
+            Load, ProgressSections, TemplateContext, date, nav_links, platform_icon, timeago,
+        },
         parse_accept,
         report::TemplateMeasures,
     },
@@ -44,6 +47,24 @@ struct ProjectInfoContext {
 pub struct ProjectsQuery {
     sort: Option<String>,
     platform: Option<String>,
+    tag: Option<String>,
+    /// Set by the group page
+    #[serde(skip)]
+    group: Option<String>,
+}
+
+/// A group page's header, with progress aggregated over its projects.
+struct GroupContext {
+    group: ProjectGroup,
+    /// Number of visible projects in the group
+    projects: usize,
+    measures: Measures,
+    code_progress: ProgressSections,
+}
+
+#[derive(Deserialize)]
+pub struct GroupParams {
+    id: String,
 }
 
 #[derive(Serialize, Copy, Clone)]
@@ -62,14 +83,28 @@ const SORT_OPTIONS: &[SortOption] = &[
 
 #[derive(serde::Serialize)]
 pub struct ProjectsResponse {
+    /// The group, if listing a group's projects
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<GroupResponse>,
     pub projects: Vec<ProjectResponse>,
 }
 
+#[derive(serde::Serialize)]
+pub struct GroupResponse {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Combined measures of the group's projects
+    pub measures: TemplateMeasures,
+}
+
 #[derive(serde::Serialize)]
 pub struct ProjectResponse {
     pub id: u64,
     pub owner: String,
     pub repo: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
     pub repo_url: String,
     pub name: Option<String>,
     pub short_name: Option<String>,
@@ -80,6 +115,10 @@ pub struct ProjectResponse {
     pub measures: TemplateMeasures,
     pub report_versions: Vec<String>,
     pub report_categories: Vec<CategoryResponse>,
+    pub tags: Vec<String>,
+    /// Completion forecasts for the selected category. Only included in single project responses.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub forecasts: Vec<ForecastResponse>,
 }
 
 impl ProjectResponse {
@@ -88,6 +127,7 @@ impl ProjectResponse {
             id: info.project.id,
             owner: info.project.owner.clone(),
             repo: info.project.repo.clone(),
+            slug: info.project.slug.clone(),
             repo_url: info.project.repo_url(),
             name: info.project.name.clone(),
             short_name: info.project.short_name.clone(),
@@ -106,6 +146,8 @@ impl ProjectResponse {
                     measures: c.measures.as_ref().map(TemplateMeasures::from).unwrap_or_default(),
                 })
                 .collect(),
+            tags: info.tags.clone(),
+            forecasts: vec![],
         }
     }
 }
@@ -134,7 +176,36 @@ impl From<Commit> for CommitResponse {
     }
 }
 
-fn extract_extension(uri: &Uri) -> Option<String> {
+#[derive(serde::Serialize)]
+pub struct ForecastResponse {
+    pub window: ForecastWindow,
+    pub samples: usize,
+    /// Matched code percentage points per day
+    pub rate_per_day: f64,
+    pub current_percent: f64,
+    pub estimate: Option<String>,
+    pub earliest: Option<String>,
+    pub latest: Option<String>,
+}
+
+impl From<&Forecast> for ForecastResponse {
+    fn from(value: &Forecast) -> Self {
+        let format = |t: Option<UtcDateTime>| {
+            t.map(|t| t.format(&Rfc3339).unwrap_or_else(|_| "[invalid]".to_string()))
+        };
+        Self {
+            window: value.window,
+            samples: value.samples,
+            rate_per_day: value.rate_per_day,
+            current_percent: value.current_percent,
+            estimate: format(value.estimate),
+            earliest: format(value.earliest),
+            latest: format(value.latest),
+        }
+    }
+}
+
+pub(crate) fn extract_extension(uri: &Uri) -> Option<String> {
     let path = uri.path();
     if let Some(pos) = path.rfind('.') {
         let ext = &path[pos + 1..];
@@ -159,16 +230,13 @@ pub async fn get_projects(
         return Err(AppError::Status(StatusCode::NOT_ACCEPTABLE));
     }
 
-    let platforms = query
-        .platform
-        .as_deref()
-        .into_iter()
-        .flat_map(|s| s.split(','))
-        .filter_map(|s| Platform::from_str(s).ok())
-        .sorted()
-        .dedup()
+    let all_platforms = state.db.get_platforms().await?;
+    let selected = query.platform.as_deref().into_iter().flat_map(|s| s.split(',')).collect_vec();
+    let platforms = all_platforms
+        .iter()
+        .filter(|p| selected.iter().any(|s| p.id.eq_ignore_ascii_case(s)))
         .collect::<Vec<_>>();
-    let show_all = platforms.is_empty() || platforms == ALL_PLATFORMS;
+    let show_all = platforms.is_empty() || platforms.len() == all_platforms.len();
 
     let projects = state.db.get_projects().await?;
 
@@ -232,23 +300,41 @@ pub async fn get_projects(
         project_visibility(&c.info.project, Some(&c.measures)) == ProjectVisibility::Visible
     });
 
-    let available_platforms = out
+    let group = match query.group.as_deref() {
+        Some(id) => {
+            let Some(group) = state
+                .db
+                .get_project_groups()
+                .await?
+                .into_iter()
+                .find(|g| g.id.eq_ignore_ascii_case(id))
+            else {
+                return Err(AppError::Status(StatusCode::NOT_FOUND));
+            };
+            out.retain(|c| group.project_ids.contains(&c.info.project.id));
+            let measures = out.iter().map(|c| c.measures).collect::<Measures>();
+            let code_progress = ctx.code_progress_sections(&measures);
+            Some(GroupContext { group, projects: out.len(), measures, code_progress })
+        }
+        None => None,
+    };
+
+    let available_platforms = all_platforms
         .iter()
-        .filter_map(|p| p.info.project.platform.as_deref())
-        .filter_map(|s| Platform::from_str(s).ok())
-        .sorted()
-        .dedup_with_count()
+        .filter_map(|platform| {
+            let n = out.iter().filter(|c| project_has_platform(&c.info.project, platform)).count();
+            (n > 0).then_some((n, platform))
+        })
         .collect::<Vec<_>>();
 
     if !show_all {
-        out.retain(|c| {
-            c.info
-                .project
-                .platform
-                .as_deref()
-                .and_then(|p| Platform::from_str(p).ok())
-                .is_some_and(|p| platforms.contains(&p))
-        });
+        out.retain(|c| platforms.iter().any(|p| project_has_platform(&c.info.project, p)));
+    }
+
+    let available_tags = tag_counts(out.iter().flat_map(|c| &c.info.tags));
+    let current_tag = query.tag.as_deref().filter(|t| !t.is_empty());
+    if let Some(tag) = current_tag {
+        out.retain(|c| c.info.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
     }
 
     let current_sort_key = query.sort.as_deref().unwrap_or("updated");
@@ -299,9 +385,13 @@ pub async fn get_projects(
                 uri,
                 current_sort,
                 current_user.as_ref(),
+                &all_platforms,
                 &available_platforms,
                 &platforms,
                 show_all,
+                &available_tags,
+                current_tag,
+                group,
             )
             .await;
         } else if mime.type_() == mime::APPLICATION && mime.subtype() == mime::JSON {
@@ -315,37 +405,88 @@ pub async fn get_projects(
                     ))
                 })
                 .collect::<Vec<_>>();
-            return Ok(Json(ProjectsResponse { projects }).into_response());
+            let group = group.map(|g| GroupResponse {
+                id: g.group.id,
+                name: g.group.name,
+                description: g.group.description,
+                measures: (&g.measures).into(),
+            });
+            return Ok(Json(ProjectsResponse { group, projects }).into_response());
         }
     }
     Err(AppError::Status(StatusCode::NOT_ACCEPTABLE))
 }
 
+pub async fn get_group(
+    ctx: TemplateContext,
+    state: State<AppState>,
+    Path(params): Path<GroupParams>,
+    Query(mut query): Query<ProjectsQuery>,
+    uri: FullUri,
+    current_user: Option<CurrentUser>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let id = match params.id.rsplit_once('.') {
+        Some((id, "json")) => id.to_string(),
+        _ => params.id,
+    };
+    query.group = Some(id);
+    get_projects(ctx, state, Query(query), uri, current_user, headers).await
+}
+
+/// Counts how many projects use each tag, merging tags that differ only in case. Tags are
+/// sorted case-insensitively.
+fn tag_counts<'a>(tags: impl IntoIterator<Item = &'a String>) -> Vec<(usize, String)> {
+    tags.into_iter()
+        .sorted_by_key(|tag| tag.to_lowercase())
+        .dedup_by_with_count(|a, b| a.eq_ignore_ascii_case(b))
+        .map(|(n, tag)| (n, tag.clone()))
+        .collect()
+}
+
+fn project_has_platform(project: &Project, platform: &Platform) -> bool {
+    project.platform.as_deref().is_some_and(|id| platform.id.eq_ignore_ascii_case(id))
+}
+
 async fn render_project(
     mut ctx: TemplateContext,
     mut out: Vec<ProjectInfoContext>,
     uri: Uri,
     current_sort: SortOption,
     current_user: Option<&CurrentUser>,
-    available_platforms: &[(usize, Platform)],
-    platforms: &[Platform],
+    all_platforms: &[Platform],
+    available_platforms: &[(usize, &Platform)],
+    platforms: &[&Platform],
     show_all: bool,
+    available_tags: &[(usize, String)],
+    current_tag: Option<&str>,
+    mut group: Option<GroupContext>,
 ) -> Result<Response, AppError> {
     let mut combined_styles = ProgressSections { nonce: ctx.nonce.clone(), ..Default::default() };
     for info in &mut out {
         combined_styles.width_classes.append(&mut info.code_progress.width_classes);
     }
+    if let Some(group) = &mut group {
+        combined_styles.width_classes.append(&mut group.code_progress.width_classes);
+    }
 
     let request_url = Url::parse(&uri.to_string()).context("Failed to parse URI")?;
-    let canonical_url = request_url.with_path("/projects");
-    let is_primary_view = show_all && current_sort.key == "updated";
+    let canonical_url = match &group {
+        Some(group) => request_url.with_path(&format!("/groups/{}", group.group.id)),
+        None => request_url.with_path("/projects"),
+    };
+    let is_primary_view = show_all && current_tag.is_none() && current_sort.key == "updated";
+    let title = match &group {
+        Some(group) => format!("{} • decomp.dev", group.group.name),
+        None => "Projects • decomp.dev".to_string(),
+    };
 
     let rendered = html! {
         (DOCTYPE)
         html lang="en" {
             head {
                 meta charset="utf-8";
-                title { "Projects • decomp.dev" }
+                title { (title) }
                 (ctx.header().await)
                 (ctx.chunks("main", Load::Deferred).await)
                 (ctx.chunks("projects", Load::Deferred).await)
@@ -373,14 +514,33 @@ async fn render_project(
                         }
                         (nav_links())
                     }
-                    .title-group {
-                        h3 { "Progress Reports" }
-                        blockquote {
-                            "Matching decompilation projects attempt to write source code (C, C++)"
-                            " that compiles to the same binary as the original."
-                            " All source code is written from scratch."
-                            footer {
-                                a href="https://decomp.wiki/" { "Learn more" }
+                    @if let Some(group) = &group {
+                        .title-group {
+                            h3 { (group.group.name) }
+                            @if let Some(description) = &group.group.description {
+                                p { (description) }
+                            }
+                            h6 {
+                                (format_percent(group.measures.matched_code_percent))
+                                " decompiled across " (group.projects) " projects"
+                                @if group.measures.complete_code_percent > 0.0 {
+                                    " | "
+                                    (format_percent(group.measures.complete_code_percent))
+                                    " fully linked"
+                                }
+                            }
+                            (group.code_progress)
+                        }
+                    } @else {
+                        .title-group {
+                            h3 { "Progress Reports" }
+                            blockquote {
+                                "Matching decompilation projects attempt to write source code (C, C++)"
+                                " that compiles to the same binary as the original."
+                                " All source code is written from scratch."
+                                footer {
+                                    a href="https://decomp.wiki/" { "Learn more" }
+                                }
                             }
                         }
                     }
@@ -392,7 +552,7 @@ async fn render_project(
                                 @if show_all {
                                     "All Platforms"
                                 } @else if platforms.len() == 1 {
-                                    (platforms[0].name())
+                                    (platforms[0].name)
                                 } @else {
                                     (platforms.len()) " Platforms"
                                 }
@@ -406,10 +566,10 @@ async fn render_project(
                                 @for (n, platform) in available_platforms {
                                     li.platform-item {
                                         label {
-                                            input type="checkbox" name="platform" value=(platform.to_str())
+                                            input type="checkbox" name="platform" value=(platform.id)
                                                 checked[show_all || platforms.contains(platform)];
-                                            span.platform-icon.(format!("icon-{}", platform.to_str())) {}
-                                            (platform.name())
+                                            (platform_icon(platform))
+                                            (platform.name)
                                             span.count-badge { (n) }
                                         }
                                         button.secondary { "Only" }
@@ -417,6 +577,30 @@ async fn render_project(
                                 }
                             }
                         }
+                        @if !available_tags.is_empty() {
+                            details.dropdown.tag-dropdown {
+                                summary {
+                                    @if let Some(tag) = current_tag {
+                                        "Tag: " (tag)
+                                    } @else {
+                                        "All Tags"
+                                    }
+                                }
+                                ul {
+                                    li {
+                                        a href=(request_url.query_param("tag", None)) { "All Tags" }
+                                    }
+                                    @for (n, tag) in available_tags {
+                                        li {
+                                            a href=(request_url.query_param("tag", Some(tag))) {
+                                                (tag) " "
+                                                span.count-badge { (n) }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                         details.dropdown {
                             summary { (current_sort.name) }
                             ul {
@@ -432,7 +616,7 @@ async fn render_project(
                     }
                     (combined_styles)
                     @for project in out {
-                        (project_fragment(project, current_sort, &canonical_url))
+                        (project_fragment(project, current_sort, &canonical_url, all_platforms))
                     }
                 }
                 (ctx.footer(current_user))
@@ -446,22 +630,29 @@ fn project_fragment(
     ctx: ProjectInfoContext,
     current_sort: SortOption,
     canonical_url: &Url,
+    platforms: &[Platform],
 ) -> Markup {
     let project = &ctx.info.project;
     let Some(commit) = ctx.report.as_ref().map(|r| r.commit.clone()) else {
         return Markup::default();
     };
-    let mut project_path = canonical_url.with_path(&format!("/{}/{}", project.owner, project.repo));
+    let mut project_path =
+        canonical_url.with_path(&format!("/{}/{}", project.owner, project.repo_path()));
     project_path.set_query(None);
     let commit_url =
         format!("https://github.com/{}/{}/commit/{}", project.owner, project.repo, commit.sha);
     let header_image_id = project.header_image_id.map(hex::encode);
     const HEADER_QUERY: &str = "?w=1024&h=256";
+    const BACKDROP_QUERY: &str = "?w=256&h=64&b=4";
     html! {
         article.project data-platform=[project.platform.as_deref()] {
             a.project-link href=(project_path) aria-label="View project" {}
             @if let Some(header_image_id) = header_image_id {
                 .project-image-container {
+                    picture {
+                        source srcset=(format!("/images/{header_image_id}.webp{BACKDROP_QUERY}")) type="image/webp";
+                        img.project-backdrop src=(format!("/images/{header_image_id}.jpg{BACKDROP_QUERY}")) alt="" loading="lazy";
+                    }
                     picture {
                         source srcset=(format!("/images/{header_image_id}.avif{HEADER_QUERY}")) type="image/avif";
                         source srcset=(format!("/images/{header_image_id}.webp{HEADER_QUERY}")) type="image/webp";
@@ -471,9 +662,8 @@ fn project_fragment(
             }
             .project-header {
                 h3.project-title { (project.name()) }
-                @if let Some(platform) = &project.platform {
-                    @let platform_name = Platform::from_str(platform).map(|p| p.name()).unwrap_or(platform);
-                    span.platform-icon.(format!("icon-{platform}")) title=(platform_name) {}
+                @if let Some(platform) = platforms.iter().find(|p| project_has_platform(project, p)) {
+                    (platform_icon(platform))
                 }
             }
             h6 {
@@ -498,6 +688,30 @@ fn project_fragment(
                 " in commit "
                 a href=(commit_url) target="_blank" { (commit.sha[..7]) }
             }
+            @if !ctx.info.tags.is_empty() {
+                .project-tags {
+                    @for tag in &ctx.info.tags {
+                        a.tag href=(canonical_url.query_param("tag", Some(tag))) { (tag) }
+                    }
+                }
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tag_counts() {
+        let tags =
+            ["Zelda", "matching", "zelda", "PC port", "Matching", "zelda"].map(str::to_string);
+        assert_eq!(tag_counts(&tags), [
+            (2, "matching".to_string()),
+            (1, "PC port".to_string()),
+            (3, "Zelda".to_string()),
+        ]);
+        assert!(tag_counts(&Vec::<String>::new()).is_empty());
+    }
+}
@@ -1,36 +1,51 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    str::FromStr,
+};
 
 use anyhow::{Context, Result};
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode, Uri, header},
+    http::{HeaderMap, HeaderValue, StatusCode, Uri, header},
     response::{IntoResponse, Response},
 };
 use decomp_dev_auth::CurrentUser;
 use decomp_dev_core::{
     AppError, FullUri,
-    models::{FullReportFile, ProjectInfo, ProjectVisibility, project_visibility},
+    forecast::Forecast,
+    models::{
+        CommitDelta, ContributorStats, CustomCategory, FullReportFile, Milestone, ProjectInfo,
+        ProjectVisibility, UnitMatcher, project_visibility,
+    },
     util::{UrlExt, format_percent, size},
 };
-use decomp_dev_images::{
-    badge,
-    treemap::{layout_units, unit_color},
-};
+use decomp_dev_db::ProjectRevision;
+use decomp_dev_github::changes::generate_changes;
+use decomp_dev_images::{animation::render_animation, badge, treemap::layout_units};
 use image::ImageFormat;
 use maud::{DOCTYPE, PreEscaped, html};
 use mime::Mime;
-use objdiff_core::bindings::report::{Measures, ReportCategory, ReportUnit};
+use objdiff_core::bindings::report::{ChangeItemInfo, Measures, ReportCategory, ReportUnit};
 use serde::{Deserialize, Serialize};
-use time::format_description::well_known::Rfc3339;
+use time::{
+    Duration, UtcDateTime, format_description::well_known::Rfc3339, macros::format_description,
+};
+use tokio::sync::{Semaphore, mpsc};
 use url::Url;
 
-use super::{parse_accept, treemap};
+use super::{
+    add_cache_control, parse_accept,
+    treemap::{self, ColorMode, NodeStats},
+};
 use crate::{
     AppState,
     handlers::{
-        common::{Load, TemplateContext, escape_script, nav_links},
-        project::ProjectResponse,
+        common::{Load, TemplateContext, date, escape_script, nav_links, timeago},
+        project::{ForecastResponse, ProjectResponse},
     },
     proto::{PROTOBUF, Protobuf},
 };
@@ -46,6 +61,16 @@ pub struct ReportParams {
 
 const DEFAULT_IMAGE_WIDTH: u32 = 950;
 const DEFAULT_IMAGE_HEIGHT: u32 = 475;
+const DEFAULT_RECENT_COMMITS: u32 = 10;
+const DEFAULT_TIMELAPSE_FRAMES: u32 = 60;
+const MAX_TIMELAPSE_FRAMES: u32 = 240;
+const TIMELAPSE_FRAME_DELAY_MS: u32 = 100;
+const TIMELAPSE_FINAL_DELAY_MS: u32 = 3000;
+const MAX_TIMELAPSE_WIDTH: u32 = 1280;
+const MAX_TIMELAPSE_HEIGHT: u32 = 720;
+
+/// Timelapses render hundreds of reports, so only a few are rendered at once.
+static TIMELAPSE_PERMITS: Semaphore = Semaphore::const_new(2);
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -59,6 +84,21 @@ pub struct ReportQuery {
     #[serde(flatten)]
     shield: badge::ShieldParams,
     unit: Option<String>,
+    path: Option<String>,
+    /// Treemap coloring mode
+    color_by: Option<String>,
+    /// Number of commits to compare against when coloring by recent changes
+    commits: Option<u32>,
+    /// Number of frames in a timelapse
+    frames: Option<u32>,
+    /// Time window in days for contributor stats
+    days: Option<u32>,
+    /// Contributor whose commits to list
+    contributor: Option<String>,
+    /// Milestone ID in milestone mode
+    milestone: Option<u64>,
+    /// Version to compare against in compare mode
+    to: Option<String>,
 }
 
 impl ReportQuery {
@@ -69,15 +109,17 @@ impl ReportQuery {
 
 #[derive(Serialize)]
 pub struct ReportTemplateUnit<'a> {
-    pub name: &'a str,
+    pub name: Cow<'a, str>,
     pub total_code: u64,
     pub fuzzy_match_percent: f32,
     pub color: String,
+    pub outer_color: String,
     pub x: f32,
     pub y: f32,
     pub w: f32,
     pub h: f32,
     pub is_linked: bool,
+    pub is_dir: bool,
 }
 
 #[derive(Serialize, Clone)]
@@ -291,6 +333,7 @@ pub async fn get_report(
     if matches!(commit, Some(c) if c.eq_ignore_ascii_case("latest")) {
         commit = None;
     }
+    let mode = query.mode.as_deref().unwrap_or("overview").to_ascii_lowercase();
     let Some(project_info) = (match (&params.id, &params.owner, &params.repo) {
         (Some(id), _, _) => {
             let id: u64 = id.parse().map_err(|_| AppError::Status(StatusCode::BAD_REQUEST))?;
@@ -301,6 +344,12 @@ pub async fn get_report(
     }) else {
         return Err(AppError::Status(StatusCode::NOT_FOUND));
     };
+    // History and contributors include every report for the version, so they can change even
+    // if the commit is pinned. The latest commit also shows forecasts and milestones, so it's
+    // only pinned once a newer commit exists.
+    let pinned = commit.is_some()
+        && project_info.next_commit.is_some()
+        && !matches!(mode.as_str(), "history" | "contributors");
     let Some(commit) = project_info.commit.as_ref() else {
         return Err(AppError::Status(StatusCode::NOT_FOUND));
     };
@@ -319,19 +368,149 @@ pub async fn get_report(
     else {
         return Err(AppError::Status(StatusCode::NOT_FOUND));
     };
-    let report = state.db.upgrade_report(&report).await?;
 
-    let scope = apply_scope(&report, &project_info, &query)?;
-    match query.mode.as_deref().unwrap_or("overview").to_ascii_lowercase().as_str() {
-        "history" => mode_history(&scope, &state, uri, query, ctx, &acceptable, current_user).await,
-        "measures" => mode_measures(&scope, &acceptable),
+    // HTML output varies with the logged-in user, so only anonymous responses are cacheable
+    let cacheable = current_user.is_none();
+    let revision = state.db.get_project_revision(project_info.project.id).await?;
+    let etag = report_etag(&project_info, &revision, version, &mode, uri.query(), &acceptable);
+    let cache_control = if !cacheable {
+        HeaderValue::from_static("private, no-cache")
+    } else if pinned {
+        HeaderValue::from_static("public, max-age=86400, stale-while-revalidate=604800")
+    } else {
+        HeaderValue::from_static("public, max-age=60, must-revalidate")
+    };
+    if cacheable && etag_matches(&headers, &etag) {
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        response.headers_mut().insert(header::ETAG, etag);
+        response.headers_mut().insert(header::VARY, HeaderValue::from_static(REPORT_VARY));
+        add_cache_control(&mut response, cache_control);
+        return Ok(response);
+    }
+
+    let color_mode = if matches!(mode.as_str(), "overview" | "report") {
+        query
+            .color_by
+            .as_deref()
+            .map(ColorMode::from_str)
+            .transpose()
+            .map_err(|_| AppError::Status(StatusCode::BAD_REQUEST))?
+            .unwrap_or_default()
+    } else {
+        ColorMode::default()
+    };
+    let previous_report = if color_mode == ColorMode::Recent {
+        let count = query.commits.unwrap_or(DEFAULT_RECENT_COMMITS).clamp(1, 1000);
+        match state
+            .db
+            .get_previous_commit(project_info.project.id, version, &commit.sha, count)
+            .await?
+        {
+            Some(sha) => match state.db.get_report(project_info.project.id, &sha, version).await? {
+                Some(previous) => Some(state.db.upgrade_report(&previous).await?),
+                None => None,
+            },
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let report = state.db.upgrade_report(&report).await?;
+    let custom_categories = state.db.get_project_categories(project_info.project.id).await?;
+    let scope = apply_scope(
+        &report,
+        &project_info,
+        &query,
+        color_mode,
+        previous_report.as_ref(),
+        &custom_categories,
+    )?;
+    let mut response = match mode.as_str() {
+        "compare" => {
+            mode_compare(&scope, &state, uri, query, ctx, &acceptable, current_user).await?
+        }
+        "contributors" => {
+            mode_contributors(&scope, &state, uri, query, ctx, &acceptable, current_user).await?
+        }
+        "history" => {
+            mode_history(&scope, &state, uri, query, ctx, &acceptable, current_user).await?
+        }
+        "measures" => mode_measures(&scope, &acceptable)?,
+        "milestone" => mode_milestone(&scope, &state, query, &acceptable).await?,
         "overview" => {
-            mode_overview(&scope, &state, uri, query, ctx, &acceptable, current_user).await
+            mode_overview(&scope, &state, uri, query, ctx, &acceptable, current_user).await?
         }
-        "report" => mode_report(&scope, &state, uri, query, ctx, &acceptable, current_user).await,
-        "shield" => mode_shield(&scope, query, &acceptable),
-        _ => Err(AppError::Status(StatusCode::BAD_REQUEST)),
+        "report" => mode_report(&scope, &state, uri, query, ctx, &acceptable, current_user).await?,
+        "shield" => mode_shield(&scope, query, &acceptable)?,
+        "targets" => mode_targets(&scope, uri, ctx, &acceptable, current_user).await?,
+        "timelapse" => mode_timelapse(&scope, &state, query, &acceptable).await?,
+        _ => return Err(AppError::Status(StatusCode::BAD_REQUEST)),
+    };
+    if cacheable && response.status() == StatusCode::OK {
+        response.headers_mut().insert(header::ETAG, etag);
     }
+    response.headers_mut().insert(header::VARY, HeaderValue::from_static(REPORT_VARY));
+    add_cache_control(&mut response, cache_control);
+    Ok(response)
+}
+
+/// Responses differ for logged-in users, so shared caches must not serve an anonymous
+/// response to a request with a session cookie.
+const REPORT_VARY: &str = "Accept, Cookie";
+
+/// Builds a strong ETag for a report response.
+///
+/// The commit is always resolved to a SHA, so `latest` URLs receive a new ETag as soon as a
+/// new report is available. The next commit is included because the HTML page links to it.
+/// The project revision covers modes that aggregate every report (history, contributors) and
+/// edits to the project's settings.
+fn report_etag(
+    project_info: &ProjectInfo,
+    revision: &ProjectRevision,
+    version: &str,
+    mode: &str,
+    query: Option<&str>,
+    acceptable: &[Mime],
+) -> HeaderValue {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&project_info.project.id.to_le_bytes());
+    let timestamp = |t: Option<UtcDateTime>| t.map_or(0, |t| t.unix_timestamp_nanos());
+    hasher.update(&timestamp(revision.updated_at).to_le_bytes());
+    hasher.update(&revision.last_report_id.unwrap_or_default().to_le_bytes());
+    hasher.update(&timestamp(revision.last_ingested_at).to_le_bytes());
+    hasher.update(&revision.authored_reports.to_le_bytes());
+    hasher.update(&revision.categories_version.to_le_bytes());
+    for value in [
+        project_info.commit.as_ref().map(|c| c.sha.as_str()).unwrap_or_default(),
+        project_info.next_commit.as_deref().unwrap_or_default(),
+        version,
+        mode,
+        query.unwrap_or_default(),
+    ] {
+        hasher.update(value.as_bytes());
+        hasher.update(&[0]);
+    }
+    for mime in acceptable {
+        hasher.update(mime.essence_str().as_bytes());
+        hasher.update(&[0]);
+    }
+    let hash = hasher.finalize();
+    HeaderValue::from_str(&format!("\"{}\"", hex::encode(&hash.as_bytes()[..16]))).unwrap()
+}
+
+/// Checks whether the If-None-Match header matches the given ETag.
+fn etag_matches(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Some(etag) = etag.to_str().ok() else {
+        return false;
+    };
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|s| s.split(','))
+        .map(|s| s.trim())
+        .any(|s| s == "*" || s.strip_prefix("W/").unwrap_or(s) == etag)
 }
 
 async fn mode_overview(
@@ -349,8 +528,16 @@ async fn mode_overview(
         {
             return render_report(scope, state, uri, current_user, ctx).await;
         } else if mime.type_() == mime::APPLICATION && mime.subtype() == mime::JSON {
-            let result =
-                ProjectResponse::new(scope.project_info, scope.measures, &scope.report.report);
+            let mut result =
+                ProjectResponse::new(scope.project_info, &scope.measures, &scope.report.report);
+            // Forecasts are based on the full history, so only include them for the latest commit
+            if scope.project_info.next_commit.is_none() {
+                result.forecasts = load_forecasts(scope, state)
+                    .await?
+                    .iter()
+                    .map(ForecastResponse::from)
+                    .collect();
+            }
             return Ok(Json(result).into_response());
         } else if mime.type_() == mime::IMAGE && mime.subtype() == mime::SVG {
             let (w, h) = query.size();
@@ -413,11 +600,11 @@ async fn mode_report(
 }
 
 fn mode_shield(
-    &Scope { project_info, measures, label, .. }: &Scope<'_>,
+    &Scope { project_info, ref measures, ref label, .. }: &Scope<'_>,
     query: ReportQuery,
     acceptable: &[Mime],
 ) -> Result<Response, AppError> {
-    let label = label.unwrap_or_else(|| project_info.project.short_name());
+    let label = label.as_deref().unwrap_or_else(|| project_info.project.short_name());
     for mime in acceptable {
         if (mime.type_() == mime::STAR && mime.subtype() == mime::STAR)
             || (mime.type_() == mime::IMAGE && mime.subtype() == mime::SVG)
@@ -443,22 +630,253 @@ fn mode_shield(
     Err(AppError::Status(StatusCode::NOT_ACCEPTABLE))
 }
 
+async fn mode_milestone(
+    scope: &Scope<'_>,
+    state: &AppState,
+    query: ReportQuery,
+    acceptable: &[Mime],
+) -> Result<Response, AppError> {
+    let Some(id) = query.milestone else {
+        return Err(AppError::Status(StatusCode::BAD_REQUEST));
+    };
+    let project_id = scope.project_info.project.id;
+    let Some(milestone) =
+        state.db.get_milestones(project_id, None).await?.into_iter().find(|m| m.id == id)
+    else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    let (message, color) = if let Some(achieved_at) = milestone.achieved_at {
+        (format!("achieved {}", short_date(achieved_at)), "success")
+    } else {
+        // The milestone may belong to a different version than the requested report
+        let progress = if milestone.version.eq_ignore_ascii_case(&scope.report.version) {
+            milestone.progress(&scope.report.report)
+        } else {
+            let Some(report) = state
+                .db
+                .get_report(project_id, &scope.report.commit.sha, &milestone.version)
+                .await?
+            else {
+                return Err(AppError::Status(StatusCode::NOT_FOUND));
+            };
+            milestone.progress(&state.db.upgrade_report(&report).await?.report)
+        };
+        let message =
+            format!("{} / {}", format_percent(progress), format_percent(milestone.target));
+        (message, "informational")
+    };
+    let response = badge::render_message(&milestone.name, &message, color, &query.shield);
+    for mime in acceptable {
+        if (mime.type_() == mime::STAR && mime.subtype() == mime::STAR)
+            || (mime.type_() == mime::IMAGE && mime.subtype() == mime::SVG)
+            || (mime.type_() == mime::TEXT && mime.subtype() == mime::HTML)
+        {
+            let data = badge::response_svg(&response)?;
+            return Ok(([(header::CONTENT_TYPE, mime::IMAGE_SVG.as_ref())], data).into_response());
+        } else if mime.type_() == mime::APPLICATION && mime.subtype() == mime::JSON {
+            return Ok(Json(response).into_response());
+        } else if mime.type_() == mime::IMAGE {
+            let format = if mime.subtype() == mime::STAR {
+                // Default to PNG
+                ImageFormat::Png
+            } else {
+                ImageFormat::from_mime_type(mime.essence_str())
+                    .ok_or_else(|| AppError::Status(StatusCode::NOT_ACCEPTABLE))?
+            };
+            let data = badge::response_image(&response, format)?;
+            return Ok(([(header::CONTENT_TYPE, format.to_mime_type())], data).into_response());
+        }
+    }
+    Err(AppError::Status(StatusCode::NOT_ACCEPTABLE))
+}
+
 fn mode_measures(
-    &Scope { measures, .. }: &Scope<'_>,
+    &Scope { ref measures, .. }: &Scope<'_>,
     acceptable: &[Mime],
 ) -> Result<Response, AppError> {
     for mime in acceptable {
         if (mime.type_() == mime::STAR && mime.subtype() == mime::STAR)
             || (mime.type_() == mime::APPLICATION && mime.subtype() == mime::JSON)
         {
-            return Ok(Json(measures).into_response());
+            return Ok(Json(measures.as_ref()).into_response());
         } else if mime.type_() == mime::APPLICATION && mime.subtype() == PROTOBUF {
-            return Ok(Protobuf(measures).into_response());
+            return Ok(Protobuf(measures.as_ref()).into_response());
+        }
+    }
+    Err(AppError::Status(StatusCode::NOT_ACCEPTABLE))
+}
+
+/// Maximum number of functions listed on the version comparison page.
+const MAX_COMPARE_ROWS: usize = 1000;
+
+/// How a function differs between two versions at the same commit.
+#[derive(Serialize, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum FunctionComparison {
+    MatchedInBase,
+    MatchedInOther,
+    OnlyInBase,
+    OnlyInOther,
+    Different,
+}
+
+impl FunctionComparison {
+    /// Classifies a changed function from its state in the base and other version.
+    fn classify(base: Option<&ChangeItemInfo>, other: Option<&ChangeItemInfo>) -> Self {
+        match (base, other) {
+            (Some(b), Some(o))
+                if b.fuzzy_match_percent >= 100.0 && o.fuzzy_match_percent < 100.0 =>
+            {
+                Self::MatchedInBase
+            }
+            (Some(b), Some(o))
+                if o.fuzzy_match_percent >= 100.0 && b.fuzzy_match_percent < 100.0 =>
+            {
+                Self::MatchedInOther
+            }
+            (Some(_), Some(_)) => Self::Different,
+            (Some(_), None) => Self::OnlyInBase,
+            (None, _) => Self::OnlyInOther,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CompareItem {
+    size: u64,
+    fuzzy_match_percent: f32,
+}
+
+#[derive(Serialize)]
+struct CompareFunction {
+    unit: String,
+    name: String,
+    demangled_name: Option<String>,
+    status: FunctionComparison,
+    base: Option<CompareItem>,
+    other: Option<CompareItem>,
+}
+
+#[derive(Serialize)]
+struct CompareUnit {
+    name: String,
+    base: Option<TemplateMeasures>,
+    other: Option<TemplateMeasures>,
+}
+
+#[derive(Serialize)]
+struct CompareResponse {
+    commit: String,
+    base_version: String,
+    other_version: String,
+    base: TemplateMeasures,
+    other: TemplateMeasures,
+    units: Vec<CompareUnit>,
+    functions: Vec<CompareFunction>,
+}
+
+impl CompareResponse {
+    fn count(&self, status: FunctionComparison) -> usize {
+        self.functions.iter().filter(|f| f.status == status).count()
+    }
+}
+
+/// Compares the report against another version at the same commit, pairing functions the
+/// same way as pull request reports.
+fn compare_versions(base: &FullReportFile, other: &FullReportFile) -> Result<CompareResponse> {
+    let base_report = base.report.flatten();
+    let other_report = other.report.flatten();
+    let changes = generate_changes(&base_report, &other_report)?;
+    let mut units = Vec::with_capacity(changes.units.len());
+    let mut functions = Vec::new();
+    for unit in changes.units {
+        for item in unit.functions {
+            let status = FunctionComparison::classify(item.from.as_ref(), item.to.as_ref());
+            let to_item = |info: &ChangeItemInfo| CompareItem {
+                size: info.size,
+                fuzzy_match_percent: info.fuzzy_match_percent,
+            };
+            functions.push(CompareFunction {
+                unit: unit.name.clone(),
+                demangled_name: item.metadata.as_ref().and_then(|m| m.demangled_name.clone()),
+                name: item.name,
+                status,
+                base: item.from.as_ref().map(to_item),
+                other: item.to.as_ref().map(to_item),
+            });
+        }
+        if unit.from.map(|m| m.matched_code) != unit.to.map(|m| m.matched_code) {
+            units.push(CompareUnit {
+                name: unit.name,
+                base: unit.from.as_ref().map(TemplateMeasures::from),
+                other: unit.to.as_ref().map(TemplateMeasures::from),
+            });
+        }
+    }
+    Ok(CompareResponse {
+        commit: base.commit.sha.clone(),
+        base_version: base.version.clone(),
+        other_version: other.version.clone(),
+        base: TemplateMeasures::from(&base.report.measures),
+        other: TemplateMeasures::from(&other.report.measures),
+        units,
+        functions,
+    })
+}
+
+async fn mode_compare(
+    scope: &Scope<'_>,
+    state: &AppState,
+    uri: Uri,
+    query: ReportQuery,
+    ctx: TemplateContext,
+    acceptable: &[Mime],
+    current_user: Option<CurrentUser>,
+) -> Result<Response, AppError> {
+    let Scope { report, project_info, .. } = scope;
+    let other_version = match query.to.as_deref() {
+        Some(to) => project_info.report_versions.iter().find(|v| v.eq_ignore_ascii_case(to)),
+        None => project_info.report_versions.iter().find(|v| **v != report.version),
+    };
+    let Some(other_version) = other_version.filter(|v| !v.eq_ignore_ascii_case(&report.version))
+    else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    let Some(other) =
+        state.db.get_report(project_info.project.id, &report.commit.sha, other_version).await?
+    else {
+        return Err(AppError::Status(StatusCode::NOT_FOUND));
+    };
+    let other = state.db.upgrade_report(&other).await?;
+    let comparison = compare_versions(report, &other)?;
+    for mime in acceptable {
+        if (mime.type_() == mime::STAR && mime.subtype() == mime::STAR)
+            || (mime.type_() == mime::TEXT && mime.subtype() == mime::HTML)
+        {
+            return render_compare(scope, uri, current_user, ctx, &comparison).await;
+        } else if mime.type_() == mime::APPLICATION && mime.subtype() == mime::JSON {
+            return Ok(Json(comparison).into_response());
         }
     }
     Err(AppError::Status(StatusCode::NOT_ACCEPTABLE))
 }
 
+/// Fits completion forecasts to the matched code history of the current category.
+async fn load_forecasts(scope: &Scope<'_>, state: &AppState) -> Result<Vec<Forecast>> {
+    let category = scope.current_category.map(|c| c.id.as_str());
+    let samples = state
+        .db
+        .get_progress_history(scope.project_info.project.id, &scope.report.version, category)
+        .await?;
+    Ok(Forecast::fit_all(&samples))
+}
+
+fn short_date(value: UtcDateTime) -> String {
+    value
+        .format(format_description!("[year]-[month]-[day]"))
+        .unwrap_or_else(|_| "[invalid]".to_string())
+}
+
 #[derive(Serialize)]
 struct ReportHistoryEntry {
     timestamp: String,
@@ -479,6 +897,7 @@ async fn mode_history(
     let report_measures =
         state.db.fetch_all_reports(&scope.project_info.project, &scope.report.version).await?;
     let mut result = Vec::with_capacity(report_measures.len());
+    let mut samples = Vec::with_capacity(report_measures.len());
     for report in report_measures {
         let mut measures =
             Some(*report.report.measures(scope.project_info.project.default_category.as_deref()));
@@ -496,13 +915,14 @@ async fn mode_history(
                 .report
                 .categories
                 .iter()
-                .find(|c| &c.id == category_id)
+                .find(|c| c.id.eq_ignore_ascii_case(category_id))
                 .and_then(|c| c.measures.as_ref())
                 .copied();
         }
         let Some(measures) = &measures else {
             continue;
         };
+        samples.push((report.commit.timestamp, measures.matched_code_percent));
         result.push(ReportHistoryEntry {
             timestamp: report
                 .commit
@@ -518,7 +938,26 @@ async fn mode_history(
         if (mime.type_() == mime::STAR && mime.subtype() == mime::STAR)
             || (mime.type_() == mime::TEXT && mime.subtype() == mime::HTML)
         {
-            let rendered = render_history(scope, state, uri, current_user, ctx, result).await?;
+            let forecasts = Forecast::fit_all(&samples);
+            let mut milestones = state
+                .db
+                .get_milestones(scope.project_info.project.id, Some(&scope.report.version))
+                .await?
+                .into_iter()
+                .filter(|m| m.achieved_at.is_some())
+                .collect::<Vec<_>>();
+            milestones.sort_by_key(|m| m.achieved_at);
+            let rendered = render_history(
+                scope,
+                state,
+                uri,
+                current_user,
+                ctx,
+                result,
+                &forecasts,
+                &milestones,
+            )
+            .await?;
             return Ok(rendered.into_response());
         } else if mime.type_() == mime::APPLICATION && mime.subtype() == mime::JSON {
             return Ok(Json(result).into_response());
@@ -527,6 +966,401 @@ async fn mode_history(
     Err(AppError::Status(StatusCode::NOT_ACCEPTABLE))
 }
 
+/// Maximum number of targets shown on the HTML page. JSON and CSV include all targets.
+const MAX_TARGET_ROWS: usize = 500;
+
+/// An unmatched function, ranked by how much code is left to match.
+#[derive(Serialize)]
+struct TargetFunction<'a> {
+    unit: &'a str,
+    name: &'a str,
+    demangled_name: Option<&'a str>,
+    size: u64,
+    fuzzy_match_percent: f32,
+    /// Estimated bytes of code left to match
+    remaining_code: u64,
+    source_path: Option<&'a str>,
+}
+
+/// Collects the unmatched functions in the current unit or category, closest to matching first.
+fn collect_targets<'a>(scope: &Scope<'a>) -> Vec<TargetFunction<'a>> {
+    let units = match scope.current_unit {
+        Some(unit) => vec![unit],
+        None => scope
+            .report
+            .report
+            .units
+            .iter()
+            .map(|unit| unit.as_ref())
+            .filter(|unit| scope.in_category(unit))
+            .collect(),
+    };
+    rank_targets(units)
+}
+
+/// Collects the unmatched functions of `units`, ordered by remaining code, then by match
+/// percent (highest first) and name.
+fn rank_targets<'a>(units: impl IntoIterator<Item = &'a ReportUnit>) -> Vec<TargetFunction<'a>> {
+    let mut targets = units
+        .into_iter()
+        .flat_map(|unit| {
+            let source_path = unit.metadata.as_ref().and_then(|m| m.source_path.as_deref());
+            unit.functions.iter().filter(|f| f.size > 0 && f.fuzzy_match_percent < 100.0).map(
+                move |f| TargetFunction {
+                    unit: &unit.name,
+                    name: &f.name,
+                    demangled_name: f.metadata.as_ref().and_then(|m| m.demangled_name.as_deref()),
+                    size: f.size,
+                    fuzzy_match_percent: f.fuzzy_match_percent,
+                    remaining_code: (f.size as f32 * (100.0 - f.fuzzy_match_percent) / 100.0).ceil()
+                        as u64,
+                    source_path,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    targets.sort_by(|a, b| {
+        a.remaining_code
+            .cmp(&b.remaining_code)
+            .then_with(|| b.fuzzy_match_percent.total_cmp(&a.fuzzy_match_percent))
+            .then_with(|| a.name.cmp(b.name))
+    });
+    targets
+}
+
+fn targets_csv(targets: &[TargetFunction]) -> String {
+    fn field(value: &str) -> Cow<'_, str> {
+        if value.contains([',', '"', '\n', '\r']) {
+            Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+    let mut out = String::from(
+        "unit,name,demangled_name,size,fuzzy_match_percent,remaining_code,source_path\n",
+    );
+    for target in targets {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            field(target.unit),
+            field(target.name),
+            field(target.demangled_name.unwrap_or_default()),
+            target.size,
+            target.fuzzy_match_percent,
+            target.remaining_code,
+            field(target.source_path.unwrap_or_default()),
+        )
+        .unwrap();
+    }
+    out
+}
+
+async fn mode_targets(
+    scope: &Scope<'_>,
+    uri: Uri,
+    ctx: TemplateContext,
+    acceptable: &[Mime],
+    current_user: Option<CurrentUser>,
+) -> Result<Response, AppError> {
+    let targets = collect_targets(scope);
+    for mime in acceptable {
+        if (mime.type_() == mime::STAR && mime.subtype() == mime::STAR)
+            || (mime.type_() == mime::TEXT && mime.subtype() == mime::HTML)
+        {
+            return render_targets(scope, uri, current_user, ctx, &targets).await;
+        } else if mime.type_() == mime::APPLICATION && mime.subtype() == mime::JSON {
+            return Ok(Json(targets).into_response());
+        } else if mime.type_() == mime::TEXT && mime.subtype() == mime::CSV {
+            return Ok((
+                [(header::CONTENT_TYPE, "text/csv; charset=utf-8")],
+                targets_csv(&targets),
+            )
+                .into_response());
+        }
+    }
+    Err(AppError::Status(StatusCode::NOT_ACCEPTABLE))
+}
+
+/// Maximum number of commits shown in a contributor's history.
+const MAX_CONTRIBUTOR_COMMITS: u32 = 500;
+const CONTRIBUTOR_WINDOWS: &[(Option<u32>, &str)] = &[
+    (None, "All time"),
+    (Some(7), "Last 7 days"),
+    (Some(30), "Last 30 days"),
+    (Some(90), "Last 90 days"),
+    (Some(365), "Last year"),
+];
+
+#[derive(Serialize)]
+struct ContributorResponse {
+    contributor: String,
+    commits: u32,
+    matched_code: i64,
+    matched_data: i64,
+    matched_functions: i64,
+    last_commit: String,
+}
+
+#[derive(Serialize)]
+struct ContributorCommitResponse {
+    sha: String,
+    message: Option<String>,
+    timestamp: String,
+    pr_number: Option<u64>,
+    matched_code: i64,
+    matched_data: i64,
+    matched_functions: i64,
+}
+
+async fn mode_contributors(
+    scope: &Scope<'_>,
+    state: &AppState,
+    uri: Uri,
+    query: ReportQuery,
+    ctx: TemplateContext,
+    acceptable: &[Mime],
+    current_user: Option<CurrentUser>,
+) -> Result<Response, AppError> {
+    let project_id = scope.project_info.project.id;
+    let version = scope.report.version.as_str();
+    let since = match query.days {
+        Some(days) => Some(
+            UtcDateTime::now()
+                .checked_sub(Duration::days(days as i64))
+                .ok_or(AppError::Status(StatusCode::BAD_REQUEST))?,
+        ),
+        None => None,
+    };
+    let stats = state.db.get_contributor_stats(project_id, version, since).await?;
+    let commits = match query.contributor.as_deref() {
+        Some(contributor) => Some(
+            state
+                .db
+                .get_contributor_commits(project_id, version, contributor, MAX_CONTRIBUTOR_COMMITS)
+                .await?,
+        ),
+        None => None,
+    };
+    for mime in acceptable {
+        if (mime.type_() == mime::STAR && mime.subtype() == mime::STAR)
+            || (mime.type_() == mime::TEXT && mime.subtype() == mime::HTML)
+        {
+            return render_contributors(
+                scope,
+                uri,
+                &query,
+                current_user,
+                ctx,
+                &stats,
+                commits.as_deref(),
+            )
+            .await;
+        } else if mime.type_() == mime::APPLICATION && mime.subtype() == mime::JSON {
+            if let Some(commits) = commits {
+                let result = commits
+                    .into_iter()
+                    .map(|delta| ContributorCommitResponse {
+                        timestamp: delta
+                            .commit
+                            .timestamp
+                            .format(&Rfc3339)
+                            .unwrap_or_else(|_| "[invalid]".to_string()),
+                        sha: delta.commit.sha,
+                        message: delta.commit.message,
+                        pr_number: delta.pr_number,
+                        matched_code: delta.matched_code,
+                        matched_data: delta.matched_data,
+                        matched_functions: delta.matched_functions,
+                    })
+                    .collect::<Vec<_>>();
+                return Ok(Json(result).into_response());
+            }
+            let result = stats
+                .into_iter()
+                .map(|s| ContributorResponse {
+                    last_commit: s
+                        .last_commit
+                        .format(&Rfc3339)
+                        .unwrap_or_else(|_| "[invalid]".to_string()),
+                    contributor: s.contributor,
+                    commits: s.commits,
+                    matched_code: s.matched_code,
+                    matched_data: s.matched_data,
+                    matched_functions: s.matched_functions,
+                })
+                .collect::<Vec<_>>();
+            return Ok(Json(result).into_response());
+        }
+    }
+    Err(AppError::Status(StatusCode::NOT_ACCEPTABLE))
+}
+
+/// Formats a size change with an explicit sign.
+fn size_delta(value: i64) -> String {
+    match value.cmp(&0) {
+        Ordering::Greater => format!("+{}", size(value as u64)),
+        Ordering::Less => format!("-{}", size(value.unsigned_abs())),
+        Ordering::Equal => "0".to_string(),
+    }
+}
+
+async fn mode_timelapse(
+    scope: &Scope<'_>,
+    state: &AppState,
+    query: ReportQuery,
+    acceptable: &[Mime],
+) -> Result<Response, AppError> {
+    let format = acceptable
+        .iter()
+        .find_map(|mime| {
+            if mime.type_() == mime::STAR
+                || (mime.type_() == mime::IMAGE && mime.subtype() == mime::STAR)
+            {
+                // Default to WebP
+                Some(ImageFormat::WebP)
+            } else if mime.type_() == mime::IMAGE {
+                ImageFormat::from_mime_type(mime.essence_str())
+                    .filter(|f| matches!(f, ImageFormat::WebP | ImageFormat::Gif))
+            } else {
+                None
+            }
+        })
+        .ok_or(AppError::Status(StatusCode::NOT_ACCEPTABLE))?;
+    let frame_count =
+        query.frames.unwrap_or(DEFAULT_TIMELAPSE_FRAMES).clamp(2, MAX_TIMELAPSE_FRAMES) as usize;
+    let Ok(_permit) = TIMELAPSE_PERMITS.try_acquire() else {
+        return Err(AppError::Status(StatusCode::TOO_MANY_REQUESTS));
+    };
+
+    // Walk reports up to the current commit, oldest first
+    let mut reports =
+        state.db.fetch_all_reports(&scope.project_info.project, &scope.report.version).await?;
+    reports.retain(|r| r.commit.timestamp <= scope.report.commit.timestamp);
+    reports.reverse();
+    let sampled = if reports.len() <= frame_count {
+        reports.iter().collect::<Vec<_>>()
+    } else {
+        (0..frame_count)
+            .map(|i| &reports[i * (reports.len() - 1) / (frame_count - 1)])
+            .collect::<Vec<_>>()
+    };
+
+    // Scale down large sizes, keeping the aspect ratio the layout was computed for
+    let (w, h) = query.size();
+    let (w, h) = (w.max(1), h.max(1));
+    let scale = (MAX_TIMELAPSE_WIDTH as f32 / w as f32)
+        .min(MAX_TIMELAPSE_HEIGHT as f32 / h as f32)
+        .min(1.0);
+    let (w, h) = (((w as f32 * scale) as u32).max(1), ((h as f32 * scale) as u32).max(1));
+    let category_id = scope.current_category.map(|c| c.id.as_str());
+    // Frames are sent to the encoder as they are rendered so that only a few SVGs are held
+    // in memory
+    let (tx, mut rx) = mpsc::channel::<(String, u32)>(2);
+    let encode = tokio::task::spawn_blocking(move || {
+        render_animation(std::iter::from_fn(|| rx.blocking_recv()), format)
+    });
+    let last_index = sampled.len().saturating_sub(1);
+    for (index, report) in sampled.into_iter().enumerate() {
+        let report = state.db.upgrade_report(report).await?;
+        let units = timelapse_frame_units(scope, &report);
+        let caption = if scope.current_unit.is_none() && scope.current_path.is_none() {
+            format!(
+                "{} • {}",
+                report.commit.timestamp.date(),
+                format_percent(report.report.measures(category_id).matched_code_percent)
+            )
+        } else {
+            report.commit.timestamp.date().to_string()
+        };
+        let frame = treemap::render_svg_with_caption(&units, w, h, Some(&caption));
+        let delay =
+            if index == last_index { TIMELAPSE_FINAL_DELAY_MS } else { TIMELAPSE_FRAME_DELAY_MS };
+        if tx.send((frame, delay)).await.is_err() {
+            // The encoder stopped early; its error is returned below
+            break;
+        }
+    }
+    drop(tx);
+    let data = encode.await.context("Failed to join timelapse task")??;
+    Ok(([(header::CONTENT_TYPE, format.to_mime_type())], data).into_response())
+}
+
+/// Recolors the current scope's treemap nodes using an older report. Reusing the current
+/// layout keeps nodes in place across frames; nodes missing from the older report are shown
+/// as unmatched.
+fn timelapse_frame_units<'a>(
+    scope: &'a Scope<'_>,
+    frame: &FullReportFile,
+) -> Vec<ReportTemplateUnit<'a>> {
+    let frame_units = frame
+        .report
+        .units
+        .iter()
+        .map(|unit| (unit.name.as_str(), unit.as_ref()))
+        .collect::<HashMap<_, _>>();
+    let frame_unit = scope.current_unit.and_then(|unit| frame_units.get(unit.name.as_str()));
+    scope
+        .units
+        .iter()
+        .map(|node| {
+            let (fuzzy_match_percent, is_linked) = if scope.current_unit.is_some() {
+                let fuzzy_match_percent = frame_unit
+                    .and_then(|unit| {
+                        unit.functions.iter().find(|f| {
+                            f.metadata.as_ref().and_then(|m| m.demangled_name.as_deref())
+                                == Some(node.name.as_ref())
+                                || f.name == node.name
+                        })
+                    })
+                    .map_or(0.0, |f| f.fuzzy_match_percent);
+                (fuzzy_match_percent, frame_unit.is_some_and(|unit| is_unit_linked(unit)))
+            } else if node.is_dir {
+                let (mut fuzzy_sum, mut total_code, mut is_linked) = (0.0, 0, true);
+                for (name, unit) in &frame_units {
+                    if path_remainder(name, &node.name).is_none() {
+                        continue;
+                    }
+                    if let Some(measures) = &unit.measures {
+                        fuzzy_sum += measures.fuzzy_match_percent * measures.total_code as f32;
+                        total_code += measures.total_code;
+                    }
+                    is_linked &= is_unit_linked(unit);
+                }
+                if total_code == 0 {
+                    (0.0, false)
+                } else {
+                    (fuzzy_sum / total_code as f32, is_linked)
+                }
+            } else {
+                frame_units.get(node.name.as_ref()).map_or((0.0, false), |unit| {
+                    let fuzzy_match_percent =
+                        unit.measures.as_ref().map_or(0.0, |m| m.fuzzy_match_percent);
+                    (fuzzy_match_percent, is_unit_linked(unit))
+                })
+            };
+            let (color, outer_color) = scope.color_mode.colors(&NodeStats {
+                fuzzy_match_percent,
+                is_linked,
+                ..Default::default()
+            });
+            ReportTemplateUnit {
+                name: Cow::Borrowed(node.name.as_ref()),
+                total_code: node.total_code,
+                fuzzy_match_percent,
+                color,
+                outer_color,
+                x: node.x,
+                y: node.y,
+                w: node.w,
+                h: node.h,
+                is_linked,
+                is_dir: node.is_dir,
+            }
+        })
+        .collect()
+}
+
 const EMPTY_MEASURES: Measures = Measures {
     fuzzy_match_percent: 0.0,
     total_code: 0,
@@ -549,28 +1383,124 @@ const EMPTY_MEASURES: Measures = Measures {
 struct Scope<'a> {
     report: &'a FullReportFile,
     project_info: &'a ProjectInfo,
-    measures: &'a Measures,
+    measures: Cow<'a, Measures>,
     current_category: Option<&'a ReportCategory>,
+    /// Rules for the current category, if it's a project category
+    category_matcher: Option<UnitMatcher>,
     current_unit: Option<&'a ReportUnit>,
+    current_path: Option<String>,
+    color_mode: ColorMode,
     units: Vec<ReportTemplateUnit<'a>>,
-    label: Option<&'a str>,
+    label: Option<Cow<'a, str>>,
 }
 
-fn apply_scope<'a>(
-    report: &'a FullReportFile,
-    project_info: &'a ProjectInfo,
-    query: &ReportQuery,
-) -> Result<Scope<'a>> {
-    let mut measures = &report.report.measures;
-    let mut current_category = None;
-    let mut category_id_filter = None;
+impl Scope<'_> {
+    /// Whether the unit belongs to the current category, if any.
+    fn in_category(&self, unit: &ReportUnit) -> bool {
+        let Some(category) = self.current_category else {
+            return true;
+        };
+        match &self.category_matcher {
+            Some(matcher) => matcher.is_match(&unit.name),
+            None => unit
+                .metadata
+                .as_ref()
+                .is_some_and(|m| m.progress_categories.iter().any(|c| *c == category.id)),
+        }
+    }
+}
+
+/// Data needed to color treemap nodes.
+struct ColorContext<'a> {
+    mode: ColorMode,
+    /// Category IDs used by [`ColorMode::Category`]
+    categories: Vec<&'a str>,
+    /// Rules for project categories, which units don't list in their metadata
+    matchers: HashMap<&'a str, UnitMatcher>,
+    /// Units from the previous report used by [`ColorMode::Recent`]
+    previous_units: Option<HashMap<&'a str, &'a ReportUnit>>,
+}
+
+/// Compiles the rules of project categories present in the report, keyed by the report's
+/// category ID. Category IDs are case-insensitive, like in the database. Categories the report
+/// defines itself are matched by unit metadata instead.
+fn category_matchers<'a>(
+    report: &'a FullReportFile,
+    custom_categories: &[CustomCategory],
+) -> HashMap<&'a str, UnitMatcher> {
+    custom_categories
+        .iter()
+        .filter_map(|category| {
+            let report_category = report
+                .report
+                .categories
+                .iter()
+                .find(|c| c.id.eq_ignore_ascii_case(&category.id))?;
+            let defined_by_report = report.report.units.iter().any(|unit| {
+                unit.metadata.as_ref().is_some_and(|m| {
+                    m.progress_categories.iter().any(|id| id.eq_ignore_ascii_case(&category.id))
+                })
+            });
+            if defined_by_report {
+                return None;
+            }
+            Some((report_category.id.as_str(), category.matcher().ok()?))
+        })
+        .collect()
+}
+
+impl ColorContext<'_> {
+    fn category_index(&self, unit: &ReportUnit) -> Option<usize> {
+        self.categories.iter().position(|id| self.in_category(unit, id))
+    }
+
+    fn in_category(&self, unit: &ReportUnit, id: &str) -> bool {
+        match self.matchers.get(id) {
+            Some(matcher) => matcher.is_match(&unit.name),
+            None => unit
+                .metadata
+                .as_ref()
+                .is_some_and(|m| m.progress_categories.iter().any(|c| c == id)),
+        }
+    }
+
+    /// Weighted fuzzy match percent of the given units in the previous report. Units that did
+    /// not exist in the previous report are ignored, and `None` is returned if none of them did.
+    fn previous_fuzzy_match_percent<'u>(
+        &self,
+        units: impl IntoIterator<Item = &'u ReportUnit>,
+    ) -> Option<f32> {
+        let previous_units = self.previous_units.as_ref()?;
+        let (mut fuzzy_sum, mut total_code, mut found) = (0.0, 0, false);
+        for unit in units.into_iter().filter_map(|unit| previous_units.get(unit.name.as_str())) {
+            found = true;
+            if let Some(measures) = &unit.measures {
+                fuzzy_sum += measures.fuzzy_match_percent * measures.total_code as f32;
+                total_code += measures.total_code;
+            }
+        }
+        found.then(|| if total_code == 0 { 0.0 } else { fuzzy_sum / total_code as f32 })
+    }
+}
+
+fn apply_scope<'a>(
+    report: &'a FullReportFile,
+    project_info: &'a ProjectInfo,
+    query: &ReportQuery,
+    color_mode: ColorMode,
+    previous_report: Option<&'a FullReportFile>,
+    custom_categories: &'a [CustomCategory],
+) -> Result<Scope<'a>> {
+    let mut measures = Cow::Borrowed(&report.report.measures);
+    let mut current_category = None;
+    let mut category_id_filter = None;
     if let Some(category) = query
         .category
         .as_deref()
         .or(project_info.project.default_category.as_deref())
-        .and_then(|id| report.report.categories.iter().find(|c| c.id == *id))
+        .and_then(|id| report.report.categories.iter().find(|c| c.id.eq_ignore_ascii_case(id)))
     {
-        measures = category.measures.as_ref().unwrap_or(&EMPTY_MEASURES);
+        measures = Cow::Borrowed(category.measures.as_ref().unwrap_or(&EMPTY_MEASURES));
         current_category = Some(category);
         category_id_filter = Some(category.id.clone());
     }
@@ -580,69 +1510,110 @@ fn apply_scope<'a>(
         .as_ref()
         .and_then(|unit_name| report.report.units.iter().find(|u| u.name == *unit_name))
     {
-        measures = unit.measures.as_ref().unwrap_or(&EMPTY_MEASURES);
+        measures = Cow::Borrowed(unit.measures.as_ref().unwrap_or(&EMPTY_MEASURES));
         current_unit = Some(unit.as_ref());
     }
+
+    // Color by subcategories of the current category, or by top-level categories
+    let mut categories = report
+        .report
+        .categories
+        .iter()
+        .filter(|c| match current_category {
+            Some(current) => {
+                c.id.strip_prefix(current.id.as_str()).is_some_and(|r| r.starts_with('.'))
+            }
+            None => !c.id.contains('.'),
+        })
+        .map(|c| c.id.as_str())
+        .collect::<Vec<_>>();
+    if categories.is_empty()
+        && let Some(current) = current_category
+    {
+        categories.push(current.id.as_str());
+    }
+    let color_ctx = ColorContext {
+        mode: color_mode,
+        categories,
+        matchers: category_matchers(report, custom_categories),
+        previous_units: previous_report.map(|previous| {
+            previous.report.units.iter().map(|unit| (unit.name.as_str(), unit.as_ref())).collect()
+        }),
+    };
+
+    let mut current_path = None;
     let (w, h) = query.size();
     let mut units = if let Some(unit) = current_unit {
-        let linked = unit.metadata.as_ref().is_some_and(|m| m.complete.is_some_and(|c| c));
+        let linked = is_unit_linked(unit);
+        let category = color_ctx.category_index(unit);
+        let previous_unit = color_ctx
+            .previous_units
+            .as_ref()
+            .and_then(|units| units.get(unit.name.as_str()).copied());
         unit.functions
             .iter()
             .filter_map(|f| {
                 if f.size == 0 {
                     return None;
                 }
+                let (color, outer_color) = color_ctx.mode.colors(&NodeStats {
+                    fuzzy_match_percent: f.fuzzy_match_percent,
+                    matched_data_percent: None,
+                    is_linked: linked,
+                    previous_fuzzy_match_percent: previous_unit
+                        .and_then(|u| u.functions.iter().find(|pf| pf.name == f.name))
+                        .map(|pf| pf.fuzzy_match_percent),
+                    category,
+                });
                 Some(ReportTemplateUnit {
-                    name: f
-                        .metadata
-                        .as_ref()
-                        .and_then(|m| m.demangled_name.as_deref())
-                        .unwrap_or(&f.name),
+                    name: Cow::Borrowed(
+                        f.metadata
+                            .as_ref()
+                            .and_then(|m| m.demangled_name.as_deref())
+                            .unwrap_or(&f.name),
+                    ),
                     total_code: f.size,
                     fuzzy_match_percent: f.fuzzy_match_percent,
-                    color: unit_color(f.fuzzy_match_percent),
+                    color,
+                    outer_color,
                     x: 0.0,
                     y: 0.0,
                     w: 0.0,
                     h: 0.0,
                     is_linked: linked,
+                    is_dir: false,
                 })
             })
             .collect::<Vec<_>>()
     } else {
-        report
+        let mut report_units = report
             .report
             .units
             .iter()
-            .filter_map(|unit| {
+            .map(|unit| unit.as_ref())
+            .filter(|unit| {
                 if let Some(category_id) = &category_id_filter
-                    && !unit
-                        .metadata
-                        .as_ref()
-                        .is_some_and(|m| m.progress_categories.iter().any(|c| c == category_id))
+                    && !color_ctx.in_category(unit, category_id)
                 {
-                    return None;
-                }
-                let measures = unit.measures.as_ref()?;
-                if measures.total_code == 0 {
-                    return None;
+                    return false;
                 }
-                Some(ReportTemplateUnit {
-                    name: &unit.name,
-                    total_code: measures.total_code,
-                    fuzzy_match_percent: measures.fuzzy_match_percent,
-                    color: unit_color(measures.fuzzy_match_percent),
-                    x: 0.0,
-                    y: 0.0,
-                    w: 0.0,
-                    h: 0.0,
-                    is_linked: unit
-                        .metadata
-                        .as_ref()
-                        .is_some_and(|m| m.complete.is_some_and(|c| c)),
-                })
+                unit.measures.as_ref().is_some_and(|m| m.total_code > 0)
             })
-            .collect::<Vec<_>>()
+            .collect::<Vec<_>>();
+        if let Some(path) = query.path.as_deref() {
+            let path = resolve_unit_path(&report_units, path);
+            report_units.retain(|unit| path_remainder(&unit.name, &path).is_some());
+            measures = if report_units.is_empty() {
+                Cow::Borrowed(&EMPTY_MEASURES)
+            } else {
+                Cow::Owned(report_units.iter().filter_map(|unit| unit.measures).collect())
+            };
+            let nodes = group_units_by_path(&report_units, &path, &color_ctx);
+            current_path = Some(path);
+            nodes
+        } else {
+            report_units.into_iter().map(|unit| unit_template(unit, &color_ctx)).collect::<Vec<_>>()
+        }
     };
     layout_units(
         &mut units,
@@ -657,16 +1628,146 @@ fn apply_scope<'a>(
     );
     let label = current_unit
         .as_ref()
-        .map(|u| u.name.rsplit_once('/').map_or(u.name.as_str(), |(_, name)| name))
+        .map(|u| Cow::Borrowed(u.name.rsplit_once('/').map_or(u.name.as_str(), |(_, name)| name)))
+        .or_else(|| {
+            let path = current_path.as_deref().filter(|p| !p.is_empty())?;
+            Some(Cow::Owned(path.rsplit_once('/').map_or(path, |(_, name)| name).to_string()))
+        })
         .or_else(|| {
             // Only show a category label if it is not the default category
             let default_category_id =
                 project_info.project.default_category.as_deref().unwrap_or("all");
             let current_category_id = current_category.map(|c| c.id.as_str()).unwrap_or("all");
             (current_category_id != default_category_id)
-                .then(|| current_category.map(|c| c.name.as_str()).unwrap_or("All"))
+                .then(|| Cow::Borrowed(current_category.map(|c| c.name.as_str()).unwrap_or("All")))
+        });
+    let category_matcher =
+        current_category.and_then(|c| color_ctx.matchers.get(c.id.as_str()).cloned());
+    Ok(Scope {
+        report,
+        project_info,
+        measures,
+        current_category,
+        category_matcher,
+        current_unit,
+        current_path,
+        color_mode,
+        units,
+        label,
+    })
+}
+
+fn is_unit_linked(unit: &ReportUnit) -> bool {
+    unit.metadata.as_ref().is_some_and(|m| m.complete.is_some_and(|c| c))
+}
+
+fn unit_template<'a>(unit: &'a ReportUnit, color_ctx: &ColorContext) -> ReportTemplateUnit<'a> {
+    let measures = unit.measures.as_ref().unwrap_or(&EMPTY_MEASURES);
+    let is_linked = is_unit_linked(unit);
+    let (color, outer_color) = color_ctx.mode.colors(&NodeStats {
+        fuzzy_match_percent: measures.fuzzy_match_percent,
+        matched_data_percent: (measures.total_data > 0).then_some(measures.matched_data_percent),
+        is_linked,
+        previous_fuzzy_match_percent: color_ctx.previous_fuzzy_match_percent([unit]),
+        category: color_ctx.category_index(unit),
+    });
+    ReportTemplateUnit {
+        name: Cow::Borrowed(&unit.name),
+        total_code: measures.total_code,
+        fuzzy_match_percent: measures.fuzzy_match_percent,
+        color,
+        outer_color,
+        x: 0.0,
+        y: 0.0,
+        w: 0.0,
+        h: 0.0,
+        is_linked,
+        is_dir: false,
+    }
+}
+
+/// Returns the remainder of a unit name below the given directory path.
+fn path_remainder<'a>(name: &'a str, path: &str) -> Option<&'a str> {
+    if path.is_empty() {
+        return Some(name);
+    }
+    name.strip_prefix(path)?.strip_prefix('/')
+}
+
+/// Normalizes a directory path, descending through directories that contain only a single
+/// subdirectory so that the treemap doesn't start with one large node.
+fn resolve_unit_path(units: &[&ReportUnit], path: &str) -> String {
+    let mut path = path.trim_matches('/').to_string();
+    loop {
+        let mut remainders = units.iter().filter_map(|unit| path_remainder(&unit.name, &path));
+        let Some((dir, _)) = remainders.next().and_then(|r| r.split_once('/')) else {
+            break;
+        };
+        if !remainders.all(|r| r.split_once('/').is_some_and(|(d, _)| d == dir)) {
+            break;
+        }
+        path = if path.is_empty() { dir.to_string() } else { format!("{path}/{dir}") };
+    }
+    path
+}
+
+/// Groups units by the path segment following the directory path. Units directly inside the
+/// directory are kept as-is, while deeper units are aggregated into directory nodes.
+fn group_units_by_path<'a>(
+    units: &[&'a ReportUnit],
+    path: &str,
+    color_ctx: &ColorContext,
+) -> Vec<ReportTemplateUnit<'a>> {
+    let mut result = Vec::new();
+    let mut dirs = BTreeMap::<String, Vec<&ReportUnit>>::new();
+    for &unit in units {
+        let Some(remainder) = path_remainder(&unit.name, path) else {
+            continue;
+        };
+        if let Some((dir, _)) = remainder.split_once('/') {
+            let dir_path = if path.is_empty() { dir.to_string() } else { format!("{path}/{dir}") };
+            dirs.entry(dir_path).or_default().push(unit);
+        } else {
+            result.push(unit_template(unit, color_ctx));
+        }
+    }
+    for (dir_path, dir_units) in dirs {
+        let measures = dir_units.iter().filter_map(|unit| unit.measures).collect::<Measures>();
+        let is_linked = dir_units.iter().all(|unit| is_unit_linked(unit));
+        // Use the category covering the most code in the directory
+        let mut category_sizes = BTreeMap::<usize, u64>::new();
+        for unit in &dir_units {
+            if let Some(index) = color_ctx.category_index(unit) {
+                *category_sizes.entry(index).or_default() +=
+                    unit.measures.as_ref().map_or(0, |m| m.total_code);
+            }
+        }
+        let category =
+            category_sizes.into_iter().max_by_key(|&(_, size)| size).map(|(index, _)| index);
+        let (color, outer_color) = color_ctx.mode.colors(&NodeStats {
+            fuzzy_match_percent: measures.fuzzy_match_percent,
+            matched_data_percent: (measures.total_data > 0)
+                .then_some(measures.matched_data_percent),
+            is_linked,
+            previous_fuzzy_match_percent: color_ctx
+                .previous_fuzzy_match_percent(dir_units.iter().copied()),
+            category,
         });
-    Ok(Scope { report, project_info, measures, current_category, current_unit, units, label })
+        result.push(ReportTemplateUnit {
+            name: Cow::Owned(dir_path),
+            total_code: measures.total_code,
+            fuzzy_match_percent: measures.fuzzy_match_percent,
+            color,
+            outer_color,
+            x: 0.0,
+            y: 0.0,
+            w: 0.0,
+            h: 0.0,
+            is_linked,
+            is_dir: true,
+        });
+    }
+    result
 }
 
 async fn render_report(
@@ -682,6 +1783,8 @@ async fn render_report(
         measures,
         current_category: current_category_ref,
         current_unit,
+        current_path,
+        color_mode,
         units,
         label,
     } = scope;
@@ -717,33 +1820,79 @@ async fn render_report(
 
     let request_url = Url::parse(&uri.to_string()).context("Failed to parse URI")?;
     let project_base_path =
-        format!("/{}/{}", project_info.project.owner, project_info.project.repo);
+        format!("/{}/{}", project_info.project.owner, project_info.project.repo_path());
     let project_history_path = request_url.query_param("mode", Some("history"));
+    let project_targets_path = request_url.query_param("mode", Some("targets"));
+    let project_contributors_path = request_url
+        .with_path(&format!(
+            "/{}/{}/{}",
+            project_info.project.owner,
+            project_info.project.repo_path(),
+            report.version
+        ))
+        .query_param("mode", Some("contributors"));
+    let project_compare_path = request_url
+        .with_path(&format!(
+            "/{}/{}/{}/{}",
+            project_info.project.owner,
+            project_info.project.repo_path(),
+            report.version,
+            report.commit.sha
+        ))
+        .query_param("mode", Some("compare"));
     let project_manage_path =
-        format!("/manage/{}/{}", project_info.project.owner, project_info.project.repo);
+        format!("/manage/{}/{}", project_info.project.owner, project_info.project.repo_path());
+    // Forecasts are based on the full history, so only show them for the latest commit
+    let forecasts = if project_info.next_commit.is_none() && current_unit.is_none() {
+        load_forecasts(scope, state).await?
+    } else {
+        vec![]
+    };
+    let milestones = if project_info.next_commit.is_none() && current_unit.is_none() {
+        state
+            .db
+            .get_milestones(project_info.project.id, Some(&report.version))
+            .await?
+            .into_iter()
+            .map(|m| {
+                let progress = m.progress(&report.report);
+                (m, progress)
+            })
+            .collect()
+    } else {
+        vec![]
+    };
     let can_manage =
-        current_user.as_ref().is_some_and(|u| u.can_manage_repo(project_info.project.id));
+        current_user.as_ref().is_some_and(|u| u.can_manage_project(&project_info.project));
     let default_category = project_info.project.default_category();
 
     let is_default_version = project_info.default_version() == Some(report.version.as_str());
     let is_latest_commit = project_info.next_commit.is_none();
     let is_default_category = current_category.is_none_or(|c| c.id == default_category);
-    let is_primary_view =
-        is_latest_commit && is_default_version && is_default_category && current_unit.is_none();
+    let is_primary_view = is_latest_commit
+        && is_default_version
+        && is_default_category
+        && current_unit.is_none()
+        && current_path.is_none();
 
     let canonical_url = if is_default_version && is_latest_commit {
-        request_url
-            .with_path(&format!("/{}/{}", project_info.project.owner, project_info.project.repo))
+        request_url.with_path(&format!(
+            "/{}/{}",
+            project_info.project.owner,
+            project_info.project.repo_path()
+        ))
     } else if is_latest_commit {
         request_url.with_path(&format!(
             "/{}/{}/{}",
-            project_info.project.owner, project_info.project.repo, report.version
+            project_info.project.owner,
+            project_info.project.repo_path(),
+            report.version
         ))
     } else {
         request_url.with_path(&format!(
             "/{}/{}/{}/{}",
             project_info.project.owner,
-            project_info.project.repo,
+            project_info.project.repo_path(),
             report.version,
             report.commit.sha
         ))
@@ -760,13 +1909,15 @@ async fn render_report(
             let version_url = if is_latest_commit {
                 request_url.with_path(&format!(
                     "/{}/{}/{}",
-                    project_info.project.owner, project_info.project.repo, version
+                    project_info.project.owner,
+                    project_info.project.repo_path(),
+                    version
                 ))
             } else {
                 request_url.with_path(&format!(
                     "/{}/{}/{}/{}",
                     project_info.project.owner,
-                    project_info.project.repo,
+                    project_info.project.repo_path(),
                     version,
                     report.commit.sha
                 ))
@@ -794,26 +1945,56 @@ async fn render_report(
     let prev_commit_path = project_info.prev_commit.as_deref().map(|commit| {
         let url = request_url.with_path(&format!(
             "/{}/{}/{}/{}",
-            project_info.project.owner, project_info.project.repo, report.version, commit
+            project_info.project.owner,
+            project_info.project.repo_path(),
+            report.version,
+            commit
         ));
         url.path_and_query().to_string()
     });
     let next_commit_path = project_info.next_commit.as_deref().map(|commit| {
         let url = request_url.with_path(&format!(
             "/{}/{}/{}/{}",
-            project_info.project.owner, project_info.project.repo, report.version, commit
+            project_info.project.owner,
+            project_info.project.repo_path(),
+            report.version,
+            commit
         ));
         url.path_and_query().to_string()
     });
     let latest_commit_path = project_info.next_commit.as_deref().map(|_| {
         let url = request_url.with_path(&format!(
             "/{}/{}/{}",
-            project_info.project.owner, project_info.project.repo, report.version
+            project_info.project.owner,
+            project_info.project.repo_path(),
+            report.version
         ));
         url.path_and_query().to_string()
     });
 
     let units_path = canonical_url.query_param("unit", None).path_and_query().to_string();
+    let flat_path = canonical_url.query_param("path", None).path_and_query().to_string();
+    let tree_path = canonical_url.query_param("path", Some("")).path_and_query().to_string();
+    let color_modes = ColorMode::variants()
+        .iter()
+        .map(|&mode| {
+            let value = (mode != ColorMode::default()).then(|| mode.as_str());
+            (mode, canonical_url.query_param("colorBy", value).path_and_query().to_string())
+        })
+        .collect::<Vec<_>>();
+    let path_crumbs = current_path.as_deref().filter(|p| !p.is_empty()).map(|path| {
+        let mut crumbs = Vec::new();
+        let mut prefix = String::new();
+        for segment in path.split('/') {
+            if !prefix.is_empty() {
+                prefix.push('/');
+            }
+            prefix.push_str(segment);
+            let url = canonical_url.query_param("path", Some(&prefix));
+            crumbs.push((segment, url.path_and_query().to_string()));
+        }
+        crumbs
+    });
     let commit_message = commit_message.as_deref().and_then(|message| message.lines().next());
     let commit_url = format!("{}/commit/{}", project_info.project.repo_url(), report.commit.sha);
     let source_file_url = current_unit
@@ -836,7 +2017,7 @@ async fn render_report(
 
     // Only show visibility banners if we're on the primary view
     let visibility = if is_primary_view {
-        project_visibility(&project_info.project, Some(measures))
+        project_visibility(&project_info.project, Some(measures.as_ref()))
     } else {
         ProjectVisibility::Visible
     };
@@ -926,6 +2107,17 @@ async fn render_report(
                                         span.icon-chart-line { " " }
                                     }
                                 }
+                                li {
+                                    a href=(project_targets_path) { "Easy targets" }
+                                }
+                                li {
+                                    a href=(project_contributors_path.path_and_query()) { "Contributors" }
+                                }
+                                @if project_info.report_versions.len() > 1 {
+                                    li {
+                                        a href=(project_compare_path.path_and_query()) { "Compare versions" }
+                                    }
+                                }
                                 @if can_manage {
                                     li {
                                         a href=(project_manage_path) {
@@ -975,6 +2167,58 @@ async fn render_report(
                         }
                         (ctx.data_progress_sections(&measures))
                     }
+                    @if !forecasts.is_empty() {
+                        h6 { "Estimated completion" }
+                        ul.forecasts {
+                            @for forecast in &forecasts {
+                                li {
+                                    strong { (forecast.window.name()) ": " }
+                                    @if let Some(estimate) = forecast.estimate {
+                                        (short_date(estimate))
+                                        small.muted {
+                                            " (95% range "
+                                            (forecast.earliest.map_or_else(|| "?".to_string(), short_date))
+                                            " to "
+                                            (forecast.latest.map_or_else(|| "never".to_string(), short_date))
+                                            ")"
+                                        }
+                                    } @else {
+                                        "not trending towards completion"
+                                    }
+                                    small.muted {
+                                        " • "
+                                        (format!("{:+.3}", forecast.rate_per_day)) "% per day over "
+                                        (forecast.samples) " reports"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    @if !milestones.is_empty() {
+                        h6 { "Milestones" }
+                        @for (milestone, progress) in &milestones {
+                            div.milestone {
+                                strong { (milestone.name) }
+                                small.muted {
+                                    " • " (format_percent(milestone.target)) " " (milestone.measure)
+                                    @if let Some(scope) = milestone.scope() {
+                                        " in " code { (scope) }
+                                    }
+                                    @if let Some(due_date) = milestone.due_date {
+                                        " • due " (short_date(due_date))
+                                    }
+                                }
+                                @if milestone.target > 0.0 {
+                                    progress value=(progress.min(milestone.target)) max=(milestone.target) {}
+                                }
+                                @if let Some(achieved_at) = milestone.achieved_at {
+                                    small { "Achieved " (short_date(achieved_at)) }
+                                } @else {
+                                    small.muted { (format_percent(*progress)) " of " (format_percent(milestone.target)) }
+                                }
+                            }
+                        }
+                    }
                     h6 { "Commit" }
                     div {
                         @if let Some(message) = commit_message {
@@ -1054,6 +2298,38 @@ async fn render_report(
                                 }
                             }
                         }
+                        div role="group" {
+                            a.secondary.outline[current_path.is_some()] role="button" href=(flat_path) {
+                                "Flat"
+                            }
+                            a.secondary.outline[current_path.is_none()] role="button" href=(tree_path) {
+                                "Directories"
+                            }
+                        }
+                        @if let Some(path_crumbs) = path_crumbs {
+                            nav aria-label="breadcrumb" {
+                                ul {
+                                    li {
+                                        a href=(tree_path) { "Root" }
+                                    }
+                                    @for (name, path) in path_crumbs {
+                                        li {
+                                            a href=(path) { (name) }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    details.dropdown title="Color by" {
+                        summary { "Color: " (color_mode) }
+                        ul {
+                            @for (mode, path) in &color_modes {
+                                li {
+                                    a href=(path) { (mode) }
+                                }
+                            }
+                        }
                     }
                     label {
                         input name="filter" required placeholder="Filter, e.g.: 'camera <70% >10kb'";
@@ -1096,6 +2372,8 @@ async fn render_history(
     current_user: Option<CurrentUser>,
     mut ctx: TemplateContext,
     result: Vec<ReportHistoryEntry>,
+    forecasts: &[Forecast],
+    milestones: &[Milestone],
 ) -> Result<Response, AppError> {
     let Scope {
         report,
@@ -1103,6 +2381,8 @@ async fn render_history(
         measures,
         current_category: current_category_ref,
         current_unit,
+        current_path: _,
+        color_mode: _,
         units: _,
         label,
     } = scope;
@@ -1110,10 +2390,12 @@ async fn render_history(
 
     let request_url = Url::parse(&uri.to_string()).context("Failed to parse URI")?;
     let project_base_path =
-        format!("/{}/{}", project_info.project.owner, project_info.project.repo);
+        format!("/{}/{}", project_info.project.owner, project_info.project.repo_path());
     let canonical_url = request_url.with_path(&format!(
         "/{}/{}/{}",
-        project_info.project.owner, project_info.project.repo, report.version
+        project_info.project.owner,
+        project_info.project.repo_path(),
+        report.version
     ));
     let image_url = canonical_url
         .with_path(&format!("{}.png", canonical_url.path()))
@@ -1125,7 +2407,10 @@ async fn render_history(
         .map(|version| {
             let version_url = request_url.with_path(&format!(
                 "/{}/{}/{}/{}",
-                project_info.project.owner, project_info.project.repo, version, report.commit.sha
+                project_info.project.owner,
+                project_info.project.repo_path(),
+                version,
+                report.commit.sha
             ));
             ReportTemplateVersion { id: version, path: version_url.path_and_query().to_string() }
         })
@@ -1156,6 +2441,8 @@ async fn render_history(
         Cow::Borrowed(project_short_name)
     };
 
+    let forecast_responses = forecasts.iter().map(ForecastResponse::from).collect::<Vec<_>>();
+
     // Load blocking resources first so we don't duplicate them
     let header = ctx.header().await;
     let history_chunks = ctx.chunks("history", Load::Blocking).await;
@@ -1240,7 +2527,25 @@ async fn render_history(
                     script nonce=[ctx.nonce.as_deref()] {
                         (PreEscaped(r#"window.historyData="#))
                         (escape_script(&serde_json::to_string(&result)?))
-                        (PreEscaped(r#";renderChart("chart",window.historyData)"#))
+                        (PreEscaped(r#";window.historyForecasts="#))
+                        (escape_script(&serde_json::to_string(&forecast_responses)?))
+                        (PreEscaped(r#";renderChart("chart",window.historyData,window.historyForecasts)"#))
+                    }
+                    @if !milestones.is_empty() {
+                        h4 { "Milestones achieved" }
+                        ul {
+                            @for milestone in milestones {
+                                @if let (Some(achieved_at), Some(commit)) = (milestone.achieved_at, &milestone.achieved_commit) {
+                                    li {
+                                        strong { (milestone.name) }
+                                        " • " (short_date(achieved_at)) " at "
+                                        a href=(format!("{}/{}/{}", project_base_path, report.version, commit)) {
+                                            code { (commit[..7.min(commit.len())]) }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
                     hr;
                     div role="group" {
@@ -1253,3 +2558,795 @@ async fn render_history(
     };
     Ok((ctx, rendered).into_response())
 }
+
+async fn render_targets(
+    scope: &Scope<'_>,
+    uri: Uri,
+    current_user: Option<CurrentUser>,
+    mut ctx: TemplateContext,
+    targets: &[TargetFunction<'_>],
+) -> Result<Response, AppError> {
+    let Scope { report, project_info, current_category, current_unit, label, .. } = scope;
+
+    let request_url = Url::parse(&uri.to_string()).context("Failed to parse URI")?;
+    let project_base_path =
+        format!("/{}/{}", project_info.project.owner, project_info.project.repo_path());
+    let report_path = if project_info.next_commit.is_none() {
+        format!("{}/{}", project_base_path, report.version)
+    } else {
+        format!("{}/{}/{}", project_base_path, report.version, report.commit.sha)
+    };
+    let canonical_url = request_url.with_path(&report_path);
+    let units_url = canonical_url.query_param("mode", None);
+    let csv_path = canonical_url.with_path(&format!("{report_path}.csv"));
+    let json_path = canonical_url.with_path(&format!("{report_path}.json"));
+    let all_units_path = canonical_url.query_param("unit", None);
+
+    let versions = project_info
+        .report_versions
+        .iter()
+        .map(|version| {
+            let version_url = request_url.with_path(&format!(
+                "/{}/{}/{}/{}",
+                project_info.project.owner,
+                project_info.project.repo_path(),
+                version,
+                report.commit.sha
+            ));
+            ReportTemplateVersion { id: version, path: version_url.path_and_query().to_string() }
+        })
+        .collect::<Vec<_>>();
+
+    let default_category = project_info.project.default_category();
+    let CategorySelection { categories, current_top_index, current_sub_index } =
+        build_category_selection(
+            &canonical_url,
+            &report.report.categories,
+            *current_category,
+            default_category,
+        );
+    let current_top_category = &categories[current_top_index];
+    let current_category_item = current_sub_index
+        .map(|i| &current_top_category.subcategories[i])
+        .unwrap_or(&current_top_category.category);
+
+    let project_short_name = project_info.project.short_name();
+    let project_short_name_with_label = if let Some(label) = label {
+        Cow::Owned(format!("{project_short_name} ({label})"))
+    } else {
+        Cow::Borrowed(project_short_name)
+    };
+    let remaining_code = targets.iter().map(|t| t.remaining_code).sum::<u64>();
+
+    let rendered = html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                title { (project_short_name_with_label) " • Easy Targets" }
+                (ctx.header().await)
+                (ctx.chunks("main", Load::Deferred).await)
+                link rel="canonical" href=(canonical_url);
+                meta name="robots" content="noindex";
+            }
+            body {
+                header {
+                    nav {
+                        ul {
+                            li {
+                                a href="/" { strong { "decomp.dev" } }
+                            }
+                            li {
+                                a href="/projects" { "Projects" }
+                            }
+                            li {
+                                a href=(project_base_path) { (project_short_name) }
+                            }
+                            li {
+                                a href=(request_url) { "Easy Targets" }
+                            }
+                        }
+                        (nav_links())
+                    }
+                }
+                main {
+                    h3 { "Easy targets for " (project_short_name_with_label) }
+                    p.muted {
+                        "Unmatched functions ordered by the estimated amount of code left to match."
+                        " Small functions with a high match percent are listed first."
+                    }
+                    details.dropdown title="Version" {
+                        summary { (report.version) }
+                        ul {
+                            @for version in &versions {
+                                li {
+                                    a href=(version.path) { (version.id) }
+                                }
+                            }
+                        }
+                    }
+                    @if current_unit.is_none() && categories.len() > 1 {
+                        details.dropdown title="Category" {
+                            summary { (current_top_category.category.name) }
+                            ul {
+                                @for category in &categories {
+                                    li {
+                                        a href=(category.category.path) { (category.category.name) }
+                                    }
+                                }
+                            }
+                        }
+                        @if !current_top_category.subcategories.is_empty() {
+                            details.dropdown title="Subcategory" {
+                                summary { (current_category_item.name) }
+                                ul {
+                                    li {
+                                        a href=(current_top_category.category.path) { (current_top_category.category.name) }
+                                    }
+                                    @for sub in &current_top_category.subcategories {
+                                        li {
+                                            a href=(sub.path) { (sub.name) }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    p {
+                        (targets.len()) " unmatched functions, about " (size(remaining_code)) " of code left."
+                        @if targets.len() > MAX_TARGET_ROWS {
+                            " Showing the first " (MAX_TARGET_ROWS) "."
+                        }
+                        " Download as "
+                        a href=(csv_path.path_and_query()) { "CSV" }
+                        " or "
+                        a href=(json_path.path_and_query()) { "JSON" }
+                        "."
+                    }
+                    table.striped {
+                        thead {
+                            tr {
+                                th { "Function" }
+                                th { "Unit" }
+                                th { "Size" }
+                                th { "Matched" }
+                                th { "Remaining" }
+                            }
+                        }
+                        tbody {
+                            @for target in targets.iter().take(MAX_TARGET_ROWS) {
+                                tr {
+                                    td {
+                                        code title=(target.name) { (target.demangled_name.unwrap_or(target.name)) }
+                                    }
+                                    td {
+                                        a href=(units_url.query_param("unit", Some(target.unit)).path_and_query()) {
+                                            (target.unit)
+                                        }
+                                        @if let Some(source_path) = target.source_path {
+                                            " "
+                                            a.muted href=(format!("{}/blob/{}/{}", project_info.project.repo_url(), report.commit.sha, source_path))
+                                                target="_blank" title=(source_path) { "(source)" }
+                                        }
+                                    }
+                                    td { (size(target.size)) }
+                                    td { (format_percent(target.fuzzy_match_percent)) }
+                                    td { (size(target.remaining_code)) }
+                                }
+                            }
+                        }
+                    }
+                    hr;
+                    div role="group" {
+                        @if current_unit.is_some() {
+                            a.secondary role="button" href=(all_units_path.path_and_query()) { "All units" }
+                        }
+                        a role="button" href=(units_url.path_and_query()) { "Back to report" }
+                    }
+                }
+            }
+            (ctx.footer(current_user.as_ref()))
+        }
+    };
+    Ok((ctx, rendered).into_response())
+}
+
+async fn render_contributors(
+    scope: &Scope<'_>,
+    uri: Uri,
+    query: &ReportQuery,
+    current_user: Option<CurrentUser>,
+    mut ctx: TemplateContext,
+    stats: &[ContributorStats],
+    commits: Option<&[CommitDelta]>,
+) -> Result<Response, AppError> {
+    let Scope { report, project_info, .. } = scope;
+
+    let request_url = Url::parse(&uri.to_string()).context("Failed to parse URI")?;
+    let project_base_path =
+        format!("/{}/{}", project_info.project.owner, project_info.project.repo_path());
+    let canonical_url = request_url.with_path(&format!(
+        "/{}/{}/{}",
+        project_info.project.owner,
+        project_info.project.repo_path(),
+        report.version
+    ));
+    let leaderboard_url = canonical_url.query_param("contributor", None);
+    let versions = project_info
+        .report_versions
+        .iter()
+        .map(|version| {
+            let version_url = request_url.with_path(&format!(
+                "/{}/{}/{}",
+                project_info.project.owner,
+                project_info.project.repo_path(),
+                version
+            ));
+            ReportTemplateVersion { id: version, path: version_url.path_and_query().to_string() }
+        })
+        .collect::<Vec<_>>();
+    let current_window = CONTRIBUTOR_WINDOWS
+        .iter()
+        .find(|(days, _)| *days == query.days)
+        .map_or("Custom", |(_, name)| *name);
+    let project_short_name = project_info.project.short_name();
+    let repo_url = project_info.project.repo_url();
+
+    let rendered = html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                title { (project_short_name) " • Contributors" }
+                (ctx.header().await)
+                (ctx.chunks("main", Load::Deferred).await)
+                link rel="canonical" href=(canonical_url);
+                meta name="description" content=(format!("Contributors to {}", project_info.project.name()));
+            }
+            body {
+                header {
+                    nav {
+                        ul {
+                            li {
+                                a href="/" { strong { "decomp.dev" } }
+                            }
+                            li {
+                                a href="/projects" { "Projects" }
+                            }
+                            li {
+                                a href=(project_base_path) { (project_short_name) }
+                            }
+                            li {
+                                a href=(leaderboard_url.path_and_query()) { "Contributors" }
+                            }
+                        }
+                        (nav_links())
+                    }
+                }
+                main {
+                    h3 { "Contributors to " (project_short_name) }
+                    p.muted {
+                        "Progress is attributed to the author of the pull request that merged each"
+                        " commit, or the commit author for direct pushes, and measured against the"
+                        " previous report."
+                    }
+                    details.dropdown title="Version" {
+                        summary { (report.version) }
+                        ul {
+                            @for version in &versions {
+                                li {
+                                    a href=(version.path) { (version.id) }
+                                }
+                            }
+                        }
+                    }
+                    details.dropdown title="Time window" {
+                        summary { (current_window) }
+                        ul {
+                            @for (days, name) in CONTRIBUTOR_WINDOWS {
+                                li {
+                                    a href=(request_url.query_param("days", days.map(|d| d.to_string()).as_deref()).path_and_query()) {
+                                        (name)
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    @if stats.is_empty() {
+                        p { "No attributed commits in this time window." }
+                    } @else {
+                        table.striped {
+                            thead {
+                                tr {
+                                    th { "Contributor" }
+                                    th { "Commits" }
+                                    th { "Matched code" }
+                                    th { "Matched data" }
+                                    th { "Matched functions" }
+                                    th { "Last commit" }
+                                }
+                            }
+                            tbody {
+                                @for s in stats {
+                                    tr {
+                                        td {
+                                            a href=(request_url.query_param("contributor", Some(&s.contributor)).path_and_query()) {
+                                                (s.contributor)
+                                            }
+                                        }
+                                        td { (s.commits) }
+                                        td { (size_delta(s.matched_code)) }
+                                        td { (size_delta(s.matched_data)) }
+                                        td { (format!("{:+}", s.matched_functions)) }
+                                        td title=(date(s.last_commit)) { (timeago(s.last_commit)) }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    @if let (Some(contributor), Some(commits)) = (query.contributor.as_deref(), commits) {
+                        h4 { "History for " (contributor) }
+                        @if commits.is_empty() {
+                            p { "No commits found." }
+                        } @else {
+                            table.striped {
+                                thead {
+                                    tr {
+                                        th { "Commit" }
+                                        th { "Pull request" }
+                                        th { "Matched code" }
+                                        th { "Matched functions" }
+                                        th { "Date" }
+                                    }
+                                }
+                                tbody {
+                                    @for delta in commits {
+                                        tr {
+                                            td {
+                                                a href=(format!("{}/{}/{}", project_base_path, report.version, delta.commit.sha)) {
+                                                    code { (delta.commit.sha[..7]) }
+                                                }
+                                                @if let Some(message) = delta.commit.message.as_deref().and_then(|m| m.lines().next()) {
+                                                    " " (message)
+                                                }
+                                            }
+                                            td {
+                                                @if let Some(pr_number) = delta.pr_number {
+                                                    a href=(format!("{repo_url}/pull/{pr_number}")) target="_blank" {
+                                                        "#" (pr_number)
+                                                    }
+                                                }
+                                            }
+                                            td { (size_delta(delta.matched_code)) }
+                                            td { (format!("{:+}", delta.matched_functions)) }
+                                            td title=(date(delta.commit.timestamp)) { (timeago(delta.commit.timestamp)) }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    hr;
+                    div role="group" {
+                        a role="button" href=(project_base_path) { "Back to report" }
+                    }
+                }
+            }
+            (ctx.footer(current_user.as_ref()))
+        }
+    };
+    Ok((ctx, rendered).into_response())
+}
+
+async fn render_compare(
+    scope: &Scope<'_>,
+    uri: Uri,
+    current_user: Option<CurrentUser>,
+    mut ctx: TemplateContext,
+    comparison: &CompareResponse,
+) -> Result<Response, AppError> {
+    let Scope { report, project_info, .. } = scope;
+
+    let request_url = Url::parse(&uri.to_string()).context("Failed to parse URI")?;
+    let project_base_path =
+        format!("/{}/{}", project_info.project.owner, project_info.project.repo_path());
+    let report_path = format!("{}/{}/{}", project_base_path, report.version, report.commit.sha);
+    let canonical_url = request_url.with_path(&report_path);
+    let json_path = canonical_url.with_path(&format!("{report_path}.json"));
+    let version_path = |base: &str, other: &str| {
+        request_url
+            .with_path(&format!("{}/{}/{}", project_base_path, base, report.commit.sha))
+            .query_param("to", Some(other))
+            .path_and_query()
+            .to_string()
+    };
+    let unit_path = |version: &str, unit: &str| {
+        request_url
+            .with_path(&format!("{}/{}/{}", project_base_path, version, report.commit.sha))
+            .query_param("mode", None)
+            .query_param("to", None)
+            .query_param("unit", Some(unit))
+            .path_and_query()
+            .to_string()
+    };
+
+    let project_short_name = project_info.project.short_name();
+    let base_version = comparison.base_version.as_str();
+    let other_version = comparison.other_version.as_str();
+    let matched_in_base = comparison.count(FunctionComparison::MatchedInBase);
+    let matched_in_other = comparison.count(FunctionComparison::MatchedInOther);
+    let only_in_base = comparison.count(FunctionComparison::OnlyInBase);
+    let only_in_other = comparison.count(FunctionComparison::OnlyInOther);
+    let matched_in_one = comparison
+        .functions
+        .iter()
+        .filter(|f| {
+            matches!(
+                f.status,
+                FunctionComparison::MatchedInBase | FunctionComparison::MatchedInOther
+            )
+        })
+        .collect::<Vec<_>>();
+    let totals = [
+        (
+            "Matched code",
+            format_percent(comparison.base.matched_code_percent),
+            format_percent(comparison.other.matched_code_percent),
+        ),
+        (
+            "Matched data",
+            format_percent(comparison.base.matched_data_percent),
+            format_percent(comparison.other.matched_data_percent),
+        ),
+        (
+            "Linked code",
+            format_percent(comparison.base.complete_code_percent),
+            format_percent(comparison.other.complete_code_percent),
+        ),
+        (
+            "Matched functions",
+            format!("{} / {}", comparison.base.matched_functions, comparison.base.total_functions),
+            format!(
+                "{} / {}",
+                comparison.other.matched_functions, comparison.other.total_functions
+            ),
+        ),
+        ("Total code", size(comparison.base.total_code), size(comparison.other.total_code)),
+    ];
+
+    let rendered = html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                title { (project_short_name) " • " (base_version) " vs " (other_version) }
+                (ctx.header().await)
+                (ctx.chunks("main", Load::Deferred).await)
+                link rel="canonical" href=(canonical_url);
+                meta name="robots" content="noindex";
+            }
+            body {
+                header {
+                    nav {
+                        ul {
+                            li {
+                                a href="/" { strong { "decomp.dev" } }
+                            }
+                            li {
+                                a href="/projects" { "Projects" }
+                            }
+                            li {
+                                a href=(project_base_path) { (project_short_name) }
+                            }
+                            li {
+                                a href=(request_url) { "Compare" }
+                            }
+                        }
+                        (nav_links())
+                    }
+                }
+                main {
+                    h3 { "Comparing " (base_version) " and " (other_version) }
+                    p.muted {
+                        "Both versions at commit "
+                        a href=(format!("{}/commit/{}", project_info.project.repo_url(), report.commit.sha)) target="_blank" {
+                            code { (report.commit.sha[..7]) }
+                        }
+                        ". Functions are paired by name or address."
+                    }
+                    details.dropdown title="Base version" {
+                        summary { (base_version) }
+                        ul {
+                            @for version in project_info.report_versions.iter().filter(|v| *v != other_version) {
+                                li {
+                                    a href=(version_path(version, other_version)) { (version) }
+                                }
+                            }
+                        }
+                    }
+                    details.dropdown title="Compare to" {
+                        summary { (other_version) }
+                        ul {
+                            @for version in project_info.report_versions.iter().filter(|v| *v != base_version) {
+                                li {
+                                    a href=(version_path(base_version, version)) { (version) }
+                                }
+                            }
+                        }
+                    }
+                    table.striped {
+                        thead {
+                            tr {
+                                th {}
+                                th { (base_version) }
+                                th { (other_version) }
+                            }
+                        }
+                        tbody {
+                            @for (name, base, other) in &totals {
+                                tr {
+                                    th scope="row" { (name) }
+                                    td { (base) }
+                                    td { (other) }
+                                }
+                            }
+                        }
+                    }
+                    p {
+                        (matched_in_base) " functions matched only in " (base_version) ", "
+                        (matched_in_other) " matched only in " (other_version) ". "
+                        (only_in_base) " functions exist only in " (base_version) " and "
+                        (only_in_other) " only in " (other_version) ". "
+                        "Download as "
+                        a href=(json_path.path_and_query()) { "JSON" }
+                        "."
+                    }
+                    @if !matched_in_one.is_empty() {
+                        h4 { "Matched in one version" }
+                        @if matched_in_one.len() > MAX_COMPARE_ROWS {
+                            p.muted { "Showing the first " (MAX_COMPARE_ROWS) "." }
+                        }
+                        .overflow-auto {
+                            table.striped {
+                                thead {
+                                    tr {
+                                        th { "Function" }
+                                        th { "Unit" }
+                                        th { (base_version) }
+                                        th { (other_version) }
+                                    }
+                                }
+                                tbody {
+                                    @for function in matched_in_one.iter().take(MAX_COMPARE_ROWS) {
+                                        tr {
+                                            td {
+                                                code title=(function.name) { (function.demangled_name.as_deref().unwrap_or(&function.name)) }
+                                            }
+                                            td {
+                                                @let version = if function.status == FunctionComparison::MatchedInBase { other_version } else { base_version };
+                                                a href=(unit_path(version, &function.unit)) { (function.unit) }
+                                            }
+                                            td {
+                                                @if let Some(item) = &function.base {
+                                                    (format_percent(item.fuzzy_match_percent))
+                                                }
+                                            }
+                                            td {
+                                                @if let Some(item) = &function.other {
+                                                    (format_percent(item.fuzzy_match_percent))
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    @if !comparison.units.is_empty() {
+                        h4 { "Units with different matched code" }
+                        .overflow-auto {
+                            table.striped {
+                                thead {
+                                    tr {
+                                        th { "Unit" }
+                                        th { (base_version) }
+                                        th { (other_version) }
+                                    }
+                                }
+                                tbody {
+                                    @for unit in comparison.units.iter().take(MAX_COMPARE_ROWS) {
+                                        tr {
+                                            td { (unit.name) }
+                                            td {
+                                                @if let Some(measures) = &unit.base {
+                                                    a href=(unit_path(base_version, &unit.name)) { (format_percent(measures.matched_code_percent)) }
+                                                } @else {
+                                                    span.muted { "Missing" }
+                                                }
+                                            }
+                                            td {
+                                                @if let Some(measures) = &unit.other {
+                                                    a href=(unit_path(other_version, &unit.name)) { (format_percent(measures.matched_code_percent)) }
+                                                } @else {
+                                                    span.muted { "Missing" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    hr;
+                    div role="group" {
+                        a role="button" href=(project_base_path) { "Back to report" }
+                    }
+                }
+            }
+            (ctx.footer(current_user.as_ref()))
+        }
+    };
+    Ok((ctx, rendered).into_response())
+}
+
+#[cfg(test)]
+mod test {
+    use objdiff_core::bindings::report::ReportItem;
+
+    use super::*;
+
+    #[test]
+    fn test_etag_matches() {
+        let etag = HeaderValue::from_static("\"abc\"");
+        let cases: &[(&[&str], bool)] = &[
+            (&[], false),
+            (&["\"abc\""], true),
+            (&["W/\"abc\""], true),
+            (&["*"], true),
+            (&["\"def\", \"abc\""], true),
+            (&["\"def\"", "\"abc\""], true),
+            (&["\"def\""], false),
+            (&["abc"], false),
+        ];
+        for &(values, expected) in cases {
+            let mut headers = HeaderMap::new();
+            for value in values {
+                headers.append(header::IF_NONE_MATCH, HeaderValue::from_str(value).unwrap());
+            }
+            assert_eq!(etag_matches(&headers, &etag), expected, "{values:?}");
+        }
+    }
+
+    #[test]
+    fn test_path_remainder() {
+        assert_eq!(path_remainder("src/game/main.c", ""), Some("src/game/main.c"));
+        assert_eq!(path_remainder("src/game/main.c", "src"), Some("game/main.c"));
+        assert_eq!(path_remainder("src/game/main.c", "src/game"), Some("main.c"));
+        assert_eq!(path_remainder("src/gameplay/main.c", "src/game"), None);
+        assert_eq!(path_remainder("lib/main.c", "src"), None);
+    }
+
+    #[test]
+    fn test_resolve_unit_path() {
+        let unit = |name: &str| ReportUnit { name: name.to_string(), ..Default::default() };
+        let nested = [unit("src/game/a.c"), unit("src/game/b.c")];
+        let nested = nested.iter().collect::<Vec<_>>();
+        assert_eq!(resolve_unit_path(&nested, ""), "src/game");
+        assert_eq!(resolve_unit_path(&nested, "/src/"), "src/game");
+
+        let split = [unit("src/game/a.c"), unit("src/util/b.c"), unit("src/c.c")];
+        let split = split.iter().collect::<Vec<_>>();
+        assert_eq!(resolve_unit_path(&split, ""), "src");
+        assert_eq!(resolve_unit_path(&split, "src/util"), "src/util");
+
+        let flat = [unit("a.c"), unit("src/b.c")];
+        let flat = flat.iter().collect::<Vec<_>>();
+        assert_eq!(resolve_unit_path(&flat, ""), "");
+    }
+
+    #[test]
+    fn test_previous_fuzzy_match_percent() {
+        let unit = |name: &str, fuzzy_match_percent: f32, total_code: u64| ReportUnit {
+            name: name.to_string(),
+            measures: Some(Measures { fuzzy_match_percent, total_code, ..Default::default() }),
+            ..Default::default()
+        };
+        let previous = [unit("a.c", 50.0, 100), unit("b.c", 100.0, 300)];
+        let current = [unit("a.c", 60.0, 100), unit("b.c", 100.0, 300), unit("new.c", 0.0, 50)];
+        let mut ctx = ColorContext {
+            mode: ColorMode::Recent,
+            categories: Vec::new(),
+            matchers: HashMap::new(),
+            previous_units: None,
+        };
+        assert_eq!(ctx.previous_fuzzy_match_percent(&current), None);
+
+        ctx.previous_units = Some(previous.iter().map(|u| (u.name.as_str(), u)).collect());
+        assert_eq!(ctx.previous_fuzzy_match_percent(&current[..1]), Some(50.0));
+        assert_eq!(ctx.previous_fuzzy_match_percent(&current), Some(87.5));
+        assert_eq!(ctx.previous_fuzzy_match_percent(&current[2..]), None);
+    }
+
+    #[test]
+    fn test_rank_targets() {
+        let function = |name: &str, size: u64, fuzzy_match_percent: f32| ReportItem {
+            name: name.to_string(),
+            size,
+            fuzzy_match_percent,
+            ..Default::default()
+        };
+        let units = [
+            ReportUnit {
+                name: "a.c".to_string(),
+                functions: vec![
+                    function("matched", 100, 100.0),
+                    function("empty", 0, 0.0),
+                    function("large", 1000, 0.0),
+                    function("close", 100, 99.5),
+                    function("half", 20, 50.0),
+                ],
+                ..Default::default()
+            },
+            ReportUnit {
+                name: "b.c".to_string(),
+                functions: vec![function("tie_low", 10, 0.0), function("tie_high", 20, 50.0)],
+                ..Default::default()
+            },
+        ];
+        let targets = rank_targets(&units);
+        let names = targets.iter().map(|t| (t.unit, t.name, t.remaining_code)).collect::<Vec<_>>();
+        assert_eq!(names, [
+            ("a.c", "close", 1),
+            ("a.c", "half", 10),
+            ("b.c", "tie_high", 10),
+            ("b.c", "tie_low", 10),
+            ("a.c", "large", 1000),
+        ]);
+    }
+
+    #[test]
+    fn test_targets_csv() {
+        let target = |unit, name, demangled_name, source_path| TargetFunction {
+            unit,
+            name,
+            demangled_name,
+            size: 16,
+            fuzzy_match_percent: 50.0,
+            remaining_code: 8,
+            source_path,
+        };
+        let csv = targets_csv(&[
+            target("main.o", "main", None, None),
+            target("a.o", "func<int, int>", Some("say \"hi\"\n"), Some("src/a.c")),
+        ]);
+        assert_eq!(
+            csv,
+            "unit,name,demangled_name,size,fuzzy_match_percent,remaining_code,source_path\n\
+             main.o,main,,16,50,8,\n\
+             a.o,\"func<int, int>\",\"say \"\"hi\"\"\n\",16,50,8,src/a.c\n"
+        );
+    }
+
+    #[test]
+    fn test_function_comparison() {
+        let info = |fuzzy_match_percent: f32| {
+            ChangeItemInfo::from(&ReportItem {
+                size: 16,
+                fuzzy_match_percent,
+                ..Default::default()
+            })
+        };
+        let (matched, partial, other_partial) = (info(100.0), info(50.0), info(75.0));
+        let cases = [
+            (Some(&matched), Some(&partial), FunctionComparison::MatchedInBase),
+            (Some(&partial), Some(&matched), FunctionComparison::MatchedInOther),
+            (Some(&partial), Some(&other_partial), FunctionComparison::Different),
+            (Some(&matched), Some(&matched), FunctionComparison::Different),
+            (Some(&partial), None, FunctionComparison::OnlyInBase),
+            (None, Some(&partial), FunctionComparison::OnlyInOther),
+        ];
+        for (base, other, expected) in cases {
+            assert_eq!(FunctionComparison::classify(base, other), expected);
+        }
+    }
+}
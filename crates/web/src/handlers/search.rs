@@ -0,0 +1,227 @@
+use anyhow::Context;
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use decomp_dev_auth::CurrentUser;
+use decomp_dev_core::{
+    AppError, FullUri,
+    models::{SymbolKind, SymbolMatch},
+    util::{UrlExt, format_percent, size},
+};
+use maud::{DOCTYPE, html};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    AppState,
+    handlers::{
+        common::{Load, TemplateContext, nav_links},
+        parse_accept,
+        project::extract_extension,
+    },
+};
+
+const MIN_QUERY_LEN: usize = 3;
+const DEFAULT_LIMIT: u32 = 100;
+const MAX_LIMIT: u32 = 1000;
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    q: Option<String>,
+    /// Restrict results to units or functions
+    kind: Option<String>,
+    limit: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub struct SearchResponse {
+    pub results: Vec<SymbolResponse>,
+}
+
+#[derive(Serialize)]
+pub struct SymbolResponse {
+    pub project_id: u64,
+    pub owner: String,
+    pub repo: String,
+    pub version: String,
+    pub commit: String,
+    pub kind: SymbolKind,
+    pub unit: String,
+    pub name: String,
+    pub demangled_name: Option<String>,
+    pub size: u64,
+    pub fuzzy_match_percent: f32,
+    /// Link to the unit in the report view
+    pub url: String,
+}
+
+fn unit_url(base: &Url, symbol: &SymbolMatch) -> Url {
+    base.with_path(&format!("/{}/{}/{}", symbol.project_owner, symbol.project_repo, symbol.version))
+        .query_param("unit", Some(&symbol.unit_name))
+}
+
+pub async fn search(
+    ctx: TemplateContext,
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+    FullUri(uri): FullUri,
+    current_user: Option<CurrentUser>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let ext = extract_extension(&uri);
+    let acceptable = parse_accept(&headers, ext.as_deref());
+    if acceptable.is_empty() {
+        return Err(AppError::Status(StatusCode::NOT_ACCEPTABLE));
+    }
+
+    let kind = match query.kind.as_deref().filter(|s| !s.is_empty()) {
+        Some(kind) => Some(
+            kind.parse::<SymbolKind>().map_err(|_| AppError::Status(StatusCode::BAD_REQUEST))?,
+        ),
+        None => None,
+    };
+    let text = query.q.as_deref().map(str::trim).unwrap_or_default();
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let results = if text.chars().count() >= MIN_QUERY_LEN {
+        state.db.search_symbols(text, kind, limit).await?
+    } else {
+        vec![]
+    };
+
+    let request_url = Url::parse(&uri.to_string()).context("Failed to parse URI")?;
+    for mime in acceptable {
+        if (mime.type_() == mime::STAR && mime.subtype() == mime::STAR)
+            || (mime.type_() == mime::TEXT && mime.subtype() == mime::HTML)
+        {
+            return render_search(ctx, &request_url, text, kind, &results, current_user.as_ref())
+                .await;
+        } else if mime.type_() == mime::APPLICATION && mime.subtype() == mime::JSON {
+            if text.chars().count() < MIN_QUERY_LEN {
+                return Err(AppError::Status(StatusCode::BAD_REQUEST));
+            }
+            let results = results
+                .into_iter()
+                .map(|symbol| SymbolResponse {
+                    url: unit_url(&request_url, &symbol).to_string(),
+                    project_id: symbol.project_id,
+                    owner: symbol.project_owner,
+                    repo: symbol.project_repo,
+                    version: symbol.version,
+                    commit: symbol.commit_sha,
+                    kind: symbol.kind,
+                    unit: symbol.unit_name,
+                    name: symbol.name,
+                    demangled_name: symbol.demangled_name,
+                    size: symbol.size,
+                    fuzzy_match_percent: symbol.fuzzy_match_percent,
+                })
+                .collect();
+            return Ok(Json(SearchResponse { results }).into_response());
+        }
+    }
+    Err(AppError::Status(StatusCode::NOT_ACCEPTABLE))
+}
+
+async fn render_search(
+    mut ctx: TemplateContext,
+    request_url: &Url,
+    text: &str,
+    kind: Option<SymbolKind>,
+    results: &[SymbolMatch],
+    current_user: Option<&CurrentUser>,
+) -> Result<Response, AppError> {
+    let rendered = html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                title { "Search • decomp.dev" }
+                (ctx.header().await)
+                (ctx.chunks("main", Load::Deferred).await)
+                meta name="robots" content="noindex";
+            }
+            body {
+                header {
+                    nav {
+                        ul {
+                            li {
+                                a href="/" { strong { "decomp.dev" } }
+                            }
+                            li {
+                                a href="/search" { "Search" }
+                            }
+                        }
+                        (nav_links())
+                    }
+                }
+                main {
+                    h1 { "Symbol search" }
+                    form method="get" action="/search" role="search" {
+                        input type="search" name="q" value=(text) minlength=(MIN_QUERY_LEN)
+                            placeholder="Unit or function name" aria-label="Search";
+                        select name="kind" aria-label="Kind" {
+                            option value="" selected[kind.is_none()] { "All" }
+                            option value="unit" selected[kind == Some(SymbolKind::Unit)] { "Units" }
+                            option value="function" selected[kind == Some(SymbolKind::Function)] {
+                                "Functions"
+                            }
+                        }
+                        input type="submit" value="Search";
+                    }
+                    @if text.chars().count() < MIN_QUERY_LEN {
+                        p.muted {
+                            "Search the latest report of every project by unit or function name."
+                            " Enter at least " (MIN_QUERY_LEN) " characters."
+                        }
+                    } @else if results.is_empty() {
+                        p.muted { "No matching symbols found." }
+                    } @else {
+                        table.striped {
+                            thead {
+                                tr {
+                                    th { "Project" }
+                                    th { "Version" }
+                                    th { "Symbol" }
+                                    th { "Unit" }
+                                    th { "Size" }
+                                    th { "Matched" }
+                                }
+                            }
+                            tbody {
+                                @for symbol in results {
+                                    tr {
+                                        td {
+                                            a href=(format!("/{}/{}", symbol.project_owner, symbol.project_repo)) {
+                                                (symbol.project_name.as_deref().unwrap_or(&symbol.project_repo))
+                                            }
+                                        }
+                                        td { (symbol.version) }
+                                        td {
+                                            @if let Some(demangled_name) = &symbol.demangled_name {
+                                                code title=(symbol.name) { (demangled_name) }
+                                            } @else {
+                                                code { (symbol.name) }
+                                            }
+                                        }
+                                        td {
+                                            a href=(unit_url(request_url, symbol).path_and_query()) {
+                                                (symbol.unit_name)
+                                            }
+                                        }
+                                        td { (size(symbol.size)) }
+                                        td { (format_percent(symbol.fuzzy_match_percent)) }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                (ctx.footer(current_user))
+            }
+        }
+    };
+    Ok((ctx, rendered).into_response())
+}
@@ -1,35 +1,127 @@
+use std::{fmt, str::FromStr};
+
 use anyhow::Result;
 use decomp_dev_images::{
     svg,
-    treemap::{color_mix, hsl, html_color},
+    treemap::{html_color, hue_colors, progress_colors},
 };
 use image::ImageFormat;
 use maud::{PreEscaped, html};
 
 use crate::handlers::report::ReportTemplateUnit;
 
+/// Treemap coloring mode, selected with the `color` query parameter.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum ColorMode {
+    #[default]
+    Fuzzy,
+    Linked,
+    Recent,
+    Data,
+    Category,
+}
+
+impl ColorMode {
+    pub const fn variants() -> &'static [Self] {
+        &[Self::Fuzzy, Self::Linked, Self::Recent, Self::Data, Self::Category]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fuzzy => "fuzzy",
+            Self::Linked => "linked",
+            Self::Recent => "recent",
+            Self::Data => "data",
+            Self::Category => "category",
+        }
+    }
+
+    /// Returns the inner and outer gradient colors for a treemap node.
+    pub fn colors(&self, stats: &NodeStats) -> (String, String) {
+        let (inner, outer) = match self {
+            Self::Fuzzy => progress_colors(stats.fuzzy_match_percent),
+            Self::Linked => progress_colors(if stats.is_linked { 100.0 } else { 0.0 }),
+            Self::Recent => match stats.previous_fuzzy_match_percent {
+                Some(previous) if stats.fuzzy_match_percent > previous + 0.01 => hue_colors(120),
+                Some(previous) if stats.fuzzy_match_percent < previous - 0.01 => hue_colors(0),
+                // Unchanged or new nodes are shown in a neutral color
+                _ => progress_colors(0.0),
+            },
+            Self::Data => progress_colors(stats.matched_data_percent.unwrap_or(0.0)),
+            Self::Category => match stats.category {
+                // Spread hues using the golden angle so neighboring indices stay distinct
+                Some(index) => hue_colors(((200 + index * 137) % 360) as u16),
+                None => progress_colors(0.0),
+            },
+        };
+        (html_color(inner), html_color(outer))
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fuzzy" => Ok(Self::Fuzzy),
+            "linked" => Ok(Self::Linked),
+            "recent" => Ok(Self::Recent),
+            "data" => Ok(Self::Data),
+            "category" => Ok(Self::Category),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Fuzzy => "Match",
+            Self::Linked => "Linked",
+            Self::Recent => "Recent changes",
+            Self::Data => "Data match",
+            Self::Category => "Category",
+        })
+    }
+}
+
+/// Values used to determine the color of a treemap node.
+#[derive(Default)]
+pub struct NodeStats {
+    pub fuzzy_match_percent: f32,
+    /// None if the node has no data
+    pub matched_data_percent: Option<f32>,
+    pub is_linked: bool,
+    /// None if there is no previous report to compare against, or the node is new since then
+    pub previous_fuzzy_match_percent: Option<f32>,
+    /// Index into the list of categories being colored
+    pub category: Option<usize>,
+}
+
 pub fn render_svg(units: &[ReportTemplateUnit], w: u32, h: u32) -> String {
-    let complete_c0 = html_color(hsl(120, 100, 39));
-    let complete_c1 = html_color(hsl(120, 100, 17));
+    render_svg_with_caption(units, w, h, None)
+}
+
+/// Renders the treemap with an optional caption in the bottom right corner.
+pub fn render_svg_with_caption(
+    units: &[ReportTemplateUnit],
+    w: u32,
+    h: u32,
+    caption: Option<&str>,
+) -> String {
     html! {
         (PreEscaped("<?xml version=\"1.0\" encoding=\"utf-8\"?>"))
         svg xmlns="http://www.w3.org/2000/svg" version="1.1" viewBox=(format!("0 0 {w} {h}")) width=(w) height=(h) {
             style { ".unit { stroke: #000; stroke-width: 0.5; }" }
             @for (i, unit) in units.iter().enumerate() {
-                radialGradient id=(format!("unit-{i}")) 
+                radialGradient id=(format!("unit-{i}"))
                     gradientUnits="userSpaceOnUse"
                     cx=(format!("{}%", (unit.x + (unit.w * 0.4)) * 100.0))
-                    cy=(format!("{}%", (unit.y + (unit.h * 0.4)) * 100.0)) 
-                    fr=(format!("{}%", (unit.w + unit.h) * 10.0)) 
+                    cy=(format!("{}%", (unit.y + (unit.h * 0.4)) * 100.0))
+                    fr=(format!("{}%", (unit.w + unit.h) * 10.0))
                     r=(format!("{}%", (unit.w + unit.h) * 50.0)) {
-                    @let pct = unit.fuzzy_match_percent;
-                    @if pct == 100.0 {
-                        stop offset="0%" stop-color=(complete_c0) {}
-                        stop offset="100%" stop-color=(complete_c1) {}
-                    } @else {
-                        stop offset="0%" stop-color=(html_color(color_mix(hsl(200, 0, 21), hsl(200, 100, 35), pct / 100.0))) {}
-                        stop offset="100%" stop-color=(html_color(color_mix(hsl(200, 0, 15), hsl(200, 100, 15), pct / 100.0))) {}
-                    }
+                    stop offset="0%" stop-color=(unit.color) {}
+                    stop offset="100%" stop-color=(unit.outer_color) {}
                 }
             }
             @for (i, unit) in units.iter().enumerate() {
@@ -40,6 +132,13 @@ pub fn render_svg(units: &[ReportTemplateUnit], w: u32, h: u32) -> String {
                     y=(format!("{}%", unit.y * 100.0))
                     fill=(format!("url(#unit-{i})")) {}
             }
+            @if let Some(caption) = caption {
+                text x=(w.saturating_sub(12)) y=(h.saturating_sub(12)) text-anchor="end"
+                    font-family="sans-serif" font-size="20" font-weight="bold" fill="#fff"
+                    stroke="#000" stroke-width="3" paint-order="stroke" {
+                    (caption)
+                }
+            }
         }
     }
     .into_string()
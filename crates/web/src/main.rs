@@ -3,6 +3,7 @@ mod cron;
 mod frogress;
 mod handlers;
 mod proto;
+mod telemetry;
 
 use std::{
     fs::File,
@@ -24,11 +25,13 @@ use axum::{
     extract::{ConnectInfo, FromRef},
     http::{Method, Request, StatusCode, header},
     middleware,
+    routing::get,
 };
 use decomp_dev_core::config::Config;
 use decomp_dev_db::Database;
 use decomp_dev_github::GitHub;
 use decomp_dev_jobs::{JobContext, JobStorage, create_monitor};
+use metrics_exporter_prometheus::PrometheusHandle;
 use tokio::{net::TcpListener, signal};
 use tower::ServiceBuilder;
 use tower_http::{
@@ -53,30 +56,43 @@ pub struct AppState {
     db: Arc<Database>,
     github: Arc<GitHub>,
     jobs: Arc<JobStorage>,
+    metrics: PrometheusHandle,
 }
 
 #[tokio::main]
 async fn main() {
+    let config: Arc<Config> = {
+        let file = BufReader::new(File::open("config.yml").expect("Failed to open config file"));
+        serde_yaml::from_reader(file).expect("Failed to parse config file")
+    };
+
     let broadcaster = TracingBroadcaster::create();
     let env_filter = EnvFilter::builder()
         // Default to info level
         .with_default_directive(LevelFilter::INFO.into())
         .from_env_lossy();
+    let (tracer_provider, otel_layer) = match &config.telemetry {
+        Some(telemetry) => {
+            let (provider, layer) =
+                telemetry::init(telemetry).expect("Failed to initialize telemetry");
+            (Some(provider), Some(layer.with_filter(env_filter.clone())))
+        }
+        None => (None, None),
+    };
     tracing_subscriber::registry()
         .with(tracing_subscriber::fmt::layer().with_filter(env_filter.clone()))
         .with(TracingSubscriber::new(&broadcaster).layer())
+        .with(otel_layer)
         .init();
 
-    let config: Arc<Config> = {
-        let file = BufReader::new(File::open("config.yml").expect("Failed to open config file"));
-        serde_yaml::from_reader(file).expect("Failed to parse config file")
-    };
+    let metrics =
+        handlers::metrics::install_recorder().expect("Failed to install metrics recorder");
     let db = Database::new(&config.db).await.expect("Failed to open database");
     let github = GitHub::new(&config.github).await.expect("Failed to create GitHub client");
     let jobs = JobStorage::setup(&config.db).await.expect("Failed to set up job storage");
 
     let job_context = JobContext { config: config.clone(), db: db.clone(), github: github.clone() };
-    let state = AppState { config: config.clone(), db: db.clone(), github, jobs };
+    let state = AppState { config: config.clone(), db: db.clone(), github, jobs, metrics };
 
     // Create session store
     let session_store = SqliteStore::new(db.pool.clone());
@@ -86,6 +102,16 @@ async fn main() {
     // cron::refresh_projects(&mut state).await.expect("Failed to refresh projects");
     // frogress::migrate_data(&mut state).await.expect("Failed to migrate data");
 
+    // Generate variants for images stored before they were pre-generated
+    {
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handlers::backfill_image_variants(&state).await {
+                tracing::error!("Failed to backfill image variants: {:?}", e);
+            }
+        });
+    }
+
     // Start the task scheduler
     let mut scheduler = cron::create(state.clone(), session_store.clone())
         .await
@@ -97,7 +123,7 @@ async fn main() {
     // Build the router
     let port = state.config.server.port;
     let jobs_port = state.config.server.jobs_port;
-    let jobs_router = jobs_app(&state.jobs, broadcaster);
+    let jobs_router = jobs_app(&state, broadcaster);
     let router = app(state, session_store).into_make_service_with_connect_info::<SocketAddr>();
 
     // Create the listener
@@ -183,6 +209,11 @@ async fn main() {
 
     scheduler.shutdown().await.expect("Failed to shut down scheduler");
     db.close().await;
+    if let Some(provider) = tracer_provider
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::error!("Failed to shut down tracer provider: {e}");
+    }
     tracing::info!("Shut down gracefully");
 }
 
@@ -216,14 +247,15 @@ fn app(state: AppState, session_store: impl SessionStore + Clone) -> Router {
     router.layer(middleware)
 }
 
-fn jobs_app(jobs: &JobStorage, broadcaster: Arc<Mutex<TracingBroadcaster>>) -> Router {
+fn jobs_app(state: &AppState, broadcaster: Arc<Mutex<TracingBroadcaster>>) -> Router {
     let middleware =
         ServiceBuilder::new().layer(NormalizePathLayer::trim_trailing_slash()).compression();
     let api = ApiBuilder::new(Router::new())
-        .register(jobs.workflow_run())
-        .register(jobs.refresh_project())
+        .register(state.jobs.workflow_run())
+        .register(state.jobs.refresh_project())
         .build();
     Router::new()
+        .route("/metrics", get(handlers::metrics::get_metrics).with_state(state.clone()))
         .nest("/api/v1", api)
         .fallback_service(ServeUI::new())
         .layer(Extension(broadcaster))
@@ -283,12 +315,14 @@ impl<B> MakeSpan<B> for MyMakeSpan {
                 )
             }
         }
-        match self.level {
+        let span = match self.level {
             Level::ERROR => make_span!(Level::ERROR),
             Level::WARN => make_span!(Level::WARN),
             Level::INFO => make_span!(Level::INFO),
             Level::DEBUG => make_span!(Level::DEBUG),
             Level::TRACE => make_span!(Level::TRACE),
-        }
+        };
+        telemetry::set_remote_parent(&span, request.headers());
+        span
     }
 }
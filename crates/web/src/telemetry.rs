@@ -0,0 +1,52 @@
+use anyhow::{Context, Result};
+use axum::http::HeaderMap;
+use decomp_dev_core::config::TelemetryConfig;
+use opentelemetry::{global, propagation::Extractor, trace::TracerProvider};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource,
+    propagation::TraceContextPropagator,
+    trace::{Sampler, SdkTracerProvider},
+};
+use tracing::Span;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Creates the OTLP exporter and the tracing layer that feeds it.
+///
+/// The returned provider must be shut down on exit to flush pending spans.
+pub fn init<S>(
+    config: &TelemetryConfig,
+) -> Result<(SdkTracerProvider, OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>)>
+where S: tracing::Subscriber + for<'span> LookupSpan<'span> {
+    let exporter = SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(&config.otlp_endpoint)
+        .build()
+        .context("Failed to create OTLP exporter")?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sample_ratio,
+        ))))
+        .with_resource(Resource::builder().with_service_name(config.service_name.clone()).build())
+        .build();
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+    let tracer = provider.tracer("decomp-dev-web");
+    Ok((provider, tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+/// Continues a trace started by the caller, if the request carries a `traceparent` header.
+pub fn set_remote_parent(span: &Span, headers: &HeaderMap) {
+    let context = global::get_text_map_propagator(|p| p.extract(&HeaderExtractor(headers)));
+    span.set_parent(context);
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> { self.0.get(key).and_then(|v| v.to_str().ok()) }
+
+    fn keys(&self) -> Vec<&str> { self.0.keys().map(|k| k.as_str()).collect() }
+}
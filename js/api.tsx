@@ -499,6 +499,7 @@ const ProjectForm = () => {
             </option>
             <option value="shield">Shield (Progress badge)</option>
             <option value="history">History (Historical report data)</option>
+            <option value="timelapse">Timelapse (Animated treemap)</option>
           </select>
         </label>
         <label>
@@ -534,6 +535,18 @@ const ProjectForm = () => {
                       PNG
                     </option>
                   );
+                case 'webp':
+                  return (
+                    <option key={format} value={format}>
+                      WebP
+                    </option>
+                  );
+                case 'gif':
+                  return (
+                    <option key={format} value={format}>
+                      GIF
+                    </option>
+                  );
               }
             })}
           </select>
@@ -563,6 +576,7 @@ const formatsByMode: Record<string, string[]> = {
   report: ['json', 'binpb'],
   shield: ['svg', 'png', 'json'],
   history: ['json'],
+  timelapse: ['webp', 'gif'],
 };
 
 const prettifyJson = (data: string): string => {
@@ -649,7 +663,12 @@ const ApiPreview = ({
     preview = <CodeBlock value={error} language="plaintext" />;
   }
 
-  if (format === 'svg' || format === 'png') {
+  if (
+    format === 'svg' ||
+    format === 'png' ||
+    format === 'webp' ||
+    format === 'gif'
+  ) {
     preview = <img src={url} alt="Preview" />;
   } else if (format === 'json' || format === 'binpb') {
     preview = (
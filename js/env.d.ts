@@ -5,11 +5,13 @@ type Unit = {
   fuzzy_match_percent: number;
   total_code: number;
   color: string;
+  outer_color: string;
   x: number;
   y: number;
   w: number;
   h: number;
   is_linked: boolean;
+  is_dir: boolean;
   // Runtime fields
   filtered: boolean;
 };
@@ -39,7 +41,21 @@ type ReportHistoryEntry = {
   measures: Measures;
 };
 
+type ForecastEntry = {
+  window: 'recent' | 'all_time';
+  samples: number;
+  rate_per_day: number;
+  current_percent: number;
+  estimate: string | null;
+  earliest: string | null;
+  latest: string | null;
+};
+
 interface Window {
   drawTreemap: (id: string, clickable: boolean, units: Unit[]) => void;
-  renderChart: (id: string, data: ReportHistoryEntry[]) => void;
+  renderChart: (
+    id: string,
+    data: ReportHistoryEntry[],
+    forecasts?: ForecastEntry[],
+  ) => void;
 }
@@ -16,7 +16,11 @@ function percentValue(
   return rawValue == null ? '' : formatPercent(rawValue);
 }
 
-function renderChart(id: string, data: ReportHistoryEntry[]) {
+function renderChart(
+  id: string,
+  data: ReportHistoryEntry[],
+  forecasts: ForecastEntry[] = [],
+) {
   const chart = document.getElementById(id);
   if (!chart) {
     console.error(`Chart element with id ${id} not found`);
@@ -25,6 +29,20 @@ function renderChart(id: string, data: ReportHistoryEntry[]) {
 
   data.reverse();
 
+  // Extend the matched code line from the latest report to the estimated completion
+  const x = data.map((e) => Date.parse(e.timestamp) / 1000);
+  const projected: (number | null)[] = data.map(() => null);
+  const forecast =
+    forecasts.find((f) => f.window === 'recent') ?? forecasts[0];
+  const latest = data[data.length - 1];
+  if (forecast?.estimate && latest) {
+    projected[projected.length - 1] = latest.measures.matched_code_percent;
+    x.push(Date.parse(forecast.estimate) / 1000);
+    projected.push(100);
+  }
+  const extend = (values: (number | null)[]) =>
+    values.concat(Array(x.length - values.length).fill(null));
+
   function getSize() {
     const container = chart!.parentElement;
     if (container) {
@@ -77,6 +95,14 @@ function renderChart(id: string, data: ReportHistoryEntry[]) {
           stroke: '#58508d',
           value: percentValue,
         },
+        {
+          label: 'Projected',
+          width: 2,
+          stroke: '#ff6361',
+          dash: [6, 6],
+          spanGaps: true,
+          value: percentValue,
+        },
       ],
       axes: [
         {
@@ -104,12 +130,13 @@ function renderChart(id: string, data: ReportHistoryEntry[]) {
       },
     },
     [
-      data.map((e) => Date.parse(e.timestamp) / 1000),
-      data.map((e) => e.measures.fuzzy_match_percent || null),
-      data.map((e) => e.measures.matched_code_percent || null),
-      data.map((e) => e.measures.matched_data_percent || null),
-      data.map((e) => e.measures.complete_code_percent || null),
-      data.map((e) => e.measures.complete_data_percent || null),
+      x,
+      extend(data.map((e) => e.measures.fuzzy_match_percent || null)),
+      extend(data.map((e) => e.measures.matched_code_percent || null)),
+      extend(data.map((e) => e.measures.matched_data_percent || null)),
+      extend(data.map((e) => e.measures.complete_code_percent || null)),
+      extend(data.map((e) => e.measures.complete_data_percent || null)),
+      projected,
     ],
     chart,
   );
@@ -158,22 +158,13 @@ const drawUnits = (
   for (const unit of units) {
     const { x, y, w, h } = unitBounds(unit, width, height);
 
-    let innerColor: string;
-    let outerColor: string;
-    if (unit.fuzzy_match_percent === 100.0) {
-      innerColor = 'hsl(120 100% 39%)';
-      outerColor = 'hsl(120 100% 17%)';
-    } else {
-      innerColor = `color-mix(in srgb, hsl(200 0% 21%), hsl(200 100% 35%) ${unit.fuzzy_match_percent}%)`;
-      outerColor = `color-mix(in srgb, hsl(200 0% 15%), hsl(200 100% 15%) ${unit.fuzzy_match_percent}%)`;
-    }
     const cx = x + w * 0.4;
     const cy = y + h * 0.4;
     const r0 = (w + h) * 0.1;
     const r1 = (w + h) * 0.5;
     const gradient = ctx.createRadialGradient(cx, cy, r0, cx, cy, r1);
-    gradient.addColorStop(0, innerColor);
-    gradient.addColorStop(1, outerColor);
+    gradient.addColorStop(0, unit.color);
+    gradient.addColorStop(1, unit.outer_color);
     ctx.fillStyle = gradient;
 
     ctx.beginPath();
@@ -373,7 +364,11 @@ const drawTreemap = (id: string, clickable: boolean, units: Unit[]) => {
       return;
     }
     const url = new URL(window.location.href);
-    url.searchParams.set('unit', unit.name);
+    if (unit.is_dir) {
+      url.searchParams.set('path', unit.name);
+    } else {
+      url.searchParams.set('unit', unit.name);
+    }
     url.searchParams.delete('filter');
     window.location.href = url.toString();
   });
@@ -0,0 +1,15 @@
+CREATE TABLE image_variants
+(
+    image_id   BLOB      NOT NULL, -- Original image ID
+    name       TEXT      NOT NULL, -- Variant name (e.g., header, thumbnail)
+    mime_type  TEXT      NOT NULL, -- MIME type of the variant
+    width      INTEGER   NOT NULL, -- Width of the variant in pixels
+    height     INTEGER   NOT NULL, -- Height of the variant in pixels
+    blur       REAL,               -- Blur sigma applied to the variant, if any
+    data       BLOB      NOT NULL, -- Variant image data
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (image_id, name, mime_type),
+    FOREIGN KEY (image_id) REFERENCES images (id) ON DELETE CASCADE
+);
+
+CREATE INDEX image_variants_lookup ON image_variants (image_id, width, height, mime_type);
@@ -0,0 +1,14 @@
+CREATE TABLE audit_log
+(
+    id         INTEGER PRIMARY KEY,
+    user_id    INTEGER   NOT NULL, -- GitHub user ID of the acting user
+    user_login TEXT      NOT NULL, -- GitHub login of the acting user
+    project_id INTEGER   NOT NULL, -- Affected project (not a foreign key, to outlive deleted projects)
+    action     TEXT      NOT NULL, -- Action performed (e.g., create_project, update_project)
+    changes    TEXT      NOT NULL, -- JSON array of changed project fields with before/after values
+    details    TEXT,               -- Additional action-specific details
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX audit_log_project_id_index ON audit_log (project_id, created_at);
+CREATE INDEX audit_log_created_at_index ON audit_log (created_at);
@@ -0,0 +1,12 @@
+CREATE TABLE project_maintainers
+(
+    project_id INTEGER   NOT NULL,
+    user_id    INTEGER   NOT NULL, -- GitHub user ID
+    user_login TEXT      NOT NULL, -- GitHub login at the time the maintainer was added
+    role       TEXT      NOT NULL, -- Maintainer role (maintainer, admin)
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (project_id, user_id),
+    FOREIGN KEY (project_id) REFERENCES projects (id) ON DELETE CASCADE
+);
+
+CREATE INDEX project_maintainers_user_id_index ON project_maintainers (user_id);
@@ -0,0 +1 @@
+ALTER TABLE reports ADD COLUMN ingested_at TIMESTAMP; -- Time the report was last inserted, NULL for older reports
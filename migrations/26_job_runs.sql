@@ -0,0 +1,18 @@
+CREATE TABLE job_runs
+(
+    id               INTEGER PRIMARY KEY,
+    project_id       INTEGER   NOT NULL, -- GitHub repository ID
+    kind             TEXT      NOT NULL, -- Job kind (workflow_run, refresh_project)
+    run_id           INTEGER,            -- GitHub Actions workflow run ID
+    event            TEXT,               -- Event that triggered the workflow run (push, pull_request)
+    commit_sha       TEXT,               -- Git commit SHA of the workflow run
+    artifacts        INTEGER,            -- Number of report artifacts found, if known
+    reports_inserted INTEGER   NOT NULL, -- Number of reports inserted
+    versions         TEXT      NOT NULL, -- JSON array of inserted report versions
+    skip_reason      TEXT,               -- Why the job did nothing, if skipped
+    error            TEXT,               -- Error chain, one cause per line, if the job failed
+    started_at       TIMESTAMP NOT NULL,
+    finished_at      TIMESTAMP NOT NULL
+);
+
+CREATE INDEX job_runs_project_id_index ON job_runs (project_id, id);
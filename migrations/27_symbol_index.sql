@@ -0,0 +1,16 @@
+CREATE TABLE symbol_index
+(
+    report_id           INTEGER NOT NULL, -- Latest report of the project version
+    project_id          INTEGER NOT NULL,
+    version             TEXT    NOT NULL COLLATE NOCASE,
+    kind                TEXT    NOT NULL, -- Symbol kind (unit, function)
+    unit_name           TEXT    NOT NULL, -- Containing unit, or the unit itself
+    name                TEXT    NOT NULL COLLATE NOCASE,
+    demangled_name      TEXT COLLATE NOCASE,
+    size                INTEGER NOT NULL, -- Code size in bytes
+    fuzzy_match_percent REAL    NOT NULL,
+    FOREIGN KEY (report_id) REFERENCES reports (id) ON DELETE CASCADE
+);
+
+CREATE INDEX symbol_index_report_id_index ON symbol_index (report_id);
+CREATE INDEX symbol_index_project_version_index ON symbol_index (project_id, version);
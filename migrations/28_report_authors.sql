@@ -0,0 +1,7 @@
+ALTER TABLE reports ADD COLUMN git_author_name TEXT;          -- Git author name of the commit
+ALTER TABLE reports ADD COLUMN git_author_login TEXT;         -- GitHub login of the commit author
+ALTER TABLE reports ADD COLUMN pr_number INTEGER;             -- Pull request that merged the commit
+ALTER TABLE reports ADD COLUMN pr_author_login TEXT;          -- GitHub login of the pull request author
+ALTER TABLE reports ADD COLUMN matched_code_delta INTEGER;    -- Change in matched code since the previous report, NULL for the first
+ALTER TABLE reports ADD COLUMN matched_data_delta INTEGER;    -- Change in matched data since the previous report
+ALTER TABLE reports ADD COLUMN matched_functions_delta INTEGER; -- Change in matched functions since the previous report
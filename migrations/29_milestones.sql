@@ -0,0 +1,18 @@
+CREATE TABLE milestones
+(
+    id              INTEGER PRIMARY KEY,
+    project_id      INTEGER   NOT NULL,
+    name            TEXT      NOT NULL,
+    version         TEXT      NOT NULL COLLATE NOCASE, -- Report version the milestone applies to
+    measure         TEXT      NOT NULL, -- Target measure (matched_code, complete_code, ...)
+    target          REAL      NOT NULL, -- Target percentage of the measure
+    category        TEXT,               -- Progress category scope
+    unit_prefix     TEXT,               -- Unit name prefix scope
+    due_date        TIMESTAMP,
+    achieved_at     TIMESTAMP,          -- Timestamp of the first commit that reached the target
+    achieved_commit TEXT,
+    created_at      TIMESTAMP NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects (id) ON DELETE CASCADE
+);
+
+CREATE INDEX milestones_project_id_index ON milestones (project_id, version);
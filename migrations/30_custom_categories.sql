@@ -0,0 +1,20 @@
+CREATE TABLE project_categories
+(
+    project_id INTEGER   NOT NULL,
+    id         TEXT      NOT NULL COLLATE NOCASE,
+    name       TEXT      NOT NULL,
+    rules      TEXT      NOT NULL, -- JSON array of unit name rules (globs, or regexes prefixed with "re:")
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (project_id, id),
+    FOREIGN KEY (project_id) REFERENCES projects (id) ON DELETE CASCADE
+);
+
+-- Measures of project categories, aggregated from the units of each report
+CREATE TABLE report_custom_categories
+(
+    report_id   INTEGER NOT NULL,
+    category_id TEXT    NOT NULL COLLATE NOCASE,
+    measures    BLOB    NOT NULL, -- Protobuf-encoded Measures
+    PRIMARY KEY (report_id, category_id),
+    FOREIGN KEY (report_id) REFERENCES reports (id) ON DELETE CASCADE
+);
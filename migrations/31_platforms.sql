@@ -0,0 +1,25 @@
+CREATE TABLE platforms
+(
+    id            TEXT PRIMARY KEY COLLATE NOCASE, -- Referenced by projects.platform
+    name          TEXT      NOT NULL,
+    year          INTEGER,                         -- Release year
+    icon_image_id BLOB,                            -- Uploaded icon, otherwise the icon font is used
+    sort_order    INTEGER   NOT NULL DEFAULT 0,
+    created_at    TIMESTAMP NOT NULL,
+    updated_at    TIMESTAMP NOT NULL
+);
+
+INSERT INTO platforms (id, name, year, sort_order, created_at, updated_at)
+VALUES ('ps', 'PlayStation', 1994, 10, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('win32', 'Windows', 1995, 20, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('n64', 'Nintendo 64', 1996, 30, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('ps2', 'PlayStation 2', 2000, 40, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('gba', 'Game Boy Advance', 2001, 50, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('gc', 'GameCube', 2001, 60, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('xbox', 'Xbox', 2001, 70, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('nds', 'Nintendo DS', 2004, 80, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('psp', 'PlayStation Portable', 2004, 90, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('xbox360', 'Xbox 360', 2005, 100, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('wii', 'Wii', 2006, 110, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('3ds', 'Nintendo 3DS', 2011, 120, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+       ('switch', 'Switch', 2017, 130, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP);
@@ -0,0 +1,25 @@
+CREATE TABLE project_tags
+(
+    project_id INTEGER NOT NULL,
+    tag        TEXT    NOT NULL COLLATE NOCASE,
+    PRIMARY KEY (project_id, tag),
+    FOREIGN KEY (project_id) REFERENCES projects (id) ON DELETE CASCADE
+);
+
+CREATE TABLE project_groups
+(
+    id          TEXT PRIMARY KEY COLLATE NOCASE, -- Used in the group page URL
+    name        TEXT      NOT NULL,
+    description TEXT,
+    created_at  TIMESTAMP NOT NULL,
+    updated_at  TIMESTAMP NOT NULL
+);
+
+CREATE TABLE project_group_members
+(
+    group_id   TEXT    NOT NULL COLLATE NOCASE,
+    project_id INTEGER NOT NULL,
+    PRIMARY KEY (group_id, project_id),
+    FOREIGN KEY (group_id) REFERENCES project_groups (id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects (id) ON DELETE CASCADE
+);
@@ -0,0 +1,18 @@
+-- Projects were keyed by their GitHub repository ID. Existing projects keep their ID and
+-- become the primary project of their repository.
+CREATE TABLE project_repositories
+(
+    project_id      INTEGER PRIMARY KEY,
+    repository_id   INTEGER NOT NULL, -- GitHub repository ID
+    slug            TEXT COLLATE NOCASE, -- NULL for the primary project of the repository
+    artifact_prefix TEXT,                -- Imported artifact versions must start with this prefix
+    version_pattern TEXT,                -- Imported versions must match this glob or regex
+    FOREIGN KEY (project_id) REFERENCES projects (id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX project_repositories_repository_id_slug_index
+    ON project_repositories (repository_id, coalesce(slug, ''));
+
+INSERT INTO project_repositories (project_id, repository_id)
+SELECT id, id
+FROM projects;
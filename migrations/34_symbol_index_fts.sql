@@ -0,0 +1,27 @@
+-- Trigram index over symbol names, so substring searches don't scan symbol_index
+CREATE VIRTUAL TABLE symbol_index_fts USING fts5
+(
+    name,
+    demangled_name,
+    content = 'symbol_index',
+    content_rowid = 'rowid',
+    tokenize = 'trigram'
+);
+
+INSERT INTO symbol_index_fts (symbol_index_fts) VALUES ('rebuild');
+
+CREATE TRIGGER symbol_index_fts_insert
+    AFTER INSERT
+    ON symbol_index
+BEGIN
+    INSERT INTO symbol_index_fts (rowid, name, demangled_name)
+    VALUES (new.rowid, new.name, new.demangled_name);
+END;
+
+CREATE TRIGGER symbol_index_fts_delete
+    AFTER DELETE
+    ON symbol_index
+BEGIN
+    INSERT INTO symbol_index_fts (symbol_index_fts, rowid, name, demangled_name)
+    VALUES ('delete', old.rowid, old.name, old.demangled_name);
+END;
@@ -0,0 +1 @@
+ALTER TABLE projects ADD COLUMN categories_version INTEGER NOT NULL DEFAULT 0; -- Incremented whenever project categories or their measures change
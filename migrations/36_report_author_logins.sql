@@ -0,0 +1,13 @@
+-- Attribute commits by authors without a linked GitHub account to the login recorded for the
+-- same author name elsewhere in the project
+UPDATE reports
+SET git_author_login = (
+    SELECT r.git_author_login
+    FROM reports r
+    WHERE r.project_id = reports.project_id
+      AND r.git_author_name = reports.git_author_name
+      AND r.git_author_login IS NOT NULL
+    ORDER BY r.timestamp DESC
+    LIMIT 1
+)
+WHERE git_author_login IS NULL AND git_author_name IS NOT NULL;